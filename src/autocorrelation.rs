@@ -0,0 +1,225 @@
+use super::{Estimate, Reset, Variance};
+
+/// Estimate the autocorrelation of a sequence of numbers ("time series") for
+/// lags up to `MAX_LAG`.
+///
+/// The lag-`k` autocorrelation is estimated as
+/// `sum_t (x_t - m)(x_{t-k} - m) / sum_t (x_t - m)^2`, where `m` is the mean
+/// of the whole sequence. This is the estimator commonly used for the
+/// autocorrelation function of a time series; it always lies in `[-1, 1]`.
+///
+/// Only the first and the last `MAX_LAG` observations are stored, so the
+/// memory and the time per observation are proportional to `MAX_LAG`.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Autocorrelation;
+///
+/// let a: Autocorrelation<2> = [1., -1., 1., -1., 1., -1.].iter().collect();
+/// assert_eq!(a.autocorrelation(0), 1.);
+/// assert!((a.autocorrelation(1) - -5./6.).abs() < 1e-14);
+/// assert!((a.autocorrelation(2) - 4./6.).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Autocorrelation<const MAX_LAG: usize = 10> {
+    /// Estimator of the mean and the sum of squares.
+    var: Variance,
+    /// The first observation, which is subtracted from all observations to
+    /// reduce cancellation in the sums below.
+    shift: f64,
+    /// The sum of the shifted observations.
+    sum: f64,
+    /// The sums of the products of shifted observations `k + 1` apart.
+    sum_prod: [f64; MAX_LAG],
+    /// The first `MAX_LAG` shifted observations.
+    first: [f64; MAX_LAG],
+    /// The last `MAX_LAG` shifted observations.
+    window: [f64; MAX_LAG],
+    /// The index of the oldest observation in the window, which is replaced
+    /// next.
+    next: usize,
+}
+
+impl<const MAX_LAG: usize> Autocorrelation<MAX_LAG> {
+    /// Create a new autocorrelation estimator.
+    #[inline]
+    pub fn new() -> Autocorrelation<MAX_LAG> {
+        Autocorrelation {
+            var: Variance::new(),
+            shift: 0.,
+            sum: 0.,
+            sum_prod: [0.; MAX_LAG],
+            first: [0.; MAX_LAG],
+            window: [0.; MAX_LAG],
+            next: 0,
+        }
+    }
+
+    /// Return the largest lag for which the autocorrelation is estimated.
+    #[inline]
+    pub fn max_lag(&self) -> usize {
+        MAX_LAG
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.var.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.var.is_empty()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.var.mean()
+    }
+
+    /// Return the shifted observation `lag` steps before the next one, where
+    /// `1 <= lag <= MAX_LAG`.
+    #[inline]
+    fn previous(&self, lag: usize) -> f64 {
+        self.window[(self.next + MAX_LAG - lag) % MAX_LAG]
+    }
+
+    /// Add an observation.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        let n = self.var.len() as usize;
+        if n == 0 {
+            self.shift = x;
+        }
+        self.var.add(x);
+        if MAX_LAG == 0 {
+            return;
+        }
+        let y = x - self.shift;
+        self.sum += y;
+        for lag in 1..=n.min(MAX_LAG) {
+            self.sum_prod[lag - 1] += y * self.previous(lag);
+        }
+        if n < MAX_LAG {
+            self.first[n] = y;
+        }
+        self.window[self.next] = y;
+        self.next = (self.next + 1) % MAX_LAG;
+    }
+
+    /// Estimate the autocorrelation for the given lag.
+    ///
+    /// The autocorrelation for lag 0 is 1. Returns NaN if `lag` is not smaller
+    /// than the sample size, or if all observations are equal.
+    ///
+    /// Panics if `lag` is larger than `MAX_LAG`.
+    #[inline]
+    pub fn autocorrelation(&self, lag: usize) -> f64 {
+        assert!(lag <= MAX_LAG, "The lag must not be larger than the maximum lag");
+        let n = self.var.len();
+        if lag as u64 >= n {
+            return f64::NAN;
+        }
+        let n = n as f64;
+        let sum_2 = self.var.population_variance() * n;
+        if sum_2 == 0. {
+            return f64::NAN;
+        }
+        if lag == 0 {
+            return 1.;
+        }
+        // Expand `sum_t (y_t - m)(y_{t-k} - m)` for the shifted observations
+        // `y`, where the first sum of `y_t` misses the first `k` observations
+        // and the second one the last `k` observations.
+        let mean = self.sum / n;
+        let sum_head: f64 = self.first[..lag].iter().sum();
+        let sum_tail: f64 = (1..=lag).map(|k| self.previous(k)).sum();
+        let sum_later = self.sum - sum_head;
+        let sum_earlier = self.sum - sum_tail;
+        let covariance = self.sum_prod[lag - 1] - mean * (sum_later + sum_earlier)
+            + (n - lag as f64) * mean * mean;
+        covariance / sum_2
+    }
+}
+
+impl<const MAX_LAG: usize> core::default::Default for Autocorrelation<MAX_LAG> {
+    fn default() -> Autocorrelation<MAX_LAG> {
+        Autocorrelation::new()
+    }
+}
+
+impl<const MAX_LAG: usize> Reset for Autocorrelation<MAX_LAG> {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Autocorrelation::new();
+    }
+}
+
+impl<const MAX_LAG: usize> Estimate for Autocorrelation<MAX_LAG> {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    /// Estimate the lag-1 autocorrelation.
+    ///
+    /// Panics if `MAX_LAG` is zero.
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.autocorrelation(1)
+    }
+}
+
+impl<const MAX_LAG: usize> core::iter::FromIterator<f64> for Autocorrelation<MAX_LAG> {
+    fn from_iter<T>(iter: T) -> Autocorrelation<MAX_LAG>
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut e = Autocorrelation::new();
+        for x in iter {
+            e.add(x);
+        }
+        e
+    }
+}
+
+impl<'a, const MAX_LAG: usize> core::iter::FromIterator<&'a f64> for Autocorrelation<MAX_LAG> {
+    fn from_iter<T>(iter: T) -> Autocorrelation<MAX_LAG>
+    where
+        T: IntoIterator<Item = &'a f64>,
+    {
+        let mut e = Autocorrelation::new();
+        for &x in iter {
+            e.add(x);
+        }
+        e
+    }
+}
+
+impl<const MAX_LAG: usize> core::iter::Extend<f64> for Autocorrelation<MAX_LAG> {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl<'a, const MAX_LAG: usize> core::iter::Extend<&'a f64> for Autocorrelation<MAX_LAG> {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = &'a f64>,
+    {
+        for &x in iter {
+            self.add(x);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use num_traits::Float;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+use super::{Estimate, Mean, Merge};
+
+/// Estimate the circular mean of a sequence of angles given in radians.
+///
+/// The angles are averaged as unit vectors, so angles on both sides of the
+/// boundary between `2π` and `0` are handled correctly. The resulting mean
+/// direction is given in `(-π, π]`.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::CircularMean;
+///
+/// let a: CircularMean = [10f64.to_radians(), 350f64.to_radians()].iter().collect();
+/// assert!(a.circular_mean().abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct CircularMean {
+    /// Estimator of the mean of the sines.
+    sin: Mean,
+    /// Estimator of the mean of the cosines.
+    cos: Mean,
+}
+
+impl CircularMean {
+    /// Create a new circular mean estimator.
+    #[inline]
+    pub fn new() -> CircularMean {
+        CircularMean {
+            sin: Mean::new(),
+            cos: Mean::new(),
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sin.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.sin.len()
+    }
+
+    /// Estimate the mean direction of the population in radians.
+    ///
+    /// This is `atan2(mean(sin), mean(cos))`, in `(-π, π]`.
+    ///
+    /// Returns NaN for an empty sample, and if the mean resultant length is
+    /// zero, such that the direction is undefined.
+    #[inline]
+    pub fn circular_mean(&self) -> f64 {
+        if self.mean_resultant_length() == 0. {
+            return f64::NAN;
+        }
+        Float::atan2(self.sin.mean(), self.cos.mean())
+    }
+
+    /// Calculate the mean resultant length `R`, the length of the average of
+    /// the angles as unit vectors.
+    ///
+    /// It is between 0 (angles spread out) and 1 (all angles equal).
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn mean_resultant_length(&self) -> f64 {
+        Float::hypot(self.sin.mean(), self.cos.mean())
+    }
+
+    /// Calculate the circular variance `1 - R`, where `R` is the mean
+    /// resultant length.
+    ///
+    /// It is between 0 (all angles equal) and 1 (angles spread out).
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn circular_variance(&self) -> f64 {
+        1. - self.mean_resultant_length()
+    }
+}
+
+impl core::default::Default for CircularMean {
+    fn default() -> CircularMean {
+        CircularMean::new()
+    }
+}
+
+impl Estimate for CircularMean {
+    #[inline]
+    fn add(&mut self, angle: f64) {
+        let (sin, cos) = Float::sin_cos(angle);
+        self.sin.add(sin);
+        self.cos.add(cos);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.circular_mean()
+    }
+}
+
+impl Merge for CircularMean {
+    #[inline]
+    fn merge(&mut self, other: &CircularMean) {
+        self.sin.merge(&other.sin);
+        self.cos.merge(&other.cos);
+    }
+}
+
+impl_add_assign!(CircularMean);
+impl_reset!(CircularMean);
+impl_from_iterator!(CircularMean);
+impl_from_par_iterator!(CircularMean);
+impl_extend!(CircularMean);
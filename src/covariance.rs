@@ -4,6 +4,28 @@ use serde_derive::{Deserialize, Serialize};
 
 use crate::Merge;
 
+/// The slices of `x` and `y` values have different lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MismatchedSliceLengths {
+    /// The length of the slice of `x` values.
+    pub expected: usize,
+    /// The length of the slice of `y` values.
+    pub got: usize,
+}
+
+impl core::fmt::Display for MismatchedSliceLengths {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "mismatched slice lengths: expected {} y values, got {}",
+            self.expected, self.got
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MismatchedSliceLengths {}
+
 /// Estimate the arithmetic means and the covariance of a sequence of number pairs
 /// ("population").
 ///
@@ -66,6 +88,61 @@ impl Covariance {
         self.sum_prod += delta_x * (y - self.avg_y);
     }
 
+    /// Add the observations `(xs[i], ys[i])` sampled from the population.
+    ///
+    /// Fails without adding anything if the slices have different lengths.
+    #[inline]
+    pub fn add_from_slice(&mut self, xs: &[f64], ys: &[f64]) -> Result<(), MismatchedSliceLengths> {
+        if xs.len() != ys.len() {
+            return Err(MismatchedSliceLengths {
+                expected: xs.len(),
+                got: ys.len(),
+            });
+        }
+        for (&x, &y) in xs.iter().zip(ys) {
+            self.add(x, y);
+        }
+        Ok(())
+    }
+
+    /// Add the observations `(xs[i], ys[i])` sampled from the population.
+    ///
+    /// This is an alias for `add_from_slice()`.
+    #[inline]
+    pub fn extend_from_slices(&mut self, xs: &[f64], ys: &[f64]) -> Result<(), MismatchedSliceLengths> {
+        self.add_from_slice(xs, ys)
+    }
+
+    /// Replace the observation `(x_old, y_old)` by `(x_new, y_new)`, keeping
+    /// the sample size.
+    ///
+    /// This is useful for a sliding window of fixed size, see
+    /// [`MovingCovariance`]. `(x_old, y_old)` is assumed to be part of the
+    /// sample, otherwise the results are meaningless.
+    ///
+    /// Panics if the sample is empty.
+    ///
+    /// [`MovingCovariance`]: ./struct.MovingCovariance.html
+    #[inline]
+    pub fn update_streaming(&mut self, (x_old, y_old): (f64, f64), (x_new, y_new): (f64, f64)) {
+        assert!(self.n > 0, "Cannot remove a sample from an empty estimator");
+        let n = self.n.to_f64().unwrap();
+        let delta_x = x_new - x_old;
+        let delta_y = y_new - y_old;
+        let (old_x, old_y) = (x_old - self.avg_x, y_old - self.avg_y);
+        let (new_x, new_y) = (x_new - self.avg_x, y_new - self.avg_y);
+
+        self.avg_x += delta_x / n;
+        self.sum_x_2 += delta_x * (new_x + old_x) - delta_x * delta_x / n;
+        self.avg_y += delta_y / n;
+        self.sum_y_2 += delta_y * (new_y + old_y) - delta_y * delta_y / n;
+        self.sum_prod += new_x * new_y - old_x * old_y - delta_x * delta_y / n;
+
+        // Avoid negative variances due to rounding errors.
+        self.sum_x_2 = self.sum_x_2.max(0.);
+        self.sum_y_2 = self.sum_y_2.max(0.);
+    }
+
     /// Calculate the population covariance of the sample.
     ///
     /// This is a biased estimator of the covariance of the population.
@@ -105,6 +182,69 @@ impl Covariance {
         self.sum_prod / num_traits::Float::sqrt(self.sum_x_2 * self.sum_y_2)
     }
 
+    /// Calculate the z-score of `x`, i.e. its distance from the mean of `x` in
+    /// units of the sample standard deviation of `x`.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn z_score_x(&self, x: f64) -> f64 {
+        (x - self.mean_x()) / num_traits::Float::sqrt(self.sample_variance_x())
+    }
+
+    /// Calculate the z-score of `y`, i.e. its distance from the mean of `y` in
+    /// units of the sample standard deviation of `y`.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn z_score_y(&self, y: f64) -> f64 {
+        (y - self.mean_y()) / num_traits::Float::sqrt(self.sample_variance_y())
+    }
+
+    /// Calculate the Mahalanobis distance of `(x, y)` from the means, using
+    /// the sample covariance matrix.
+    ///
+    /// This generalizes the z-score to two correlated variables.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if the covariance matrix
+    /// is singular.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn mahalanobis_distance(&self, x: f64, y: f64) -> f64 {
+        if self.n < 2 {
+            return f64::NAN;
+        }
+        // The sample size cancels, so we can use the sums directly.
+        let det = self.sum_x_2 * self.sum_y_2 - self.sum_prod * self.sum_prod;
+        if det <= 0. {
+            return f64::NAN;
+        }
+        let dx = x - self.avg_x;
+        let dy = y - self.avg_y;
+        let n_1 = (self.n - 1).to_f64().unwrap();
+        let d_2 = (self.sum_y_2 * dx * dx - 2. * self.sum_prod * dx * dy + self.sum_x_2 * dy * dy)
+            / det;
+        num_traits::Float::sqrt(n_1 * d_2)
+    }
+
+    /// Calculate the partial correlation coefficient of `x` and `y`,
+    /// controlling for `z`.
+    ///
+    /// `self` is the covariance of `x` and `y`, `xz` the one of `x` and `z` and
+    /// `yz` the one of `y` and `z`. See [`partial_correlation`].
+    ///
+    /// [`partial_correlation`]: ./fn.partial_correlation.html
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn partial_correlation_with(&self, xz: &Covariance, yz: &Covariance) -> f64 {
+        partial_correlation(self, xz, yz)
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
@@ -185,15 +325,219 @@ impl Covariance {
         self.sum_y_2 / self.n.to_f64().unwrap()
     }
 
+    /// Calculate the slope of the ordinary least squares regression line of
+    /// `y` on `x`.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        if self.n < 2 || self.sum_x_2 == 0. {
+            return f64::NAN;
+        }
+        self.sum_prod / self.sum_x_2
+    }
+
+    /// Calculate the intercept of the ordinary least squares regression line
+    /// of `y` on `x`.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn intercept(&self) -> f64 {
+        self.mean_y() - self.slope() * self.mean_x()
+    }
+
+    /// Calculate the coefficient of determination of the ordinary least
+    /// squares regression line, i.e. the square of the Pearson correlation
+    /// coefficient.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` or all `y` are
+    /// equal.
+    #[inline]
+    pub fn r_squared(&self) -> f64 {
+        let denominator = self.sum_x_2 * self.sum_y_2;
+        if self.n < 2 || denominator == 0. {
+            return f64::NAN;
+        }
+        self.sum_prod * self.sum_prod / denominator
+    }
+
+    /// Predict `y` at `x` using the ordinary least squares regression line.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn prediction(&self, x: f64) -> f64 {
+        self.intercept() + self.slope() * x
+    }
+
+    /// Calculate the population variance of the residuals of the ordinary
+    /// least squares regression line, i.e. `(1 - r²)` times the population
+    /// variance of `y`.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn residual_variance(&self) -> f64 {
+        if self.n < 2 || self.sum_x_2 == 0. {
+            return f64::NAN;
+        }
+        // This equals `(1 - r²) * sum_y_2`, but it is also defined if all `y`
+        // are equal.
+        let sum_residuals_2 = self.sum_y_2 - self.sum_prod * self.sum_prod / self.sum_x_2;
+        sum_residuals_2.max(0.) / self.n.to_f64().unwrap()
+    }
+
+    /// Calculate the population standard deviation of the residuals of the
+    /// ordinary least squares regression line.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn residual_std(&self) -> f64 {
+        num_traits::Float::sqrt(self.residual_variance())
+    }
+
+    /// Estimate the standard error of the slope of the ordinary least squares
+    /// regression line.
+    ///
+    /// This assumes that the residuals are independent and normally
+    /// distributed with constant variance.
+    ///
+    /// Returns NaN for samples of size 2 or less, or if all `x` or all `y` are
+    /// equal.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn standard_error_of_slope(&self) -> f64 {
+        if self.n < 3 {
+            return f64::NAN;
+        }
+        let residual_variance = (1. - self.r_squared()) * self.sample_variance_y();
+        let n_2 = (self.n - 2).to_f64().unwrap();
+        num_traits::Float::sqrt(residual_variance / (n_2 * self.sample_variance_x()))
+    }
+
+    /// Return the degrees of freedom of the residuals of the ordinary least
+    /// squares regression line, i.e. `n - 2`.
+    ///
+    /// Returns 0 for samples of size 2 or less.
+    #[inline]
+    pub fn degrees_of_freedom(&self) -> u64 {
+        self.n.saturating_sub(2)
+    }
+
+    /// Calculate the t-statistic `slope / standard_error_of_slope` for the
+    /// hypothesis that the slope of the regression line is zero.
+    ///
+    /// Under the hypothesis, this follows Student's t-distribution with
+    /// `degrees_of_freedom()` degrees of freedom. The critical values can be
+    /// calculated with [`t_critical`].
+    ///
+    /// Returns NaN for samples of size 2 or less, or if all `x` or all `y` are
+    /// equal.
+    ///
+    /// [`t_critical`]: ./fn.t_critical.html
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn t_statistic_slope(&self) -> f64 {
+        self.slope() / self.standard_error_of_slope()
+    }
+
+    /// Calculate the two-sided confidence interval of the slope of the
+    /// regression line at the given confidence level (for example 0.95),
+    /// using the critical value of Student's t-distribution.
+    ///
+    /// See [`t_critical`] and `standard_error_of_slope`.
+    ///
+    /// Returns NaN for samples of size 2 or less, or if all `x` or all `y` are
+    /// equal.
+    ///
+    /// [`t_critical`]: ./fn.t_critical.html
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn confidence_interval_slope(&self, confidence: f64) -> (f64, f64) {
+        // The slope of constant `y` is zero, but the standard error is
+        // undefined, so we do not return the degenerate interval `(0, 0)`.
+        if self.n < 3 || self.sum_x_2 == 0. || self.sum_y_2 == 0. {
+            return (f64::NAN, f64::NAN);
+        }
+        let error = crate::t_critical(self.degrees_of_freedom(), confidence)
+            * self.standard_error_of_slope();
+        let slope = self.slope();
+        (slope - error, slope + error)
+    }
+
     // TODO: Standard deviation and standard error
 }
 
+/// Calculate the partial correlation coefficient of `x` and `y`, controlling
+/// for `z`.
+///
+/// The arguments are the covariances of `x` and `y`, `x` and `z` and `y` and
+/// `z`, which are expected to be estimated from the same sample. The partial
+/// correlation is calculated from the Pearson correlation coefficients:
+///
+/// ```text
+/// (ρ_xy - ρ_xz ρ_yz) / sqrt((1 - ρ_xz²) (1 - ρ_yz²))
+/// ```
+///
+/// Returns NaN for samples of size 1 or less, and if `z` is perfectly
+/// correlated with `x` or `y`.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{partial_correlation, Covariance};
+///
+/// let (mut xy, mut xz, mut yz) = (Covariance::new(), Covariance::new(), Covariance::new());
+/// for &(x, y, z) in &[(1., 2., 1.), (2., 1., 3.), (3., 5., 2.), (4., 3., 5.), (5., 6., 4.)] {
+///     xy.add(x, y);
+///     xz.add(x, z);
+///     yz.add(y, z);
+/// }
+/// println!("The partial correlation is {}.", partial_correlation(&xy, &xz, &yz));
+/// ```
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+#[inline]
+pub fn partial_correlation(xy: &Covariance, xz: &Covariance, yz: &Covariance) -> f64 {
+    let r_xy = xy.pearson();
+    let r_xz = xz.pearson();
+    let r_yz = yz.pearson();
+    let denominator = num_traits::Float::sqrt((1. - r_xz * r_xz) * (1. - r_yz * r_yz));
+    if denominator == 0. {
+        return f64::NAN;
+    }
+    (r_xy - r_xz * r_yz) / denominator
+}
+
 impl core::default::Default for Covariance {
     fn default() -> Covariance {
         Covariance::new()
     }
 }
 
+impl PartialEq for Covariance {
+    /// Compare the sample sizes, the means and the sums of squares and
+    /// products of both estimators.
+    ///
+    /// The sums are subject to rounding errors, so estimators of the same
+    /// samples might compare unequal if the samples were added in a different
+    /// order or merged. Following `f64`, estimators containing NaN are not
+    /// equal, and `0.0` equals `-0.0`.
+    #[inline]
+    fn eq(&self, other: &Covariance) -> bool {
+        self.avg_x == other.avg_x
+            && self.sum_x_2 == other.sum_x_2
+            && self.avg_y == other.avg_y
+            && self.sum_y_2 == other.sum_y_2
+            && self.sum_prod == other.sum_prod
+            && self.n == other.n
+    }
+}
+
 impl Merge for Covariance {
     /// Merge another sample into this one.
     ///
@@ -238,6 +582,28 @@ impl Merge for Covariance {
     }
 }
 
+impl_add_assign!(Covariance);
+impl_reset!(Covariance);
+
+impl core::ops::Neg for Covariance {
+    type Output = Covariance;
+
+    /// Negate both estimated means, as if all samples `(x, y)` had been
+    /// replaced by `(-x, -y)`.
+    ///
+    /// The variances are not affected. Because both variables are negated,
+    /// the signs of the cross term cancel and the covariance is not affected
+    /// either.
+    #[inline]
+    fn neg(self) -> Covariance {
+        Covariance {
+            avg_x: -self.avg_x,
+            avg_y: -self.avg_y,
+            ..self
+        }
+    }
+}
+
 impl core::iter::FromIterator<(f64, f64)> for Covariance {
     fn from_iter<T>(iter: T) -> Covariance
         where
@@ -279,3 +645,506 @@ impl<'a> core::iter::Extend<&'a (f64, f64)> for Covariance {
         }
     }
 }
+
+/// Estimate the weighted arithmetic means and the weighted covariance of a
+/// sequence of number pairs ("population").
+///
+/// The algorithm was suggested by West in 1979 for the weighted variance and
+/// is extended here to the covariance. Because the weighted variances are
+/// calculated as well, this can be used to calculate the weighted Pearson
+/// correlation coefficient.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedCovariance;
+///
+/// let a: WeightedCovariance = [(1., 5., 1.), (2., 4., 2.), (3., 3., 1.)].iter().collect();
+/// assert_eq!(a.mean_x(), 2.);
+/// assert_eq!(a.mean_y(), 4.);
+/// assert!((a.weighted_covariance() - -0.5).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WeightedCovariance {
+    weight_sum: f64,
+    avg_x: f64,
+    sum_x_2: f64,
+    avg_y: f64,
+    sum_y_2: f64,
+    sum_prod: f64,
+}
+
+impl WeightedCovariance {
+    /// Create a new weighted covariance estimator.
+    #[inline]
+    pub fn new() -> WeightedCovariance {
+        WeightedCovariance {
+            weight_sum: 0.,
+            avg_x: 0.,
+            sum_x_2: 0.,
+            avg_y: 0.,
+            sum_y_2: 0.,
+            sum_prod: 0.,
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64, weight: f64) {
+        // See West, D. H. D. (1979). "Updating mean and variance estimates: an
+        // improved method". Communications of the ACM 22 (9): 532–535.
+        self.weight_sum += weight;
+        let ratio = weight / self.weight_sum;
+
+        let delta_x = x - self.avg_x;
+        self.avg_x += ratio * delta_x;
+        self.sum_x_2 += weight * delta_x * (x - self.avg_x);
+
+        let delta_y = y - self.avg_y;
+        self.avg_y += ratio * delta_y;
+        self.sum_y_2 += weight * delta_y * (y - self.avg_y);
+
+        self.sum_prod += weight * delta_x * (y - self.avg_y);
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// Might be a false positive if the sum of weights is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weight_sum == 0.
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weight_sum
+    }
+
+    /// Estimate the weighted mean of the `x` population.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn mean_x(&self) -> f64 {
+        if self.is_empty() { f64::NAN } else { self.avg_x }
+    }
+
+    /// Estimate the weighted mean of the `y` population.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn mean_y(&self) -> f64 {
+        if self.is_empty() { f64::NAN } else { self.avg_y }
+    }
+
+    /// Calculate the weighted population variance of the `x` sample.
+    ///
+    /// This reduces to the unweighted population variance if all weights are
+    /// equal.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn weighted_variance_x(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        self.sum_x_2 / self.weight_sum
+    }
+
+    /// Calculate the weighted population variance of the `y` sample.
+    ///
+    /// This reduces to the unweighted population variance if all weights are
+    /// equal.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn weighted_variance_y(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        self.sum_y_2 / self.weight_sum
+    }
+
+    /// Calculate the weighted population covariance of the sample.
+    ///
+    /// This reduces to the unweighted population covariance if all weights are
+    /// equal.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn weighted_covariance(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        self.sum_prod / self.weight_sum
+    }
+
+    /// Calculate the weighted Pearson correlation coefficient.
+    ///
+    /// Returns NaN for an empty sample, or if all `x` or all `y` are equal.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn weighted_pearson(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        self.sum_prod / num_traits::Float::sqrt(self.sum_x_2 * self.sum_y_2)
+    }
+}
+
+impl core::default::Default for WeightedCovariance {
+    fn default() -> WeightedCovariance {
+        WeightedCovariance::new()
+    }
+}
+
+impl Merge for WeightedCovariance {
+    /// Merge another sample into this one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{WeightedCovariance, Merge};
+    ///
+    /// let sequence: &[(f64, f64, f64)] = &[
+    ///     (1., 2., 0.1), (3., 5., 0.2), (5., 6., 0.3), (7., 7., 0.4), (9., 10., 0.5)];
+    /// let (left, right) = sequence.split_at(3);
+    /// let cov_total: WeightedCovariance = sequence.iter().collect();
+    /// let mut cov_left: WeightedCovariance = left.iter().collect();
+    /// let cov_right: WeightedCovariance = right.iter().collect();
+    /// cov_left.merge(&cov_right);
+    /// assert!((cov_total.weighted_covariance() - cov_left.weighted_covariance()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &WeightedCovariance) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+
+        let delta_x = other.avg_x - self.avg_x;
+        let delta_y = other.avg_y - self.avg_y;
+        let weight_total = self.weight_sum + other.weight_sum;
+        let factor = self.weight_sum * other.weight_sum / weight_total;
+
+        self.avg_x = (self.weight_sum * self.avg_x + other.weight_sum * other.avg_x) / weight_total;
+        self.sum_x_2 += other.sum_x_2 + delta_x * delta_x * factor;
+
+        self.avg_y = (self.weight_sum * self.avg_y + other.weight_sum * other.avg_y) / weight_total;
+        self.sum_y_2 += other.sum_y_2 + delta_y * delta_y * factor;
+
+        self.sum_prod += other.sum_prod + delta_x * delta_y * factor;
+
+        self.weight_sum = weight_total;
+    }
+}
+
+impl_add_assign!(WeightedCovariance);
+impl_reset!(WeightedCovariance);
+
+impl core::iter::FromIterator<(f64, f64, f64)> for WeightedCovariance {
+    fn from_iter<T>(iter: T) -> WeightedCovariance
+        where
+            T: IntoIterator<Item = (f64, f64, f64)>,
+    {
+        let mut cov = WeightedCovariance::new();
+        for (x, y, w) in iter {
+            cov.add(x, y, w);
+        }
+        cov
+    }
+}
+
+impl core::iter::Extend<(f64, f64, f64)> for WeightedCovariance {
+    fn extend<T: IntoIterator<Item = (f64, f64, f64)>>(&mut self, iter: T) {
+        for (x, y, w) in iter {
+            self.add(x, y, w);
+        }
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64, f64)> for WeightedCovariance {
+    fn from_iter<T>(iter: T) -> WeightedCovariance
+        where
+            T: IntoIterator<Item = &'a (f64, f64, f64)>,
+    {
+        let mut cov = WeightedCovariance::new();
+        for &(x, y, w) in iter {
+            cov.add(x, y, w);
+        }
+        cov
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64, f64)> for WeightedCovariance {
+    fn extend<T: IntoIterator<Item = &'a (f64, f64, f64)>>(&mut self, iter: T) {
+        for &(x, y, w) in iter {
+            self.add(x, y, w);
+        }
+    }
+}
+
+/// Fit a line to a sequence of number pairs `(x, y)` using ordinary least
+/// squares.
+///
+/// This is a thin wrapper around [`Covariance`](./struct.Covariance.html)
+/// exposing only the regression statistics.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::LinearRegression;
+///
+/// let a: LinearRegression = [(1., 1.), (2., 3.), (3., 5.)].iter().collect();
+/// assert_eq!(a.slope(), 2.);
+/// assert_eq!(a.intercept(), -1.);
+/// assert_eq!(a.predict(4.), 7.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LinearRegression {
+    cov: Covariance,
+}
+
+impl LinearRegression {
+    /// Create a new linear regression estimator.
+    #[inline]
+    pub fn new() -> LinearRegression {
+        LinearRegression {
+            cov: Covariance::new(),
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        self.cov.add(x, y);
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.cov.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cov.is_empty()
+    }
+
+    /// Return the underlying covariance estimator.
+    #[inline]
+    pub fn covariance(&self) -> &Covariance {
+        &self.cov
+    }
+
+    /// Calculate the slope of the regression line.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        self.cov.slope()
+    }
+
+    /// Calculate the intercept of the regression line.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn intercept(&self) -> f64 {
+        self.cov.intercept()
+    }
+
+    /// Calculate the coefficient of determination `r²`.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` or all `y` are
+    /// equal.
+    #[inline]
+    pub fn r_squared(&self) -> f64 {
+        self.cov.r_squared()
+    }
+
+    /// Predict `y` at `x` using the regression line.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[inline]
+    pub fn predict(&self, x: f64) -> f64 {
+        self.cov.prediction(x)
+    }
+
+    /// Calculate the population standard deviation of the residuals, see
+    /// [`Covariance::residual_std`](./struct.Covariance.html#method.residual_std).
+    ///
+    /// Returns NaN for samples of size 1 or less, or if all `x` are equal.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn residual_std(&self) -> f64 {
+        self.cov.residual_std()
+    }
+}
+
+impl core::default::Default for LinearRegression {
+    fn default() -> LinearRegression {
+        LinearRegression::new()
+    }
+}
+
+impl Merge for LinearRegression {
+    #[inline]
+    fn merge(&mut self, other: &LinearRegression) {
+        self.cov.merge(&other.cov);
+    }
+}
+
+impl_add_assign!(LinearRegression);
+impl_reset!(LinearRegression);
+
+impl core::iter::FromIterator<(f64, f64)> for LinearRegression {
+    fn from_iter<T>(iter: T) -> LinearRegression
+        where
+            T: IntoIterator<Item = (f64, f64)>,
+    {
+        LinearRegression {
+            cov: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for LinearRegression {
+    fn extend<T: IntoIterator<Item = (f64, f64)>>(&mut self, iter: T) {
+        self.cov.extend(iter);
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for LinearRegression {
+    fn from_iter<T>(iter: T) -> LinearRegression
+        where
+            T: IntoIterator<Item = &'a (f64, f64)>,
+    {
+        LinearRegression {
+            cov: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for LinearRegression {
+    fn extend<T: IntoIterator<Item = &'a (f64, f64)>>(&mut self, iter: T) {
+        self.cov.extend(iter);
+    }
+}
+
+/// Estimate the covariance of the last `W` number pairs of a sequence.
+///
+/// The last `W` pairs are stored in a ring buffer, such that the oldest pair
+/// can be removed from the estimate when a new one is added.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::MovingCovariance;
+///
+/// let mut a = MovingCovariance::<3>::new();
+/// for &(x, y) in &[(10., 0.), (1., 5.), (2., 4.), (3., 3.)] {
+///     a.add(x, y);
+/// }
+/// assert!(a.is_full());
+/// assert_eq!(a.covariance(), -1.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MovingCovariance<const W: usize> {
+    /// The last `W` pairs.
+    window: [(f64, f64); W],
+    /// The index of the oldest pair, which is replaced next.
+    next: usize,
+    /// The estimator of the pairs in the window.
+    cov: Covariance,
+}
+
+impl<const W: usize> MovingCovariance<W> {
+    /// Create a new moving covariance estimator.
+    ///
+    /// Panics if `W` is zero.
+    #[inline]
+    pub fn new() -> MovingCovariance<W> {
+        assert!(W > 0, "The window must not be empty");
+        MovingCovariance {
+            window: [(0., 0.); W],
+            next: 0,
+            cov: Covariance::new(),
+        }
+    }
+
+    /// Add an observation, removing the oldest one if the window is full.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        if self.is_full() {
+            self.cov.update_streaming(self.window[self.next], (x, y));
+        } else {
+            self.cov.add(x, y);
+        }
+        self.window[self.next] = (x, y);
+        self.next = (self.next + 1) % W;
+    }
+
+    /// Calculate the sample covariance of the window.
+    ///
+    /// This is an unbiased estimator of the covariance of the population.
+    ///
+    /// Returns NaN for windows with 1 observation or less.
+    #[inline]
+    pub fn covariance(&self) -> f64 {
+        self.cov.sample_covariance()
+    }
+
+    /// Calculate the Pearson correlation coefficient of the window.
+    ///
+    /// Returns NaN for windows with 1 observation or less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn pearson(&self) -> f64 {
+        self.cov.pearson()
+    }
+
+    /// Return the number of observations in the window.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.cov.len()
+    }
+
+    /// Determine whether the window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cov.is_empty()
+    }
+
+    /// Determine whether the window is full, such that adding an observation
+    /// removes the oldest one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.cov.len() == W as u64
+    }
+
+    /// Return the estimator of the observations in the window.
+    #[inline]
+    pub fn estimator(&self) -> &Covariance {
+        &self.cov
+    }
+}
+
+impl<const W: usize> core::default::Default for MovingCovariance<W> {
+    fn default() -> MovingCovariance<W> {
+        MovingCovariance::new()
+    }
+}
@@ -0,0 +1,282 @@
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+use super::{Estimate, Reset};
+
+/// Estimate the exponentially weighted moving average of a sequence of numbers.
+///
+/// Each observation `x_t` updates the average according to
+/// `s_t = alpha * x_t + (1 - alpha) * s_{t-1}`, so older observations are
+/// discounted exponentially. The first observation initializes the average.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Ewma;
+///
+/// let mut a = Ewma::new(0.5);
+/// a.update(1.);
+/// a.update(3.);
+/// assert_eq!(a.value(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Ewma {
+    /// Weight of a new observation.
+    alpha: f64,
+    /// Current value of the average.
+    value: f64,
+    /// Sample size.
+    n: u64,
+}
+
+impl Ewma {
+    /// Create a new estimator, where `alpha` is the weight of a new
+    /// observation.
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    #[inline]
+    pub fn new(alpha: f64) -> Ewma {
+        assert!(
+            alpha > 0. && alpha <= 1.,
+            "The smoothing factor has to be in (0, 1]"
+        );
+        Ewma {
+            alpha,
+            value: f64::NAN,
+            n: 0,
+        }
+    }
+
+    /// Add an observation.
+    #[inline]
+    pub fn update(&mut self, x: f64) {
+        if self.n == 0 {
+            self.value = x;
+        } else {
+            self.value += self.alpha * (x - self.value);
+        }
+        self.n += 1;
+    }
+
+    /// Return the smoothing factor `alpha`.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Return the current value of the average.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl core::default::Default for Ewma {
+    /// Create a new estimator with `alpha = 0.1`.
+    fn default() -> Ewma {
+        Ewma::new(0.1)
+    }
+}
+
+impl Reset for Ewma {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Ewma::new(self.alpha);
+    }
+}
+
+impl Estimate for Ewma {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.update(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.value()
+    }
+}
+
+impl core::iter::FromIterator<f64> for Ewma {
+    /// Create a new estimator with the default smoothing factor from the
+    /// samples.
+    fn from_iter<T>(iter: T) -> Ewma
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut e = Ewma::default();
+        for i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for Ewma {
+    /// Create a new estimator with the default smoothing factor from the
+    /// samples.
+    fn from_iter<T>(iter: T) -> Ewma
+    where
+        T: IntoIterator<Item = &'a f64>,
+    {
+        let mut e = Ewma::default();
+        for &i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl_extend!(Ewma);
+
+/// Estimate the exponentially weighted moving average and variance of a
+/// sequence of numbers.
+///
+/// The average is calculated like [`Ewma`](./struct.Ewma.html). The variance is
+/// the exponentially weighted average of the squared deviations from the
+/// moving average (Hunter, 1986), updated as
+/// `v_t = (1 - alpha) * (v_{t-1} + alpha * (x_t - s_{t-1})^2)`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct EwmaVariance {
+    /// Estimator of the moving average.
+    avg: Ewma,
+    /// Current value of the variance.
+    variance: f64,
+}
+
+impl EwmaVariance {
+    /// Create a new estimator, where `alpha` is the weight of a new
+    /// observation.
+    ///
+    /// Panics if `alpha` is not in `(0, 1]`.
+    #[inline]
+    pub fn new(alpha: f64) -> EwmaVariance {
+        EwmaVariance {
+            avg: Ewma::new(alpha),
+            variance: f64::NAN,
+        }
+    }
+
+    /// Add an observation.
+    #[inline]
+    pub fn update(&mut self, x: f64) {
+        if self.avg.is_empty() {
+            self.avg.update(x);
+            self.variance = 0.;
+            return;
+        }
+        let alpha = self.avg.alpha();
+        let delta = x - self.avg.value();
+        self.avg.update(x);
+        self.variance = (1. - alpha) * (self.variance + alpha * delta * delta);
+    }
+
+    /// Return the smoothing factor `alpha`.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.avg.alpha()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Return the current value of the moving average.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.value()
+    }
+
+    /// Return the current value of the moving variance.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+}
+
+impl core::default::Default for EwmaVariance {
+    /// Create a new estimator with `alpha = 0.1`.
+    fn default() -> EwmaVariance {
+        EwmaVariance::new(0.1)
+    }
+}
+
+impl Reset for EwmaVariance {
+    #[inline]
+    fn reset(&mut self) {
+        *self = EwmaVariance::new(self.alpha());
+    }
+}
+
+impl Estimate for EwmaVariance {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.update(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.variance()
+    }
+}
+
+impl core::iter::FromIterator<f64> for EwmaVariance {
+    /// Create a new estimator with the default smoothing factor from the
+    /// samples.
+    fn from_iter<T>(iter: T) -> EwmaVariance
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut e = EwmaVariance::default();
+        for i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for EwmaVariance {
+    /// Create a new estimator with the default smoothing factor from the
+    /// samples.
+    fn from_iter<T>(iter: T) -> EwmaVariance
+    where
+        T: IntoIterator<Item = &'a f64>,
+    {
+        let mut e = EwmaVariance::default();
+        for &i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl_extend!(EwmaVariance);
@@ -10,16 +10,638 @@ pub enum InvalidRangeError {
     NaN,
 }
 
+impl core::fmt::Display for InvalidRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            InvalidRangeError::NotEnoughRanges => "not enough histogram ranges",
+            InvalidRangeError::NotSorted => "histogram ranges are not sorted",
+            InvalidRangeError::NaN => "histogram range contains NaN",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidRangeError {}
+
+/// A histogram cannot be constructed from the given samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromDataError {
+    /// No data was given to derive the ranges from.
+    EmptyData,
+    /// The data to derive the ranges from contains `nan` or `inf`.
+    NonFiniteData,
+    /// The width of the range covering the data is too large to be
+    /// represented.
+    RangeOverflow,
+    /// The requested number of bins differs from the one of the histogram.
+    WrongNumberOfBins,
+}
+
+impl core::fmt::Display for FromDataError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            FromDataError::EmptyData => "no data to derive the histogram ranges from",
+            FromDataError::NonFiniteData => "data to derive the histogram ranges from is not finite",
+            FromDataError::RangeOverflow => "range of the histogram data is too wide",
+            FromDataError::WrongNumberOfBins => "wrong number of histogram bins requested",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromDataError {}
+
 /// A sample is out of range of the histogram.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SampleOutOfRangeError;
 
+impl core::fmt::Display for SampleOutOfRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("sample out of range of the histogram")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SampleOutOfRangeError {}
+
+/// Two histograms with different ranges cannot be merged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IncompatibleRangesError {
+    /// The index of the first range that differs.
+    pub index: usize,
+    /// The range of the histogram that was merged into.
+    pub expected: f64,
+    /// The range of the histogram that was merged.
+    pub got: f64,
+}
+
+impl core::fmt::Display for IncompatibleRangesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "incompatible histogram ranges: expected {} at index {}, got {}",
+            self.expected, self.index, self.got
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IncompatibleRangesError {}
+
+/// A histogram cannot be rebinned to the requested number of bins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebinError {
+    /// The requested number of bins is zero.
+    ZeroBins,
+    /// The requested number of bins is larger than the current one.
+    TooManyBins,
+    /// The current number of bins is not a multiple of the requested one.
+    NotDivisible,
+}
+
+impl core::fmt::Display for RebinError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            RebinError::ZeroBins => "cannot rebin histogram to zero bins",
+            RebinError::TooManyBins => "cannot rebin histogram to more bins",
+            RebinError::NotDivisible => "number of histogram bins is not divisible by the requested one",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RebinError {}
+
+/// Format the bins of a histogram as a text bar chart.
+///
+/// Each bin is written as a row with a bar proportional to its count, scaled
+/// such that the largest bin has the width given by the precision of the
+/// formatter (by default 40 characters).
+#[doc(hidden)]
+pub fn fmt_bar_chart<I>(f: &mut core::fmt::Formatter<'_>, iter: I) -> core::fmt::Result
+where
+    I: Iterator<Item = ((f64, f64), u64)> + Clone,
+{
+    use core::fmt::Write;
+
+    /// Blocks with a width of `i/8` characters for `i` in `1..=8`.
+    const BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+    let width = f.precision().unwrap_or(40) as u128;
+    let max = iter.clone().map(|(_, count)| count).max().unwrap_or(0);
+    for (i, ((lower, upper), count)) in iter.enumerate() {
+        if i > 0 {
+            f.write_str("\n")?;
+        }
+        write!(f, "[{:?}, {:?}): ", lower, upper)?;
+        // The length of the bar in eighths of a character, rounded.
+        let eighths = if max == 0 {
+            0
+        } else {
+            (u128::from(count) * width * 16 + u128::from(max)) / (2 * u128::from(max))
+        };
+        for _ in 0..eighths / 8 {
+            f.write_char(BLOCKS[7])?;
+        }
+        if eighths % 8 > 0 {
+            f.write_char(BLOCKS[(eighths % 8) as usize - 1])?;
+        }
+        if eighths > 0 {
+            f.write_char(' ')?;
+        }
+        write!(f, "({})", count)?;
+    }
+    Ok(())
+}
+
+/// Count the characters written by `Display`, to determine column widths.
+struct CharCounter(usize);
+
+impl core::fmt::Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Calculate the number of characters of the `Display` representation.
+fn display_width<T: core::fmt::Display>(x: T) -> usize {
+    use core::fmt::Write;
+
+    let mut counter = CharCounter(0);
+    write!(counter, "{}", x).unwrap();
+    counter.0
+}
+
+/// Format the bins of a histogram as a table with ASCII bars.
+///
+/// Each bin is written as a row with the bin range, the count and a bar of
+/// `#` proportional to the count, scaled such that the largest bin has the
+/// width given by the precision of the formatter (by default 40 characters).
+/// The columns are aligned.
+#[doc(hidden)]
+pub fn fmt_table<I>(f: &mut core::fmt::Formatter<'_>, iter: I) -> core::fmt::Result
+where
+    I: Iterator<Item = ((f64, f64), u64)> + Clone,
+{
+    use core::fmt::Write;
+
+    let width = f.precision().unwrap_or(40) as u128;
+    let mut max = 0;
+    let mut lower_width = 0;
+    let mut upper_width = 0;
+    for ((lower, upper), count) in iter.clone() {
+        max = max.max(count);
+        lower_width = lower_width.max(display_width(lower));
+        upper_width = upper_width.max(display_width(upper));
+    }
+    let count_width = display_width(max);
+    for (i, ((lower, upper), count)) in iter.enumerate() {
+        if i > 0 {
+            f.write_str("\n")?;
+        }
+        write!(
+            f,
+            "[{:>lw$}, {:>uw$}) | {:>cw$} |",
+            lower,
+            upper,
+            count,
+            lw = lower_width,
+            uw = upper_width,
+            cw = count_width
+        )?;
+        // The length of the bar, rounded.
+        let len = if max == 0 {
+            0
+        } else {
+            (u128::from(count) * width * 2 + u128::from(max)) / (2 * u128::from(max))
+        };
+        if len > 0 {
+            f.write_char(' ')?;
+        }
+        for _ in 0..len {
+            f.write_char('#')?;
+        }
+    }
+    Ok(())
+}
+
+/// Calculate the mean and the population variance of the bin centers,
+/// weighted by the bin counts.
+#[doc(hidden)]
+pub fn histogram_mean_variance<H>(histogram: H) -> (f64, f64)
+where
+    H: IntoIterator<Item = ((f64, f64), u64)> + Copy,
+{
+    let total: u64 = histogram.into_iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let total_inv = 1. / (total as f64);
+    let mean: f64 = histogram
+        .into_iter()
+        .map(|((a, b), count)| 0.5 * (a + b) * (count as f64))
+        .sum::<f64>()
+        * total_inv;
+    let variance: f64 = histogram
+        .into_iter()
+        .map(|((a, b), count)| {
+            let delta = 0.5 * (a + b) - mean;
+            delta * delta * (count as f64)
+        })
+        .sum::<f64>()
+        * total_inv;
+    (mean, variance)
+}
+
+/// Calculate the Kullback-Leibler divergence of the distribution given by the
+/// bin counts `q` from the one given by the bin counts `p`.
+///
+/// Panics if the numbers of bins differ.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub(crate) fn histogram_kl_divergence(p: &[u64], q: &[u64]) -> f64 {
+    assert_eq!(p.len(), q.len(), "Both histograms must have the same number of bins");
+    let total_p: u64 = p.iter().sum();
+    let total_q: u64 = q.iter().sum();
+    if total_p == 0 || total_q == 0 {
+        return f64::NAN;
+    }
+    let total_p_inv = 1. / total_p as f64;
+    let total_q_inv = 1. / total_q as f64;
+    let mut result = 0.;
+    for (&count_p, &count_q) in p.iter().zip(q) {
+        if count_p == 0 {
+            continue;
+        }
+        if count_q == 0 {
+            return f64::INFINITY;
+        }
+        let p_i = count_p as f64 * total_p_inv;
+        let q_i = count_q as f64 * total_q_inv;
+        result += p_i * num_traits::Float::ln(p_i / q_i);
+    }
+    result
+}
+
+/// Calculate the Jensen-Shannon divergence of the distributions given by the
+/// bin counts `p` and `q`.
+///
+/// Panics if the numbers of bins differ.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub(crate) fn histogram_js_divergence(p: &[u64], q: &[u64]) -> f64 {
+    assert_eq!(p.len(), q.len(), "Both histograms must have the same number of bins");
+    let total_p: u64 = p.iter().sum();
+    let total_q: u64 = q.iter().sum();
+    if total_p == 0 || total_q == 0 {
+        return f64::NAN;
+    }
+    let total_p_inv = 1. / total_p as f64;
+    let total_q_inv = 1. / total_q as f64;
+    // `x ln(x / m)`, which vanishes for `x = 0`.
+    let term = |x: f64, m: f64| if x == 0. { 0. } else { x * num_traits::Float::ln(x / m) };
+    let mut result = 0.;
+    for (&count_p, &count_q) in p.iter().zip(q) {
+        let p_i = count_p as f64 * total_p_inv;
+        let q_i = count_q as f64 * total_q_inv;
+        let m_i = 0.5 * (p_i + q_i);
+        result += 0.5 * (term(p_i, m_i) + term(q_i, m_i));
+    }
+    result
+}
+
+/// Check that two histograms have the same ranges, as far as both have bins.
+///
+/// Fails with the first range that differs.
+#[doc(hidden)]
+pub fn histogram_check_same_ranges<I, J>(a: I, b: J) -> Result<(), IncompatibleRangesError>
+where
+    I: IntoIterator<Item = f64>,
+    J: IntoIterator<Item = f64>,
+{
+    for (index, (expected, got)) in a.into_iter().zip(b).enumerate() {
+        if expected != got {
+            return Err(IncompatibleRangesError { index, expected, got });
+        }
+    }
+    Ok(())
+}
+
+/// Assert that two histograms given by their `((lower, upper), count)` pairs
+/// have the same ranges, as far as both have bins.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub(crate) fn histogram_assert_same_ranges<I, J>(a: I, b: J)
+where
+    I: IntoIterator<Item = ((f64, f64), u64)>,
+    J: IntoIterator<Item = ((f64, f64), u64)>,
+{
+    // The ranges are the lower bound of the first bin followed by the upper
+    // bounds of all bins.
+    fn ranges<I: IntoIterator<Item = ((f64, f64), u64)>>(bins: I) -> impl Iterator<Item = f64> {
+        let mut bins = bins.into_iter().peekable();
+        let start = bins.peek().map(|&((lower, _), _)| lower);
+        start.into_iter().chain(bins.map(|((_, upper), _)| upper))
+    }
+    if let Err(e) = histogram_check_same_ranges(ranges(a), ranges(b)) {
+        panic!("Both histograms must have the same ranges: {}", e);
+    }
+}
+
+/// Calculate Pearson's chi-squared statistic comparing the bin counts to the
+/// ones expected for the given fractions of the total count.
+///
+/// Returns NaN if the numbers of bins differ or if an expected count is zero.
+pub(crate) fn histogram_chi_squared_test(bins: &[u64], expected_fractions: &[f64]) -> f64 {
+    if bins.len() != expected_fractions.len() {
+        return f64::NAN;
+    }
+    let total = bins.iter().sum::<u64>() as f64;
+    let mut chi_squared = 0.;
+    for (&observed, &fraction) in bins.iter().zip(expected_fractions) {
+        let expected = fraction * total;
+        if expected == 0. {
+            return f64::NAN;
+        }
+        let delta = observed as f64 - expected;
+        chi_squared += delta * delta / expected;
+    }
+    chi_squared
+}
+
+/// Transform the histogram given by its `((lower, upper), count)` pairs into a
+/// histogram of `f(x)` with `n_output_bins` bins of constant width.
+///
+/// Panics if `n_output_bins` is zero.
+#[cfg(feature = "std")]
+pub(crate) fn histogram_apply_transform<I, F>(
+    bins: I,
+    f: F,
+    n_output_bins: usize,
+) -> crate::DynHistogram
+where
+    I: IntoIterator<Item = ((f64, f64), u64)> + Copy,
+    F: Fn(f64) -> f64,
+{
+    let mut lower = f64::INFINITY;
+    let mut upper = f64::NEG_INFINITY;
+    for ((a, b), _) in bins {
+        for y in [f(a), f(b)] {
+            if y.is_finite() {
+                lower = lower.min(y);
+                upper = upper.max(y);
+            }
+        }
+    }
+    if lower > upper {
+        // No boundary has a finite image.
+        lower = 0.;
+        upper = 0.;
+    }
+    let mut result = crate::DynHistogram::with_const_width(lower, upper, n_output_bins);
+    for ((a, b), count) in bins {
+        let y = f(0.5 * (a + b));
+        if y.is_nan() {
+            continue;
+        }
+        let i = match result.find(y) {
+            Ok(i) => i,
+            Err(_) if y < lower => 0,
+            Err(_) => n_output_bins - 1,
+        };
+        result.bins_mut()[i] += count;
+    }
+    result
+}
+
+/// Smooth the bin counts by convolving them with a Gaussian kernel with a
+/// standard deviation of `sigma` bins.
+///
+/// Returns NaN for all bins if `sigma` is not positive or not finite.
+#[cfg(feature = "std")]
+pub(crate) fn histogram_smooth_gaussian(bins: &[u64], sigma: f64) -> std::vec::Vec<f64> {
+    let n = bins.len();
+    if !sigma.is_finite() || sigma <= 0. {
+        return std::vec![f64::NAN; n];
+    }
+    // Kernel entries beyond the last bin are never used.
+    let radius = ((3. * sigma) as usize).min(n.saturating_sub(1));
+    let kernel: std::vec::Vec<f64> = (0..=radius)
+        .map(|k| (-0.5 * (k as f64 / sigma).powi(2)).exp())
+        .collect();
+    let norm = kernel[0] + 2. * kernel[1..].iter().sum::<f64>();
+    let mut result = std::vec![0.; n];
+    for (i, &count) in bins.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let count = count as f64 / norm;
+        let start = i.saturating_sub(radius);
+        let end = n.min(i + radius + 1);
+        for (j, x) in result[start..end].iter_mut().enumerate() {
+            *x += count * kernel[(start + j).abs_diff(i)];
+        }
+    }
+    result
+}
+
+/// Calculate the expected counts of the bins given by their
+/// `((lower, upper), count)` pairs for `total` samples drawn from a
+/// distribution with the probability density function `pdf`.
+///
+/// Bins of infinite width yield NaN.
+#[cfg(feature = "std")]
+pub(crate) fn histogram_expected_counts<I, F>(bins: I, pdf: F, total: u64) -> std::vec::Vec<f64>
+where
+    I: IntoIterator<Item = ((f64, f64), u64)>,
+    F: Fn(f64) -> f64,
+{
+    let total = total as f64;
+    bins.into_iter()
+        .map(|((a, b), _)| {
+            let width = b - a;
+            if !width.is_finite() {
+                return f64::NAN;
+            }
+            pdf(0.5 * (a + b)) * width * total
+        })
+        .collect()
+}
+
+/// Calculate Pearson's chi-squared statistic comparing the bins given by their
+/// `((lower, upper), count)` pairs to the counts expected for a distribution
+/// with the probability density function `pdf`.
+///
+/// Bins where both the observed and the expected count are zero are skipped.
+#[cfg(feature = "std")]
+pub(crate) fn histogram_chi_squared_vs_distribution<I, F>(bins: I, pdf: F) -> f64
+where
+    I: IntoIterator<Item = ((f64, f64), u64)> + Copy,
+    F: Fn(f64) -> f64,
+{
+    let total: u64 = bins.into_iter().map(|(_, count)| count).sum();
+    let expected = histogram_expected_counts(bins, pdf, total);
+    let mut chi_squared = 0.;
+    for ((_, observed), expected) in bins.into_iter().zip(expected) {
+        let observed = observed as f64;
+        if observed == 0. && expected == 0. {
+            continue;
+        }
+        let delta = observed - expected;
+        chi_squared += delta * delta / expected;
+    }
+    chi_squared
+}
+
+/// Calculate the range `start..end` covering all samples of `data`.
+///
+/// `start` is the smallest sample and `end` is the smallest float larger than
+/// the largest sample, so that all samples fall into `start <= x < end`. If all
+/// samples are equal, the range is centered on them and has width 1, or is
+/// widened to the neighboring floats if they are further apart.
+///
+/// Fails if `data` is empty or contains `nan` or `inf`, or if the width of the
+/// range overflows.
+#[doc(hidden)]
+pub fn histogram_data_range(data: &[f64]) -> Result<(f64, f64), FromDataError> {
+    if data.is_empty() {
+        return Err(FromDataError::EmptyData);
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(FromDataError::NonFiniteData);
+    }
+    let (min, max) = data
+        .iter()
+        .copied()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+            (min.min(x), max.max(x))
+        });
+    let (start, end) = if min == max {
+        // For large samples, adding 0.5 is lost to rounding, so we make sure
+        // the range is at least one float wider on both sides.
+        (
+            (min - 0.5).min(-next_float_up(-min)),
+            (max + 0.5).max(next_float_up(max)),
+        )
+    } else {
+        (min, next_float_up(max))
+    };
+    if !(end - start).is_finite() {
+        return Err(FromDataError::RangeOverflow);
+    }
+    Ok((start, end))
+}
+
+/// Return the smallest float larger than the finite float `x`.
+fn next_float_up(x: f64) -> f64 {
+    if x == 0. {
+        f64::from_bits(1)
+    } else if x > 0. {
+        f64::from_bits(x.to_bits() + 1)
+    } else {
+        f64::from_bits(x.to_bits() - 1)
+    }
+}
+
+/// Calculate the range `start..end` of bins that are kept when trimming the
+/// given fractions of the total count from both sides.
+#[doc(hidden)]
+pub fn histogram_trim_range<I>(counts: I, lower_pct: f64, upper_pct: f64) -> (usize, usize)
+where
+    I: Iterator<Item = u64> + Clone,
+{
+    let total = counts.clone().sum::<u64>() as f64;
+    let lower = lower_pct * total;
+    let upper = (1. - upper_pct) * total;
+    let mut start = 0;
+    let mut end = 0;
+    let mut cumulative = 0;
+    for (i, count) in counts.enumerate() {
+        if (cumulative as f64) < upper {
+            end = i + 1;
+        }
+        cumulative += count;
+        if (cumulative as f64) <= lower {
+            start = i + 1;
+        }
+    }
+    (start, end.max(start))
+}
+
+/// Scale the bin counts proportionally such that they sum to `new_total`.
+///
+/// The scaled counts are rounded by the largest remainder method: they are
+/// rounded down, and the remaining count is distributed one per bin to the
+/// bins with the largest fractional parts, preferring the most populated bins
+/// for equal fractional parts. All-zero bins are not modified.
+///
+/// Panics if a scaled count does not fit into the count type.
+#[doc(hidden)]
+pub fn histogram_normalize_to_total<C>(bins: &mut [C], new_total: u64)
+where
+    C: Copy + Into<u64> + core::convert::TryFrom<u64>,
+    <C as core::convert::TryFrom<u64>>::Error: core::fmt::Debug,
+{
+    let total: u64 = bins.iter().map(|&count| count.into()).sum();
+    if total == 0 {
+        return;
+    }
+    let scaled = |count: u64| u128::from(count) * u128::from(new_total);
+    let floor = |count: u64| (scaled(count) / u128::from(total)) as u64;
+    let remaining = new_total - bins.iter().map(|&count| floor(count.into())).sum::<u64>();
+
+    // Order the bins by decreasing remainder, decreasing count and increasing
+    // index, and find the last bin receiving one of the remaining counts.
+    // Each remainder is less than the total, so there are fewer remaining
+    // counts than bins with a nonzero remainder.
+    let key = |i: usize, count: u64| (scaled(count) % u128::from(total), count, usize::MAX - i);
+    let mut last: Option<(u128, u64, usize)> = None;
+    for _ in 0..remaining {
+        let mut next = None;
+        for (i, &count) in bins.iter().enumerate() {
+            let bin = key(i, count.into());
+            if last.map_or(true, |last| bin < last) && next.map_or(true, |next| bin > next) {
+                next = Some(bin);
+            }
+        }
+        last = next;
+    }
+
+    for (i, count) in bins.iter_mut().enumerate() {
+        let c: u64 = (*count).into();
+        let mut result = floor(c);
+        if last.map_or(false, |last| key(i, c) >= last) {
+            result += 1;
+        }
+        *count = C::try_from(result).unwrap();
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! define_histogram_common {
+    (@bins u64) => {
+        impl $crate::Histogram for Histogram {
+            #[inline]
+            fn bins(&self) -> &[u64] {
+                &self.bin[..]
+            }
+        }
+    };
+    (@bins $Count:ident) => {
+        impl Histogram {
+            /// Return the bins of the histogram, converted to `u64`.
+            ///
+            /// Histograms with a count type other than `u64` do not implement
+            /// the `Histogram` trait, use `bins_raw()` to access the bins
+            /// without conversion.
+            #[inline]
+            pub fn bins(&self) -> impl ::core::iter::Iterator<Item = u64> + '_ {
+                self.bin.iter().map(|&x| u64::from(x))
+            }
+        }
+    };
     ($LEN:expr) => {
-        use $crate::Histogram as Trait;
-
+        $crate::define_histogram_common!($LEN, u64);
+    };
+    ($LEN:expr, $Count:ident) => {
         /// The number of bins of the histogram.
         const LEN: usize = $LEN;
 
@@ -86,6 +708,75 @@ macro_rules! define_histogram_common {
                 })
             }
 
+            /// Construct a histogram with constant bin width covering the
+            /// range of the given samples and add them.
+            ///
+            /// The range starts at the smallest sample and ends just above the
+            /// largest one. If all samples are equal, the range is centered on
+            /// them and has width 1, or more if the samples are large.
+            ///
+            /// Fails if `data` is empty or contains `nan` or `inf`, or if the
+            /// width of the range is too large to be represented.
+            #[inline]
+            pub fn from_data(data: &[f64]) -> Result<Self, $crate::FromDataError> {
+                let (start, end) = $crate::histogram_data_range(data)?;
+                let mut h = Self::with_const_width(start, end);
+                // Make sure the largest sample is not lost to rounding errors.
+                h.range[LEN] = end;
+                let out_of_range = h.add_slice(data);
+                debug_assert_eq!(out_of_range, 0);
+                Ok(h)
+            }
+
+            /// Like `from_data`, but check that the histogram has `n_bins`
+            /// bins.
+            ///
+            /// Because the number of bins is fixed at compile time, this fails
+            /// if `n_bins` differs from it.
+            #[inline]
+            pub fn from_data_with_bins(
+                data: &[f64],
+                n_bins: usize,
+            ) -> Result<Self, $crate::FromDataError> {
+                if n_bins != LEN {
+                    return Err($crate::FromDataError::WrongNumberOfBins);
+                }
+                Self::from_data(data)
+            }
+
+            /// Merge another histogram into this one.
+            ///
+            /// Fails without modifying the histogram if the ranges differ.
+            #[inline]
+            pub fn try_merge(&mut self, other: &Self) -> Result<(), $crate::IncompatibleRangesError> {
+                $crate::histogram_check_same_ranges(
+                    self.range.iter().copied(),
+                    other.range.iter().copied(),
+                )?;
+                for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
+                    *a += *b;
+                }
+                Ok(())
+            }
+
+            /// Subtract the bin counts of another histogram from this one, saturating
+            /// at zero.
+            ///
+            /// This is useful for removing a background from a signal, where
+            /// background counts exceeding the signal counts yield empty bins. Fails
+            /// without modifying the histogram if the ranges differ.
+            #[inline]
+            pub fn try_sub_assign(&mut self, other: &Self) -> Result<(), $crate::IncompatibleRangesError> {
+                $crate::histogram_check_same_ranges(
+                    self.range.iter().copied(),
+                    other.range.iter().copied(),
+                )?;
+                for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
+                    *a = a.saturating_sub(*b);
+                }
+                Ok(())
+            }
+
             /// Find the index of the bin corresponding to the given sample.
             ///
             /// Fails if the sample is out of range of the histogram.
@@ -113,12 +804,73 @@ macro_rules! define_histogram_common {
                 }
             }
 
+            /// Add a sample with an integer multiplicity `weight` to the histogram.
+            ///
+            /// This is equivalent to calling `add(x)` `weight` times. Fails if the
+            /// sample is out of range of the histogram. Panics if `weight` cannot be
+            /// represented by the count type.
+            #[inline]
+            pub fn add_weighted(&mut self, x: f64, weight: u64) -> Result<(), $crate::SampleOutOfRangeError> {
+                let i = self.find(x)?;
+                self.bin[i] += <$Count as ::core::convert::TryFrom<u64>>::try_from(weight).unwrap();
+                Ok(())
+            }
+
+            /// Add all samples with their multiplicities `(x, weight)` of a slice to
+            /// the histogram.
+            ///
+            /// Fails at the first sample that is out of range of the histogram. The
+            /// samples before it are added.
+            #[inline]
+            pub fn add_weighted_many(&mut self, items: &[(f64, u64)]) -> Result<(), $crate::SampleOutOfRangeError> {
+                for &(x, weight) in items {
+                    self.add_weighted(x, weight)?;
+                }
+                Ok(())
+            }
+
+            /// Add all samples of a slice to the histogram.
+            ///
+            /// Samples that are out of range of the histogram are skipped. Returns the
+            /// number of skipped samples.
+            #[inline]
+            pub fn add_slice(&mut self, data: &[f64]) -> usize {
+                let mut out_of_range = 0;
+                for &x in data {
+                    if self.add(x).is_err() {
+                        out_of_range += 1;
+                    }
+                }
+                out_of_range
+            }
+
+            /// Add all samples of a slice to the histogram.
+            ///
+            /// Fails at the first sample that is out of range of the histogram,
+            /// returning its index. The samples before it are added.
+            #[inline]
+            pub fn add_slice_strict(
+                &mut self,
+                data: &[f64],
+            ) -> Result<(), (usize, $crate::SampleOutOfRangeError)> {
+                for (i, &x) in data.iter().enumerate() {
+                    self.add(x).map_err(|e| (i, e))?;
+                }
+                Ok(())
+            }
+
             /// Return the ranges of the histogram.
             #[inline]
             pub fn ranges(&self) -> &[f64] {
                 &self.range[..]
             }
 
+            /// Return the bins of the histogram with their original count type.
+            #[inline]
+            pub fn bins_raw(&self) -> &[$Count] {
+                &self.bin[..]
+            }
+
             /// Return an iterator over the bins and corresponding ranges:
             /// `((lower, upper), count)`
             #[inline]
@@ -126,6 +878,115 @@ macro_rules! define_histogram_common {
                 self.into_iter()
             }
 
+            /// Return an iterator over the mutable bins and corresponding ranges:
+            /// `((lower, upper), &mut count)`
+            #[inline]
+            pub fn iter_mut(&mut self) -> IterHistogramMut<'_> {
+                IterHistogramMut {
+                    remaining_bin: &mut self.bin[..],
+                    remaining_range: &self.range[..],
+                }
+            }
+
+            /// Fold all `((lower, upper), count)` pairs of the histogram into an
+            /// accumulator, in the order of the bins.
+            ///
+            /// This is a shorthand for `iter().fold(init, f)`.
+            #[inline]
+            pub fn fold<B, F>(&self, init: B, f: F) -> B
+            where
+                F: FnMut(B, ((f64, f64), u64)) -> B,
+            {
+                self.iter().fold(init, f)
+            }
+
+            /// Call a closure on all `((lower, upper), count)` pairs of the histogram,
+            /// in the order of the bins.
+            ///
+            /// This is a shorthand for `iter().for_each(|(range, count)| f(range, count))`.
+            #[inline]
+            pub fn for_each<F>(&self, mut f: F)
+            where
+                F: FnMut((f64, f64), u64),
+            {
+                self.iter().for_each(|(range, count)| f(range, count))
+            }
+
+            /// Return a copy of the histogram with the bin counts smoothed by a moving
+            /// average.
+            ///
+            /// The count of each bin is replaced by the average of the `width` bins on
+            /// each side and the bin itself, rounded to the nearest integer. The window
+            /// is truncated at the boundaries of the histogram. The total count is not
+            /// necessarily preserved.
+            #[inline]
+            pub fn smooth(&self, width: usize) -> Self {
+                let mut result = self.clone();
+                let len = self.bin.len();
+                for (i, bin) in result.bin.iter_mut().enumerate() {
+                    let window = &self.bin[i.saturating_sub(width)..=i.saturating_add(width).min(len - 1)];
+                    let n = window.len() as u64;
+                    let sum: u64 = window.iter().map(|&count| u64::from(count)).sum();
+                    *bin = <$Count as ::core::convert::TryFrom<u64>>::try_from((sum + n / 2) / n)
+                        .unwrap();
+                }
+                result
+            }
+
+            /// Return a copy of the histogram with the bin counts scaled
+            /// proportionally such that they sum to `new_total`.
+            ///
+            /// The scaled counts are rounded such that the rounding errors go to
+            /// the bins with the largest fractional parts first, and to the most
+            /// populated bins for equal fractional parts. An empty histogram is
+            /// returned unchanged.
+            ///
+            /// Panics if a scaled count does not fit into the count type.
+            pub fn normalize_to_total(&self, new_total: u64) -> Self {
+                let mut result = self.clone();
+                $crate::histogram_normalize_to_total(&mut result.bin[..], new_total);
+                result
+            }
+
+            /// Return a copy of the histogram with the extreme bins set to zero.
+            ///
+            /// Bins are removed from the left while their cumulative count is
+            /// not larger than the fraction `lower_pct` of the total count, and
+            /// from the right while the cumulative count of the preceding bins
+            /// is not smaller than the fraction `1 - upper_pct` of the total
+            /// count.
+            pub fn trim(&self, lower_pct: f64, upper_pct: f64) -> Self {
+                let (start, end) =
+                    $crate::histogram_trim_range(self.iter().map(|(_, c)| c), lower_pct, upper_pct);
+                let mut result = self.clone();
+                for bin in &mut result.bin[..start] {
+                    *bin = 0;
+                }
+                for bin in &mut result.bin[end..] {
+                    *bin = 0;
+                }
+                result
+            }
+
+            /// Return a copy of the histogram with the bins whose center is
+            /// more than `n_std` standard deviations away from the mean set to
+            /// zero.
+            ///
+            /// The mean and standard deviation are estimated from the bin
+            /// centers, see `Histogram::histogram_mean()`.
+            pub fn trim_by_std(&self, n_std: f64) -> Self {
+                let (mean, variance) = $crate::histogram_mean_variance(self);
+                let max_delta_2 = n_std * n_std * variance;
+                let mut result = self.clone();
+                for (((a, b), _), bin) in self.iter().zip(result.bin.iter_mut()) {
+                    let delta = 0.5 * (a + b) - mean;
+                    if delta * delta > max_delta_2 {
+                        *bin = 0;
+                    }
+                }
+                result
+            }
+
             /// Reset all bins to zero.
             #[inline]
             pub fn reset(&mut self) {
@@ -152,7 +1013,7 @@ macro_rules! define_histogram_common {
         /// Iterate over all `(range, count)` pairs in the histogram.
         #[derive(Debug, Clone)]
         pub struct IterHistogram<'a> {
-            remaining_bin: &'a [u64],
+            remaining_bin: &'a [$Count],
             remaining_range: &'a [f64],
         }
 
@@ -164,27 +1025,93 @@ macro_rules! define_histogram_common {
                     let right = self.remaining_range[1];
                     self.remaining_bin = rest;
                     self.remaining_range = &self.remaining_range[1..];
+                    return Some(((left, right), u64::from(bin)));
+                }
+                None
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.remaining_bin.len(), Some(self.remaining_bin.len()))
+            }
+        }
+
+        impl<'a> ::core::iter::DoubleEndedIterator for IterHistogram<'a> {
+            fn next_back(&mut self) -> Option<((f64, f64), u64)> {
+                if let Some((&bin, rest)) = self.remaining_bin.split_last() {
+                    let len = self.remaining_range.len();
+                    let left = self.remaining_range[len - 2];
+                    let right = self.remaining_range[len - 1];
+                    self.remaining_bin = rest;
+                    self.remaining_range = &self.remaining_range[..len - 1];
+                    return Some(((left, right), u64::from(bin)));
+                }
+                None
+            }
+        }
+
+        impl<'a> ::core::iter::ExactSizeIterator for IterHistogram<'a> {}
+
+        /// Iterate over all `(range, &mut count)` pairs in the histogram.
+        #[derive(Debug)]
+        pub struct IterHistogramMut<'a> {
+            remaining_bin: &'a mut [$Count],
+            remaining_range: &'a [f64],
+        }
+
+        impl<'a> ::core::iter::Iterator for IterHistogramMut<'a> {
+            type Item = ((f64, f64), &'a mut $Count);
+            fn next(&mut self) -> Option<((f64, f64), &'a mut $Count)> {
+                let remaining_bin = ::core::mem::take(&mut self.remaining_bin);
+                if let Some((bin, rest)) = remaining_bin.split_first_mut() {
+                    let left = self.remaining_range[0];
+                    let right = self.remaining_range[1];
+                    self.remaining_bin = rest;
+                    self.remaining_range = &self.remaining_range[1..];
+                    return Some(((left, right), bin));
+                }
+                None
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.remaining_bin.len(), Some(self.remaining_bin.len()))
+            }
+        }
+
+        impl<'a> ::core::iter::DoubleEndedIterator for IterHistogramMut<'a> {
+            fn next_back(&mut self) -> Option<((f64, f64), &'a mut $Count)> {
+                let remaining_bin = ::core::mem::take(&mut self.remaining_bin);
+                if let Some((bin, rest)) = remaining_bin.split_last_mut() {
+                    let len = self.remaining_range.len();
+                    let left = self.remaining_range[len - 2];
+                    let right = self.remaining_range[len - 1];
+                    self.remaining_bin = rest;
+                    self.remaining_range = &self.remaining_range[..len - 1];
                     return Some(((left, right), bin));
                 }
                 None
             }
         }
 
+        impl<'a> ::core::iter::ExactSizeIterator for IterHistogramMut<'a> {}
+
         impl<'a> ::core::iter::IntoIterator for &'a Histogram {
             type Item = ((f64, f64), u64);
             type IntoIter = IterHistogram<'a>;
             fn into_iter(self) -> IterHistogram<'a> {
                 IterHistogram {
-                    remaining_bin: self.bins(),
+                    remaining_bin: self.bins_raw(),
                     remaining_range: self.ranges(),
                 }
             }
         }
 
-        impl $crate::Histogram for Histogram {
+        $crate::define_histogram_common!(@bins $Count);
+
+        impl $crate::Reset for Histogram {
+            /// Set all bin counts to zero, keeping the ranges.
             #[inline]
-            fn bins(&self) -> &[u64] {
-                &self.bin[..]
+            fn reset(&mut self) {
+                self.bin = [0; LEN];
             }
         }
 
@@ -200,9 +1127,68 @@ macro_rules! define_histogram_common {
             }
         }
 
-        impl ::core::ops::MulAssign<u64> for Histogram {
+        impl<'a> ::core::ops::SubAssign<&'a Self> for Histogram {
+            /// Subtract the bin counts of another histogram from this one,
+            /// saturating at zero.
+            ///
+            /// Panics if the histograms have different ranges. See
+            /// `try_sub_assign` for a non-panicking variant.
             #[inline]
-            fn mul_assign(&mut self, other: u64) {
+            fn sub_assign(&mut self, other: &Self) {
+                if let Err(e) = self.try_sub_assign(other) {
+                    panic!("Both histograms must have the same ranges: {}", e);
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for Histogram {
+            /// Format the histogram as a table with the ranges, the counts and
+            /// ASCII bars.
+            ///
+            /// The precision specifies the width of the largest bar, by default
+            /// it is 40 characters.
+            fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                $crate::fmt_table(formatter, self.iter())
+            }
+        }
+
+        impl ::core::fmt::Binary for Histogram {
+            /// Format the histogram as a text bar chart.
+            ///
+            /// The precision specifies the width of the largest bar, by default
+            /// it is 40 characters.
+            fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                $crate::fmt_bar_chart(formatter, self.iter())
+            }
+        }
+
+        impl ::core::ops::Index<usize> for Histogram {
+            type Output = $Count;
+
+            #[inline]
+            fn index(&self, bin: usize) -> &$Count {
+                let len = self.bin.len();
+                match self.bin.get(bin) {
+                    Some(count) => count,
+                    None => panic!("histogram bin index {} out of range [0, {})", bin, len),
+                }
+            }
+        }
+
+        impl ::core::ops::IndexMut<usize> for Histogram {
+            #[inline]
+            fn index_mut(&mut self, bin: usize) -> &mut $Count {
+                let len = self.bin.len();
+                match self.bin.get_mut(bin) {
+                    Some(count) => count,
+                    None => panic!("histogram bin index {} out of range [0, {})", bin, len),
+                }
+            }
+        }
+
+        impl ::core::ops::MulAssign<$Count> for Histogram {
+            #[inline]
+            fn mul_assign(&mut self, other: $Count) {
                 for x in &mut self.bin[..] {
                     *x *= other;
                 }
@@ -210,13 +1196,13 @@ macro_rules! define_histogram_common {
         }
 
         impl $crate::Merge for Histogram {
+            /// Merge another histogram into this one.
+            ///
+            /// Panics if the histograms have different ranges. See
+            /// `try_merge` for a non-panicking variant.
             fn merge(&mut self, other: &Self) {
-                assert_eq!(self.bin.len(), other.bin.len());
-                for (a, b) in self.range.iter().zip(other.range.iter()) {
-                    assert_eq!(a, b, "Both histograms must have the same ranges");
-                }
-                for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
-                    *a += *b;
+                if let Err(e) = self.try_merge(other) {
+                    panic!("Both histograms must have the same ranges: {}", e);
                 }
             }
         }
@@ -228,8 +1214,11 @@ macro_rules! define_histogram_common {
 #[macro_export]
 macro_rules! define_histogram_inner {
     ($name:ident, $LEN:expr) => {
+        $crate::define_histogram_inner!($name, $LEN, u64);
+    };
+    ($name:ident, $LEN:expr, $Count:ident) => {
         mod $name {
-            $crate::define_histogram_common!($LEN);
+            $crate::define_histogram_common!($LEN, $Count);
 
             use ::serde::{Deserialize, Serialize};
             use serde_big_array::BigArray;
@@ -242,7 +1231,7 @@ macro_rules! define_histogram_inner {
                 range: [f64; LEN + 1],
                 /// The bins of the histogram.
                 #[serde(with = "BigArray")]
-                bin: [u64; LEN],
+                bin: [$Count; LEN],
             }
         }
     };
@@ -253,8 +1242,11 @@ macro_rules! define_histogram_inner {
 #[macro_export]
 macro_rules! define_histogram_inner {
     ($name:ident, $LEN:expr) => {
+        $crate::define_histogram_inner!($name, $LEN, u64);
+    };
+    ($name:ident, $LEN:expr, $Count:ident) => {
         mod $name {
-            $crate::define_histogram_common!($LEN);
+            $crate::define_histogram_common!($LEN, $Count);
 
             /// A histogram with a number of bins known at compile time.
             #[derive(Clone)]
@@ -262,7 +1254,7 @@ macro_rules! define_histogram_inner {
                 /// The ranges defining the bins of the histogram.
                 range: [f64; LEN + 1],
                 /// The bins of the histogram.
-                bin: [u64; LEN],
+                bin: [$Count; LEN],
             }
         }
     };
@@ -276,6 +1268,13 @@ macro_rules! define_histogram_inner {
 ///
 /// Note that you need to make sure that `core` is accessible to the macro.
 ///
+/// By default, the bins are counted with `u64`. A smaller unsigned integer
+/// type can be given with `count_type: $type` to save memory. Such histograms
+/// do not implement the [`Histogram`] trait; their `bins()` method returns an
+/// iterator over the counts converted to `u64` instead of a slice, and
+/// `bins_raw()` returns the bins with their original type.
+///
+/// [`Histogram`]: ./trait.Histogram.html
 ///
 /// # Example
 ///
@@ -288,10 +1287,21 @@ macro_rules! define_histogram_inner {
 ///     h.add(i as f64).unwrap();
 /// }
 /// assert_eq!(h.bins(), &[10, 10, 10, 10, 10, 10, 10, 10, 10, 10]);
+///
+/// define_histogram!(hist_u32, 10, count_type: u32);
+/// let mut h = hist_u32::Histogram::with_const_width(0., 100.);
+/// for i in 0..100 {
+///     h.add(i as f64).unwrap();
+/// }
+/// assert_eq!(h.bins_raw(), &[10u32; 10]);
+/// assert!(h.bins().all(|count| count == 10));
 /// ```
 #[macro_export]
 macro_rules! define_histogram {
     ($name:ident, $LEN:expr) => {
-        $crate::define_histogram_inner!($name, $LEN);
+        $crate::define_histogram_inner!($name, $LEN, u64);
+    };
+    ($name:ident, $LEN:expr, count_type: $Count:ident) => {
+        $crate::define_histogram_inner!($name, $LEN, $Count);
     };
 }
@@ -12,10 +12,45 @@ pub enum InvalidRangeError {
     NaN,
 }
 
+impl core::fmt::Display for InvalidRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            InvalidRangeError::NotEnoughRanges => "not enough histogram ranges",
+            InvalidRangeError::NotSorted => "histogram ranges are not sorted",
+            InvalidRangeError::NaN => "histogram range contains NaN",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidRangeError {}
+
 /// A sample is out of range of the histogram.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SampleOutOfRangeError;
 
+impl core::fmt::Display for SampleOutOfRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("sample out of range of the histogram")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SampleOutOfRangeError {}
+
+/// How to handle samples out of range of the histogram when adding them with
+/// `+=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaturationMode {
+    /// Count samples below the range in the first bin and samples above the
+    /// range in the last bin. NaN is ignored.
+    Saturate,
+    /// Ignore samples out of range.
+    Ignore,
+    /// Panic for samples out of range.
+    Panic,
+}
+
 impl<const LEN: usize> ::core::fmt::Debug for Histogram<LEN>
 where
     [u8; LEN + 1]: Sized,
@@ -85,6 +120,24 @@ where
         })
     }
 
+    /// Subtract the bin counts of another histogram from this one, saturating
+    /// at zero.
+    ///
+    /// This is useful for removing a background from a signal, where
+    /// background counts exceeding the signal counts yield empty bins. Fails
+    /// without modifying the histogram if the ranges differ.
+    #[inline]
+    pub fn try_sub_assign(&mut self, other: &Self) -> Result<(), crate::IncompatibleRangesError> {
+        crate::histogram::histogram_check_same_ranges(
+            self.range.iter().copied(),
+            other.range.iter().copied(),
+        )?;
+        for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
+            *a = a.saturating_sub(*b);
+        }
+        Ok(())
+    }
+
     /// Find the index of the bin corresponding to the given sample.
     ///
     /// Fails if the sample is out of range of the histogram.
@@ -112,6 +165,60 @@ where
         }
     }
 
+    /// Add a sample with an integer multiplicity `weight` to the histogram.
+    ///
+    /// This is equivalent to calling `add(x)` `weight` times. Fails if the
+    /// sample is out of range of the histogram.
+    #[inline]
+    pub fn add_weighted(&mut self, x: f64, weight: u64) -> Result<(), SampleOutOfRangeError> {
+        let i = self.find(x)?;
+        self.bin[i] += weight;
+        Ok(())
+    }
+
+    /// Add all samples with their multiplicities `(x, weight)` of a slice to
+    /// the histogram.
+    ///
+    /// Fails at the first sample that is out of range of the histogram. The
+    /// samples before it are added.
+    #[inline]
+    pub fn add_weighted_many(&mut self, items: &[(f64, u64)]) -> Result<(), SampleOutOfRangeError> {
+        for &(x, weight) in items {
+            self.add_weighted(x, weight)?;
+        }
+        Ok(())
+    }
+
+    /// Add all samples of a slice to the histogram.
+    ///
+    /// Samples that are out of range of the histogram are skipped. Returns the
+    /// number of skipped samples.
+    #[inline]
+    pub fn add_slice(&mut self, data: &[f64]) -> usize {
+        let mut out_of_range = 0;
+        for &x in data {
+            if self.add(x).is_err() {
+                out_of_range += 1;
+            }
+        }
+        out_of_range
+    }
+
+    /// Add all samples of a slice to the histogram.
+    ///
+    /// Fails at the first sample that is out of range of the histogram,
+    /// returning its index. The samples before it are added.
+    #[inline]
+    pub fn add_slice_strict(
+        &mut self,
+        data: &[f64],
+    ) -> Result<(), (usize, SampleOutOfRangeError)> {
+        for (i, &x) in data.iter().enumerate() {
+            self.add(x).map_err(|e| (i, e))?;
+        }
+        Ok(())
+    }
+
     /// Return the ranges of the histogram.
     #[inline]
     pub fn ranges(&self) -> &[f64] {
@@ -125,6 +232,112 @@ where
         self.into_iter()
     }
 
+    /// Return an iterator over the mutable bins and corresponding ranges:
+    /// `((lower, upper), &mut count)`
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterHistogramMut<'_> {
+        IterHistogramMut {
+            remaining_bin: &mut self.bin[..],
+            remaining_range: &self.range[..],
+        }
+    }
+
+    /// Fold all `((lower, upper), count)` pairs of the histogram into an
+    /// accumulator, in the order of the bins.
+    ///
+    /// This is a shorthand for `iter().fold(init, f)`.
+    #[inline]
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, ((f64, f64), u64)) -> B,
+    {
+        self.iter().fold(init, f)
+    }
+
+    /// Call a closure on all `((lower, upper), count)` pairs of the histogram,
+    /// in the order of the bins.
+    ///
+    /// This is a shorthand for `iter().for_each(|(range, count)| f(range, count))`.
+    #[inline]
+    pub fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut((f64, f64), u64),
+    {
+        self.iter().for_each(|(range, count)| f(range, count))
+    }
+
+    /// Return a copy of the histogram with the bin counts smoothed by a moving
+    /// average.
+    ///
+    /// The count of each bin is replaced by the average of the `width` bins on
+    /// each side and the bin itself, rounded to the nearest integer. The window
+    /// is truncated at the boundaries of the histogram. The total count is not
+    /// necessarily preserved.
+    #[inline]
+    pub fn smooth(&self, width: usize) -> Self {
+        let mut result = self.clone();
+        let len = self.bin.len();
+        for (i, bin) in result.bin.iter_mut().enumerate() {
+            let window = &self.bin[i.saturating_sub(width)..=i.saturating_add(width).min(len - 1)];
+            let n = window.len() as u64;
+            let sum: u64 = window.iter().sum();
+            *bin = (sum + n / 2) / n;
+        }
+        result
+    }
+
+    /// Return a copy of the histogram with the bin counts scaled
+    /// proportionally such that they sum to `new_total`.
+    ///
+    /// The scaled counts are rounded such that the rounding errors go to the
+    /// bins with the largest fractional parts first, and to the most populated
+    /// bins for equal fractional parts. An empty histogram is returned
+    /// unchanged.
+    pub fn normalize_to_total(&self, new_total: u64) -> Self {
+        let mut result = self.clone();
+        crate::histogram::histogram_normalize_to_total(&mut result.bin[..], new_total);
+        result
+    }
+
+    /// Return a copy of the histogram with the extreme bins set to zero.
+    ///
+    /// Bins are removed from the left while their cumulative count is
+    /// not larger than the fraction `lower_pct` of the total count, and
+    /// from the right while the cumulative count of the preceding bins
+    /// is not smaller than the fraction `1 - upper_pct` of the total
+    /// count.
+    pub fn trim(&self, lower_pct: f64, upper_pct: f64) -> Self {
+        let (start, end) =
+            crate::histogram::histogram_trim_range(self.iter().map(|(_, c)| c), lower_pct, upper_pct);
+        let mut result = self.clone();
+        for bin in &mut result.bin[..start] {
+            *bin = 0;
+        }
+        for bin in &mut result.bin[end..] {
+            *bin = 0;
+        }
+        result
+    }
+
+    /// Return a copy of the histogram with the bins whose center is
+    /// more than `n_std` standard deviations away from the mean set to
+    /// zero.
+    ///
+    /// The mean and standard deviation are estimated from the bin
+    /// centers, see `Histogram::histogram_mean()`.
+    pub fn trim_by_std(&self, n_std: f64) -> Self {
+        let (mean, variance) = crate::histogram::histogram_mean_variance(self);
+        let max_delta_2 = n_std * n_std * variance;
+        let mut result = self.clone();
+        for (((a, b), _), bin) in self.iter().zip(result.bin.iter_mut()) {
+            let delta = 0.5 * (a + b) - mean;
+            if delta * delta > max_delta_2 {
+                *bin = 0;
+            }
+        }
+        result
+    }
+
     /// Reset all bins to zero.
     #[inline]
     pub fn reset(&mut self) {
@@ -187,6 +400,451 @@ where
         }
     }
 
+    /// Return an iterator over the bins divided by the total count, i.e. the
+    /// probabilities of the bins.
+    ///
+    /// The items are 0 for an empty histogram.
+    #[inline]
+    pub fn bins_as_probabilities(&self) -> IterProbabilities<<&Self as IntoIterator>::IntoIter> {
+        let total: u64 = self.bins().iter().sum();
+        IterProbabilities {
+            histogram_iter: self.into_iter(),
+            total_inv: if total == 0 { 0. } else { 1. / (total as f64) },
+        }
+    }
+
+    /// Return an iterator over the bins divided by the total count and the
+    /// bin widths, i.e. the probability densities of the bins.
+    ///
+    /// The items are 0 for an empty histogram.
+    #[inline]
+    pub fn bins_as_densities(&self) -> IterDensities<<&Self as IntoIterator>::IntoIter> {
+        let total: u64 = self.bins().iter().sum();
+        IterDensities {
+            histogram_iter: self.into_iter(),
+            total_inv: if total == 0 { 0. } else { 1. / (total as f64) },
+        }
+    }
+
+    /// Return an iterator over the cumulative bin counts.
+    ///
+    /// The `i`-th item is the sum of the counts of the bins `0..=i`, so the
+    /// last item equals the total count.
+    #[inline]
+    pub fn cumulative_bins(&self) -> IterCumulative<'_> {
+        IterCumulative {
+            bins: self.bins().iter(),
+            sum: 0,
+        }
+    }
+
+    /// Return an iterator over the cumulative bin counts divided by the total
+    /// count.
+    ///
+    /// This is the empirical cumulative distribution function evaluated at the
+    /// upper bin boundaries. The items are NaN for an empty histogram.
+    #[inline]
+    pub fn cumulative_fractions(&self) -> IterCumulativeFractions<'_> {
+        let total: u64 = self.bins().iter().sum();
+        IterCumulativeFractions {
+            cumulative: self.cumulative_bins(),
+            total: total as f64,
+        }
+    }
+
+    /// Return an iterator over the probability mass function, i.e. the bin
+    /// counts divided by the total count.
+    ///
+    /// This is the same as `bins_as_probabilities`. The items sum to 1 for a
+    /// non-empty histogram and are 0 for an empty histogram.
+    #[inline]
+    pub fn pmf(&self) -> IterProbabilities<<&Self as IntoIterator>::IntoIter> {
+        self.bins_as_probabilities()
+    }
+
+    /// Return an iterator over the cumulative distribution function, i.e. the
+    /// running fraction of samples up to and including each bin.
+    ///
+    /// This is the same as `cumulative_fractions`. The last item is exactly 1
+    /// for a non-empty histogram. The items are NaN for an empty histogram.
+    #[inline]
+    pub fn cdf(&self) -> IterCumulativeFractions<'_> {
+        self.cumulative_fractions()
+    }
+
+    /// Estimate the mean of the sample from the bin centers, weighted by the
+    /// bin counts.
+    ///
+    /// Returns NaN for an empty histogram.
+    #[inline]
+    pub fn histogram_mean(&self) -> f64 {
+        crate::histogram::histogram_mean_variance(self).0
+    }
+
+    /// Estimate the population variance of the sample from the bin centers,
+    /// weighted by the bin counts.
+    ///
+    /// This neglects the spread of the samples within the bins. Returns NaN
+    /// for an empty histogram.
+    #[inline]
+    pub fn histogram_variance(&self) -> f64 {
+        crate::histogram::histogram_mean_variance(self).1
+    }
+
+    /// Return the range and count of the bin with the largest count.
+    ///
+    /// On ties, the first such bin is returned. Returns `None` if all bins are
+    /// empty.
+    #[inline]
+    pub fn max_bin(&self) -> Option<((f64, f64), u64)> {
+        let mut result: Option<((f64, f64), u64)> = None;
+        for (range, count) in self.into_iter() {
+            if count > 0 && result.map_or(true, |(_, max)| count > max) {
+                result = Some((range, count));
+            }
+        }
+        result
+    }
+
+    /// Return the range and count of the bin with the smallest non-zero count.
+    ///
+    /// On ties, the first such bin is returned. Returns `None` if all bins are
+    /// empty.
+    #[inline]
+    pub fn min_bin(&self) -> Option<((f64, f64), u64)> {
+        let mut result: Option<((f64, f64), u64)> = None;
+        for (range, count) in self.into_iter() {
+            if count > 0 && result.map_or(true, |(_, min)| count < min) {
+                result = Some((range, count));
+            }
+        }
+        result
+    }
+
+    /// Return the largest bin count.
+    ///
+    /// Returns `None` if all bins are empty.
+    #[inline]
+    pub fn max_bin_count(&self) -> Option<u64> {
+        self.bins().iter().copied().max().filter(|&count| count > 0)
+    }
+
+    /// Return the smallest non-zero bin count.
+    ///
+    /// Returns `None` if all bins are empty.
+    #[inline]
+    pub fn min_nonzero_bin_count(&self) -> Option<u64> {
+        self.bins().iter().copied().filter(|&count| count > 0).min()
+    }
+
+    /// Return the range of the bin with the largest count, i.e. of the mode.
+    ///
+    /// On ties, the first such bin is returned. Returns `(NaN, NaN)` if all
+    /// bins are empty.
+    #[inline]
+    pub fn mode_range(&self) -> (f64, f64) {
+        self.max_bin().map_or((f64::NAN, f64::NAN), |(range, _)| range)
+    }
+
+    /// Return the center of the bin with the largest count, i.e. of the mode.
+    ///
+    /// On ties, the first such bin is used. Returns NaN if all bins are empty.
+    #[inline]
+    pub fn mode_center(&self) -> f64 {
+        let (a, b) = self.mode_range();
+        0.5 * (a + b)
+    }
+
+    /// Return the fraction of samples below `x` (the percentile rank of `x`).
+    ///
+    /// This evaluates the empirical cumulative distribution function at `x`,
+    /// interpolating linearly within the bin containing `x`. Samples in bins of
+    /// infinite width are only counted once `x` is above the bin.
+    ///
+    /// Returns 0 for `x` below the range and 1 for `x` above the range of the
+    /// histogram. Returns NaN for an empty histogram or if `x` is NaN.
+    #[inline]
+    pub fn percentile_of_score(&self, x: f64) -> f64 {
+        let total: u64 = self.bins().iter().sum();
+        if total == 0 || x.is_nan() {
+            return f64::NAN;
+        }
+        let mut below = 0.;
+        for ((a, b), count) in self.into_iter() {
+            if x >= b {
+                below += count as f64;
+                continue;
+            }
+            let width = b - a;
+            if x > a && width.is_finite() {
+                below += count as f64 * (x - a) / width;
+            }
+            break;
+        }
+        below / (total as f64)
+    }
+
+    /// Return the total count, i.e. the sum of all bin counts.
+    #[inline]
+    pub fn total_count(&self) -> u64 {
+        self.bins().iter().sum()
+    }
+
+    /// Determine whether all bins are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bins().iter().all(|&count| count == 0)
+    }
+
+    /// Return the fraction of samples in the bins up to and including the bin
+    /// containing `x`.
+    ///
+    /// Unlike `percentile_of_score`, this does not interpolate within the bin
+    /// containing `x`.
+    ///
+    /// Returns 0 for `x` below the range and 1 for `x` above the range of the
+    /// histogram. Returns NaN for an empty histogram or if `x` is NaN.
+    #[inline]
+    pub fn fraction_below(&self, x: f64) -> f64 {
+        let total = self.total_count();
+        if total == 0 || x.is_nan() {
+            return f64::NAN;
+        }
+        let mut below = 0;
+        for ((a, _), count) in self.into_iter() {
+            if x < a {
+                break;
+            }
+            below += count;
+        }
+        below as f64 / total as f64
+    }
+
+    /// Estimate the value below which a fraction `p` of the samples falls.
+    ///
+    /// The samples are assumed to be uniformly distributed within the bins, so
+    /// the result is interpolated linearly within the bin containing the
+    /// `p`-th percentile. This is an approximation: the true percentile of
+    /// the samples lies in the same bin, so the error is bounded by the width
+    /// of the bin. For bins with an infinite boundary, the finite boundary is
+    /// returned instead.
+    ///
+    /// `p` is clamped to the interval from 0 to 1. Returns NaN for an empty
+    /// histogram or if `p` is NaN.
+    #[inline]
+    pub fn find_percentile(&self, p: f64) -> f64 {
+        let total = self.total_count();
+        if total == 0 || p.is_nan() {
+            return f64::NAN;
+        }
+        let target = p.clamp(0., 1.) * total as f64;
+        let mut below = 0.;
+        for ((a, b), count) in self.into_iter() {
+            if count == 0 {
+                continue;
+            }
+            let count = count as f64;
+            if below + count >= target {
+                if !a.is_finite() {
+                    return b;
+                }
+                if !b.is_finite() {
+                    return a;
+                }
+                return a + (target - below) / count * (b - a);
+            }
+            below += count;
+        }
+        unreachable!("the loop always reaches the total count")
+    }
+
+    /// Estimate the Shannon entropy (in nats) of the distribution from the
+    /// bin counts.
+    ///
+    /// This is `-sum(p_i * ln(p_i))`, where `p_i` is the fraction of samples
+    /// in bin `i`. Empty bins do not contribute. The widths of the bins are not
+    /// taken into account.
+    ///
+    /// Returns NaN for an empty histogram.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn entropy(&self) -> f64 {
+        let total = self.total_count();
+        if total == 0 {
+            return f64::NAN;
+        }
+        let total_inv = 1. / total as f64;
+        -self
+            .bins()
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 * total_inv;
+                p * num_traits::Float::ln(p)
+            })
+            .sum::<f64>()
+    }
+
+    /// Estimate the Kullback-Leibler divergence (in nats) of the distribution
+    /// `Q` given by `other` from the distribution `P` given by `self`.
+    ///
+    /// This is `sum(p_i * ln(p_i / q_i))`, where `p_i` and `q_i` are the
+    /// fractions of samples in bin `i`. Bins where `p_i` is zero do not
+    /// contribute. The result is infinite if `q_i` is zero for a bin where
+    /// `p_i` is not.
+    ///
+    /// Returns NaN if one of the histograms is empty. Panics if the histograms
+    /// have different ranges.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn kl_divergence(&self, other: &Self) -> f64 {
+        crate::histogram::histogram_assert_same_ranges(self, other);
+        crate::histogram::histogram_kl_divergence(self.bins(), other.bins())
+    }
+
+    /// Estimate the Jensen-Shannon divergence (in nats) of the distributions
+    /// given by `self` and `other`.
+    ///
+    /// This is the symmetrized `(KL(P || M) + KL(Q || M)) / 2`, where `M` is
+    /// the mixture `(P + Q) / 2`. Unlike the Kullback-Leibler divergence, it
+    /// is always finite, with values between 0 and `ln(2)`.
+    ///
+    /// Returns NaN if one of the histograms is empty. Panics if the histograms
+    /// have different ranges.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn js_divergence(&self, other: &Self) -> f64 {
+        crate::histogram::histogram_assert_same_ranges(self, other);
+        crate::histogram::histogram_js_divergence(self.bins(), other.bins())
+    }
+
+    /// Transform the histogram of `x` into a histogram of `f(x)` with
+    /// `n_output_bins` bins of constant width.
+    ///
+    /// The new histogram spans the images of the finite bin boundaries. The
+    /// count of each bin is moved to the bin containing the image of its
+    /// center, so the total count is preserved (except for bins whose center
+    /// is mapped to NaN, which are dropped), and the Jacobian `|f'(x)|` of
+    /// the transformation is accounted for by the widths of the new bins.
+    /// This is an approximation, which is better for narrow bins.
+    ///
+    /// Panics if `n_output_bins` is zero.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn apply_transform<F>(&self, f: F, n_output_bins: usize) -> crate::DynHistogram
+    where
+        F: Fn(f64) -> f64,
+    {
+        crate::histogram::histogram_apply_transform(self, f, n_output_bins)
+    }
+
+    /// Smooth the bin counts by convolving them with a Gaussian kernel.
+    ///
+    /// The standard deviation `sigma` of the kernel is given in units of bins,
+    /// and the kernel is truncated at `3 * sigma` or at the number of bins,
+    /// whichever is smaller. It is normalized, so the total count is preserved
+    /// except for the counts smoothed beyond the outermost bins. The widths of
+    /// the bins are not taken into account.
+    ///
+    /// Returns one smoothed count per bin. All of them are NaN if `sigma` is
+    /// not positive or not finite.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn smooth_gaussian(&self, sigma: f64) -> std::vec::Vec<f64> {
+        crate::histogram::histogram_smooth_gaussian(self.bins(), sigma)
+    }
+
+    /// Calculate the expected bin counts of `total` samples drawn from a
+    /// distribution with the probability density function `pdf`.
+    ///
+    /// The density is integrated over each bin using the midpoint rule, which
+    /// is an approximation that is better for narrow bins. Bins of infinite
+    /// width yield NaN.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn expected_counts<F>(&self, pdf: F, total: u64) -> std::vec::Vec<f64>
+    where
+        F: Fn(f64) -> f64,
+    {
+        crate::histogram::histogram_expected_counts(self, pdf, total)
+    }
+
+    /// Calculate Pearson's chi-squared statistic comparing the bin counts to
+    /// the ones expected for a distribution with the probability density
+    /// function `pdf`.
+    ///
+    /// The expected counts are calculated by `expected_counts` for the total
+    /// count of the histogram. Bins where both the observed and the expected
+    /// count are zero are skipped. The result is infinite if a sample falls
+    /// into a bin with an expected count of zero.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn chi_squared_vs_distribution<F>(&self, pdf: F) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        crate::histogram::histogram_chi_squared_vs_distribution(self, pdf)
+    }
+
+    /// Calculate Pearson's chi-squared statistic for the goodness of fit of
+    /// the bin counts to the expected fractions of the total count.
+    ///
+    /// `expected_fractions` must have one entry per bin, and the entries
+    /// should sum to 1. The statistic is `sum((observed - expected)^2 /
+    /// expected)`, where `expected` is the fraction times the total count.
+    /// Under the hypothesis that the samples follow the expected fractions, it
+    /// approximately follows a chi-squared distribution with `n_bins - 1`
+    /// degrees of freedom, from which the p-value can be obtained.
+    ///
+    /// Returns NaN if the length of `expected_fractions` differs from the
+    /// number of bins, or if an expected count is zero (in particular for an
+    /// empty histogram).
+    #[inline]
+    pub fn chi_squared_test(&self, expected_fractions: &[f64]) -> f64 {
+        crate::histogram::histogram_chi_squared_test(self.bins(), expected_fractions)
+    }
+
+    /// Draw a random sample from the distribution given by the histogram.
+    ///
+    /// A bin is chosen with a probability proportional to its count, and the
+    /// sample is drawn uniformly from the range of the bin. For bins with an
+    /// infinite boundary, the finite boundary is returned instead.
+    ///
+    /// Returns `None` for an empty histogram.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn sample<R>(&self, rng: &mut R) -> Option<f64>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let total: u64 = self.bins().iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut k = rng.gen_range(0..total);
+        for ((a, b), count) in self.into_iter() {
+            if k >= count {
+                k -= count;
+                continue;
+            }
+            return Some(match (a.is_finite(), b.is_finite()) {
+                _ if a == b => a,
+                (true, true) => rng.gen_range(a..b),
+                (true, false) => a,
+                (false, true) => b,
+                (false, false) => f64::NAN,
+            });
+        }
+        unreachable!("The sum of the bins is larger than the total")
+    }
+
     /// Return an iterator over the bin variances.
     ///
     /// This is more efficient than calling `variance()` for each bin.
@@ -219,8 +877,71 @@ impl<'a> ::core::iter::Iterator for IterHistogram<'a> {
         }
         None
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_bin.len(), Some(self.remaining_bin.len()))
+    }
+}
+
+impl<'a> ::core::iter::DoubleEndedIterator for IterHistogram<'a> {
+    fn next_back(&mut self) -> Option<((f64, f64), u64)> {
+        if let Some((&bin, rest)) = self.remaining_bin.split_last() {
+            let len = self.remaining_range.len();
+            let left = self.remaining_range[len - 2];
+            let right = self.remaining_range[len - 1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[..len - 1];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+}
+
+impl<'a> ::core::iter::ExactSizeIterator for IterHistogram<'a> {}
+
+/// Iterate over all `(range, &mut count)` pairs in the histogram.
+#[derive(Debug)]
+pub struct IterHistogramMut<'a> {
+    remaining_bin: &'a mut [u64],
+    remaining_range: &'a [f64],
+}
+
+impl<'a> ::core::iter::Iterator for IterHistogramMut<'a> {
+    type Item = ((f64, f64), &'a mut u64);
+    fn next(&mut self) -> Option<((f64, f64), &'a mut u64)> {
+        let remaining_bin = ::core::mem::take(&mut self.remaining_bin);
+        if let Some((bin, rest)) = remaining_bin.split_first_mut() {
+            let left = self.remaining_range[0];
+            let right = self.remaining_range[1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[1..];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_bin.len(), Some(self.remaining_bin.len()))
+    }
+}
+
+impl<'a> ::core::iter::DoubleEndedIterator for IterHistogramMut<'a> {
+    fn next_back(&mut self) -> Option<((f64, f64), &'a mut u64)> {
+        let remaining_bin = ::core::mem::take(&mut self.remaining_bin);
+        if let Some((bin, rest)) = remaining_bin.split_last_mut() {
+            let len = self.remaining_range.len();
+            let left = self.remaining_range[len - 2];
+            let right = self.remaining_range[len - 1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[..len - 1];
+            return Some(((left, right), bin));
+        }
+        None
+    }
 }
 
+impl<'a> ::core::iter::ExactSizeIterator for IterHistogramMut<'a> {}
+
 impl<'a, const LEN: usize> ::core::iter::IntoIterator for &'a Histogram<LEN>
 where
     [u8; LEN + 1]: Sized,
@@ -235,6 +956,17 @@ where
     }
 }
 
+impl<const LEN: usize> crate::Reset for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    /// Set all bin counts to zero, keeping the ranges.
+    #[inline]
+    fn reset(&mut self) {
+        self.bin = [0; LEN];
+    }
+}
+
 impl<'a, const LEN: usize> ::core::ops::AddAssign<&'a Self> for Histogram<LEN>
 where
     [u8; LEN + 1]: Sized,
@@ -250,6 +982,116 @@ where
     }
 }
 
+impl<'a, const LEN: usize> ::core::ops::SubAssign<&'a Self> for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    /// Subtract the bin counts of another histogram from this one, saturating
+    /// at zero.
+    ///
+    /// Panics if the histograms have different ranges. See `try_sub_assign`
+    /// for a non-panicking variant.
+    #[inline]
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = self.try_sub_assign(other) {
+            panic!("Both histograms must have the same ranges: {}", e);
+        }
+    }
+}
+
+impl<const LEN: usize> ::core::ops::AddAssign<(f64, SaturationMode)> for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    /// Add a sample to the histogram, handling samples out of range as
+    /// specified by the mode.
+    #[inline]
+    fn add_assign(&mut self, (x, mode): (f64, SaturationMode)) {
+        let found = if x.is_nan() { Err(SampleOutOfRangeError) } else { self.find(x) };
+        let i = match (found, mode) {
+            (Ok(i), _) => i,
+            (Err(_), SaturationMode::Saturate) if x < self.range_min() => 0,
+            (Err(_), SaturationMode::Saturate) if x >= self.range_max() => LEN - 1,
+            (Err(_), SaturationMode::Saturate) | (Err(_), SaturationMode::Ignore) => return,
+            (Err(_), SaturationMode::Panic) => {
+                panic!("Sample {} is out of range of the histogram", x)
+            }
+        };
+        self.bin[i] += 1;
+    }
+}
+
+impl<const LEN: usize> ::core::ops::AddAssign<f64> for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    /// Add a sample to the histogram, counting samples below the range in the
+    /// first bin and samples above the range in the last bin.
+    ///
+    /// NaN is ignored.
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        *self += (x, SaturationMode::Saturate);
+    }
+}
+
+impl<const LEN: usize> ::core::fmt::Display for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    /// Format the histogram as a table with the ranges, the counts and ASCII
+    /// bars.
+    ///
+    /// The precision specifies the width of the largest bar, by default it is
+    /// 40 characters.
+    fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        crate::histogram::fmt_table(formatter, self.iter())
+    }
+}
+
+impl<const LEN: usize> ::core::fmt::Binary for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    /// Format the histogram as a text bar chart.
+    ///
+    /// The precision specifies the width of the largest bar, by default it is
+    /// 40 characters.
+    fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        crate::histogram::fmt_bar_chart(formatter, self.iter())
+    }
+}
+
+impl<const LEN: usize> ::core::ops::Index<usize> for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    type Output = u64;
+
+    #[inline]
+    fn index(&self, bin: usize) -> &u64 {
+        let len = self.bin.len();
+        match self.bin.get(bin) {
+            Some(count) => count,
+            None => panic!("histogram bin index {} out of range [0, {})", bin, len),
+        }
+    }
+}
+
+impl<const LEN: usize> ::core::ops::IndexMut<usize> for Histogram<LEN>
+where
+    [u8; LEN + 1]: Sized,
+{
+    #[inline]
+    fn index_mut(&mut self, bin: usize) -> &mut u64 {
+        let len = self.bin.len();
+        match self.bin.get_mut(bin) {
+            Some(count) => count,
+            None => panic!("histogram bin index {} out of range [0, {})", bin, len),
+        }
+    }
+}
+
 impl<const LEN: usize> ::core::ops::MulAssign<u64> for Histogram<LEN>
 where
     [u8; LEN + 1]: Sized,
@@ -316,8 +1158,30 @@ where
             .next()
             .map(|((a, b), count)| (count as f64) / (b - a))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterNormalized<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|((a, b), count)| (count as f64) / (b - a))
+    }
 }
 
+impl<T> ExactSizeIterator for IterNormalized<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
 /// Iterate over the widths of the bins.
 #[derive(Clone, Debug)]
 pub struct IterWidths<T>
@@ -337,8 +1201,28 @@ where
     fn next(&mut self) -> Option<f64> {
         self.histogram_iter.next().map(|((a, b), _)| b - a)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
 }
 
+impl<T> DoubleEndedIterator for IterWidths<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter.next_back().map(|((a, b), _)| b - a)
+    }
+}
+
+impl<T> ExactSizeIterator for IterWidths<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
 /// Iterate over the bin centers.
 #[derive(Clone, Debug)]
 pub struct IterBinCenters<T>
@@ -358,8 +1242,28 @@ where
     fn next(&mut self) -> Option<f64> {
         self.histogram_iter.next().map(|((a, b), _)| 0.5 * (a + b))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
 }
 
+impl<T> DoubleEndedIterator for IterBinCenters<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter.next_back().map(|((a, b), _)| 0.5 * (a + b))
+    }
+}
+
+impl<T> ExactSizeIterator for IterBinCenters<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
 /// Iterate over the variances.
 #[derive(Clone, Debug)]
 pub struct IterVariances<T>
@@ -382,4 +1286,169 @@ where
             .next()
             .map(|(_, n)| multinomial_variance(n as f64, self.sum_inv))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterVariances<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|(_, n)| multinomial_variance(n as f64, self.sum_inv))
+    }
+}
+
+impl<T> ExactSizeIterator for IterVariances<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
+/// Iterate over the probabilities of the bins.
+#[derive(Debug, Clone)]
+pub struct IterProbabilities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    histogram_iter: T,
+    total_inv: f64,
+}
+
+impl<T> Iterator for IterProbabilities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next()
+            .map(|(_, count)| (count as f64) * self.total_inv)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterProbabilities<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|(_, count)| (count as f64) * self.total_inv)
+    }
+}
+
+impl<T> ExactSizeIterator for IterProbabilities<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
+/// Iterate over the probability densities of the bins.
+#[derive(Debug, Clone)]
+pub struct IterDensities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    histogram_iter: T,
+    total_inv: f64,
+}
+
+impl<T> Iterator for IterDensities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next()
+            .map(|((a, b), count)| (count as f64) * self.total_inv / (b - a))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterDensities<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|((a, b), count)| (count as f64) * self.total_inv / (b - a))
+    }
 }
+
+impl<T> ExactSizeIterator for IterDensities<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
+/// Iterate over the cumulative bin counts.
+#[derive(Debug, Clone)]
+pub struct IterCumulative<'a> {
+    bins: core::slice::Iter<'a, u64>,
+    sum: u64,
+}
+
+impl<'a> Iterator for IterCumulative<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        self.bins.next().map(|&count| {
+            self.sum += count;
+            self.sum
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bins.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for IterCumulative<'a> {}
+
+/// Iterate over the cumulative bin counts divided by the total count.
+#[derive(Debug, Clone)]
+pub struct IterCumulativeFractions<'a> {
+    cumulative: IterCumulative<'a>,
+    total: f64,
+}
+
+impl<'a> Iterator for IterCumulativeFractions<'a> {
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.cumulative
+            .next()
+            .map(|sum| (sum as f64) / self.total)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cumulative.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for IterCumulativeFractions<'a> {}
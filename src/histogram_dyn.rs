@@ -0,0 +1,403 @@
+use std::vec::Vec;
+
+use crate::{FromDataError, InvalidRangeError, Merge, Reset, SampleOutOfRangeError};
+
+/// A histogram with a number of bins determined at runtime.
+///
+/// The bins and ranges are allocated on the heap. Otherwise, this behaves like
+/// the histograms defined by [`define_histogram`].
+///
+/// [`define_histogram`]: ./macro.define_histogram.html
+#[derive(Debug, Clone)]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct DynHistogram {
+    /// The ranges defining the bins of the histogram.
+    range: Vec<f64>,
+    /// The bins of the histogram.
+    bin: Vec<u64>,
+}
+
+impl DynHistogram {
+    /// Construct a histogram with `n_bins` bins of constant width.
+    ///
+    /// Panics if `n_bins` is zero.
+    #[inline]
+    pub fn with_const_width(start: f64, end: f64, n_bins: usize) -> Self {
+        assert!(n_bins > 0, "A histogram needs at least one bin");
+        let step = (end - start) / (n_bins as f64);
+        let range = (0..=n_bins).map(|i| start + step * (i as f64)).collect();
+
+        Self {
+            range,
+            bin: std::vec![0; n_bins],
+        }
+    }
+
+    /// Construct a histogram from given ranges.
+    ///
+    /// The ranges are given by an iterator of floats where neighboring
+    /// pairs `(a, b)` define a bin for all `x` where `a <= x < b`. The number
+    /// of bins is one less than the number of ranges.
+    ///
+    /// Fails if the iterator is too short (less than 2 ranges), is not sorted
+    /// or contains `nan`. `inf` and empty ranges are allowed.
+    #[inline]
+    pub fn from_ranges<T>(ranges: T) -> Result<Self, InvalidRangeError>
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut range: Vec<f64> = Vec::new();
+        for r in ranges {
+            if r.is_nan() {
+                return Err(InvalidRangeError::NaN);
+            }
+            if let Some(&last) = range.last() {
+                if last > r {
+                    return Err(InvalidRangeError::NotSorted);
+                }
+            }
+            range.push(r);
+        }
+        if range.len() < 2 {
+            return Err(InvalidRangeError::NotEnoughRanges);
+        }
+        let n_bins = range.len() - 1;
+        Ok(Self {
+            range,
+            bin: std::vec![0; n_bins],
+        })
+    }
+
+    /// Construct a histogram with constant bin width covering the range of
+    /// the given samples and add them.
+    ///
+    /// The number of bins is chosen by Sturges' rule, `ceil(log2(n) + 1)` for
+    /// `n` samples. See `from_data_with_bins` for details on the range.
+    ///
+    /// Fails if `data` is empty or contains `nan` or `inf`.
+    #[inline]
+    pub fn from_data(data: &[f64]) -> Result<Self, FromDataError> {
+        let n_bins = ((data.len() as f64).log2() + 1.).ceil().max(1.) as usize;
+        Self::from_data_with_bins(data, n_bins)
+    }
+
+    /// Construct a histogram with `n_bins` bins of constant width covering
+    /// the range of the given samples and add them.
+    ///
+    /// The range starts at the smallest sample and ends just above the largest
+    /// one. If all samples are equal, the range is centered on them and has
+    /// width 1, or more if the samples are large.
+    ///
+    /// Fails if `data` is empty or contains `nan` or `inf`, or if the width of
+    /// the range is too large to be represented. Panics if `n_bins` is zero.
+    #[inline]
+    pub fn from_data_with_bins(data: &[f64], n_bins: usize) -> Result<Self, FromDataError> {
+        let (start, end) = crate::histogram_data_range(data)?;
+        let mut h = Self::with_const_width(start, end, n_bins);
+        // Make sure the largest sample is not lost to rounding errors.
+        h.range[n_bins] = end;
+        for &x in data {
+            let _ = h.add(x);
+        }
+        Ok(h)
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn n_bins(&self) -> usize {
+        self.bin.len()
+    }
+
+    /// Find the index of the bin corresponding to the given sample.
+    ///
+    /// Fails if the sample is out of range of the histogram.
+    #[inline]
+    pub fn find(&self, x: f64) -> Result<usize, SampleOutOfRangeError> {
+        // We made sure our ranges are valid at construction, so we can
+        // safely unwrap.
+        let len = self.bin.len();
+        match self.range.binary_search_by(|p| p.partial_cmp(&x).unwrap()) {
+            Ok(i) if i < len => Ok(i),
+            Err(i) if i > 0 && i < len + 1 => Ok(i - 1),
+            _ => Err(SampleOutOfRangeError),
+        }
+    }
+
+    /// Add a sample to the histogram.
+    ///
+    /// Fails if the sample is out of range of the histogram.
+    #[inline]
+    pub fn add(&mut self, x: f64) -> Result<(), SampleOutOfRangeError> {
+        if let Ok(i) = self.find(x) {
+            self.bin[i] += 1;
+            Ok(())
+        } else {
+            Err(SampleOutOfRangeError)
+        }
+    }
+
+    /// Return the bins of the histogram for modification.
+    #[inline]
+    pub(crate) fn bins_mut(&mut self) -> &mut [u64] {
+        &mut self.bin[..]
+    }
+
+    /// Return the ranges of the histogram.
+    #[inline]
+    pub fn ranges(&self) -> &[f64] {
+        &self.range[..]
+    }
+
+    /// Return an iterator over the bins and corresponding ranges:
+    /// `((lower, upper), count)`
+    #[inline]
+    pub fn iter(&self) -> IterDynHistogram<'_> {
+        self.into_iter()
+    }
+
+    /// Return a copy of the histogram with the bin counts smoothed by a moving
+    /// average.
+    ///
+    /// The count of each bin is replaced by the average of the `width` bins on
+    /// each side and the bin itself, rounded to the nearest integer. The window
+    /// is truncated at the boundaries of the histogram. The total count is not
+    /// necessarily preserved.
+    #[inline]
+    pub fn smooth(&self, width: usize) -> Self {
+        let mut result = self.clone();
+        let len = self.bin.len();
+        for (i, bin) in result.bin.iter_mut().enumerate() {
+            let window = &self.bin[i.saturating_sub(width)..=i.saturating_add(width).min(len - 1)];
+            let n = window.len() as u64;
+            let sum: u64 = window.iter().sum();
+            *bin = (sum + n / 2) / n;
+        }
+        result
+    }
+
+    /// Return a copy of the histogram with the bin counts scaled
+    /// proportionally such that they sum to `new_total`.
+    ///
+    /// The scaled counts are rounded such that the rounding errors go to the
+    /// bins with the largest fractional parts first, and to the most populated
+    /// bins for equal fractional parts. An empty histogram is returned
+    /// unchanged.
+    pub fn normalize_to_total(&self, new_total: u64) -> Self {
+        let mut result = self.clone();
+        crate::histogram::histogram_normalize_to_total(&mut result.bin[..], new_total);
+        result
+    }
+
+    /// Return a copy of the histogram with the extreme bins set to zero.
+    ///
+    /// Bins are removed from the left while their cumulative count is
+    /// not larger than the fraction `lower_pct` of the total count, and
+    /// from the right while the cumulative count of the preceding bins
+    /// is not smaller than the fraction `1 - upper_pct` of the total
+    /// count.
+    pub fn trim(&self, lower_pct: f64, upper_pct: f64) -> Self {
+        let (start, end) =
+            crate::histogram::histogram_trim_range(self.iter().map(|(_, c)| c), lower_pct, upper_pct);
+        let mut result = self.clone();
+        for bin in &mut result.bin[..start] {
+            *bin = 0;
+        }
+        for bin in &mut result.bin[end..] {
+            *bin = 0;
+        }
+        result
+    }
+
+    /// Return a copy of the histogram with the bins whose center is
+    /// more than `n_std` standard deviations away from the mean set to
+    /// zero.
+    ///
+    /// The mean and standard deviation are estimated from the bin
+    /// centers, see `Histogram::histogram_mean()`.
+    pub fn trim_by_std(&self, n_std: f64) -> Self {
+        let (mean, variance) = crate::histogram::histogram_mean_variance(self);
+        let max_delta_2 = n_std * n_std * variance;
+        let mut result = self.clone();
+        for (((a, b), _), bin) in self.iter().zip(result.bin.iter_mut()) {
+            let delta = 0.5 * (a + b) - mean;
+            if delta * delta > max_delta_2 {
+                *bin = 0;
+            }
+        }
+        result
+    }
+
+    /// Reset all bins to zero.
+    #[inline]
+    pub fn reset(&mut self) {
+        for x in &mut self.bin {
+            *x = 0;
+        }
+    }
+
+    /// Return the lower range limit.
+    ///
+    /// (The corresponding bin might be empty.)
+    #[inline]
+    pub fn range_min(&self) -> f64 {
+        self.range[0]
+    }
+
+    /// Return the upper range limit.
+    ///
+    /// (The corresponding bin might be empty.)
+    #[inline]
+    pub fn range_max(&self) -> f64 {
+        self.range[self.bin.len()]
+    }
+}
+
+/// Iterate over all `(range, count)` pairs in a `DynHistogram`.
+#[derive(Debug, Clone)]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct IterDynHistogram<'a> {
+    remaining_bin: &'a [u64],
+    remaining_range: &'a [f64],
+}
+
+impl Iterator for IterDynHistogram<'_> {
+    type Item = ((f64, f64), u64);
+    fn next(&mut self) -> Option<((f64, f64), u64)> {
+        if let Some((&bin, rest)) = self.remaining_bin.split_first() {
+            let left = self.remaining_range[0];
+            let right = self.remaining_range[1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[1..];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_bin.len(), Some(self.remaining_bin.len()))
+    }
+}
+
+impl DoubleEndedIterator for IterDynHistogram<'_> {
+    fn next_back(&mut self) -> Option<((f64, f64), u64)> {
+        if let Some((&bin, rest)) = self.remaining_bin.split_last() {
+            let len = self.remaining_range.len();
+            let left = self.remaining_range[len - 2];
+            let right = self.remaining_range[len - 1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[..len - 1];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+}
+
+impl ExactSizeIterator for IterDynHistogram<'_> {}
+
+impl<'a> IntoIterator for &'a DynHistogram {
+    type Item = ((f64, f64), u64);
+    type IntoIter = IterDynHistogram<'a>;
+    fn into_iter(self) -> IterDynHistogram<'a> {
+        IterDynHistogram {
+            remaining_bin: &self.bin[..],
+            remaining_range: &self.range[..],
+        }
+    }
+}
+
+impl crate::Histogram for DynHistogram {
+    #[inline]
+    fn bins(&self) -> &[u64] {
+        &self.bin[..]
+    }
+}
+
+impl Reset for DynHistogram {
+    /// Set all bin counts to zero, keeping the ranges.
+    #[inline]
+    fn reset(&mut self) {
+        for b in self.bin.iter_mut() {
+            *b = 0;
+        }
+    }
+}
+
+impl core::ops::AddAssign<&DynHistogram> for DynHistogram {
+    #[inline]
+    fn add_assign(&mut self, other: &Self) {
+        self.merge(other);
+    }
+}
+
+impl core::fmt::Display for DynHistogram {
+    /// Format the histogram as a table with the ranges, the counts and ASCII
+    /// bars.
+    ///
+    /// The precision specifies the width of the largest bar, by default it is
+    /// 40 characters.
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::histogram::fmt_table(formatter, self.iter())
+    }
+}
+
+impl core::fmt::Binary for DynHistogram {
+    /// Format the histogram as a text bar chart.
+    ///
+    /// The precision specifies the width of the largest bar, by default it is
+    /// 40 characters.
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::histogram::fmt_bar_chart(formatter, self.iter())
+    }
+}
+
+impl core::ops::Index<usize> for DynHistogram {
+    type Output = u64;
+
+    #[inline]
+    fn index(&self, bin: usize) -> &u64 {
+        let len = self.bin.len();
+        match self.bin.get(bin) {
+            Some(count) => count,
+            None => panic!("histogram bin index {} out of range [0, {})", bin, len),
+        }
+    }
+}
+
+impl core::ops::IndexMut<usize> for DynHistogram {
+    #[inline]
+    fn index_mut(&mut self, bin: usize) -> &mut u64 {
+        let len = self.bin.len();
+        match self.bin.get_mut(bin) {
+            Some(count) => count,
+            None => panic!("histogram bin index {} out of range [0, {})", bin, len),
+        }
+    }
+}
+
+impl core::ops::MulAssign<u64> for DynHistogram {
+    #[inline]
+    fn mul_assign(&mut self, other: u64) {
+        for x in &mut self.bin {
+            *x *= other;
+        }
+    }
+}
+
+impl Merge for DynHistogram {
+    fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            self.bin.len(),
+            other.bin.len(),
+            "Both histograms must have the same number of bins"
+        );
+        if let Err(e) = crate::histogram::histogram_check_same_ranges(
+            self.range.iter().copied(),
+            other.range.iter().copied(),
+        ) {
+            panic!("Both histograms must have the same ranges: {}", e);
+        }
+        for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
+            *a += *b;
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::vec::Vec;
+
+/// A histogram with floating point bins, for example probabilities.
+///
+/// This is obtained from a histogram of counts via
+/// [`Histogram::to_probability_histogram`] or
+/// [`Histogram::to_density_histogram`].
+///
+/// [`Histogram::to_probability_histogram`]: ./trait.Histogram.html#method.to_probability_histogram
+/// [`Histogram::to_density_histogram`]: ./trait.Histogram.html#method.to_density_histogram
+#[derive(Debug, Clone)]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct FloatHistogram {
+    /// The ranges defining the bins of the histogram.
+    range: Vec<f64>,
+    /// The bins of the histogram.
+    bin: Vec<f64>,
+}
+
+impl FloatHistogram {
+    /// Construct a histogram from the given ranges and bins.
+    ///
+    /// There has to be one more range than bins.
+    #[inline]
+    pub(crate) fn from_parts(range: Vec<f64>, bin: Vec<f64>) -> Self {
+        debug_assert_eq!(range.len(), bin.len() + 1);
+        Self { range, bin }
+    }
+
+    /// Divide the bins by their widths.
+    #[inline]
+    pub(crate) fn into_density(mut self) -> Self {
+        for (bin, r) in self.bin.iter_mut().zip(self.range.windows(2)) {
+            *bin /= r[1] - r[0];
+        }
+        self
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn n_bins(&self) -> usize {
+        self.bin.len()
+    }
+
+    /// Return the bins of the histogram.
+    #[inline]
+    pub fn bins(&self) -> &[f64] {
+        &self.bin[..]
+    }
+
+    /// Return the ranges of the histogram.
+    #[inline]
+    pub fn ranges(&self) -> &[f64] {
+        &self.range[..]
+    }
+
+    /// Return the sum of all bins.
+    ///
+    /// This is 1 for a probability histogram.
+    #[inline]
+    pub fn total(&self) -> f64 {
+        self.bin.iter().sum()
+    }
+
+    /// Return an iterator over the bins and corresponding ranges:
+    /// `((lower, upper), value)`
+    #[inline]
+    pub fn iter(&self) -> IterFloatHistogram<'_> {
+        self.into_iter()
+    }
+
+    /// Return an iterator over the bin widths.
+    #[inline]
+    pub fn widths(&self) -> impl Iterator<Item = f64> + '_ {
+        self.range.windows(2).map(|r| r[1] - r[0])
+    }
+
+    /// Return an iterator over the bin centers.
+    #[inline]
+    pub fn centers(&self) -> impl Iterator<Item = f64> + '_ {
+        self.range.windows(2).map(|r| 0.5 * (r[0] + r[1]))
+    }
+
+    /// Return the lower range limit.
+    #[inline]
+    pub fn range_min(&self) -> f64 {
+        self.range[0]
+    }
+
+    /// Return the upper range limit.
+    #[inline]
+    pub fn range_max(&self) -> f64 {
+        self.range[self.bin.len()]
+    }
+}
+
+/// Iterate over all `(range, value)` pairs in a `FloatHistogram`.
+#[derive(Debug, Clone)]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct IterFloatHistogram<'a> {
+    remaining_bin: &'a [f64],
+    remaining_range: &'a [f64],
+}
+
+impl Iterator for IterFloatHistogram<'_> {
+    type Item = ((f64, f64), f64);
+    fn next(&mut self) -> Option<((f64, f64), f64)> {
+        if let Some((&bin, rest)) = self.remaining_bin.split_first() {
+            let left = self.remaining_range[0];
+            let right = self.remaining_range[1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[1..];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_bin.len(), Some(self.remaining_bin.len()))
+    }
+}
+
+impl DoubleEndedIterator for IterFloatHistogram<'_> {
+    fn next_back(&mut self) -> Option<((f64, f64), f64)> {
+        if let Some((&bin, rest)) = self.remaining_bin.split_last() {
+            let len = self.remaining_range.len();
+            let left = self.remaining_range[len - 2];
+            let right = self.remaining_range[len - 1];
+            self.remaining_bin = rest;
+            self.remaining_range = &self.remaining_range[..len - 1];
+            return Some(((left, right), bin));
+        }
+        None
+    }
+}
+
+impl ExactSizeIterator for IterFloatHistogram<'_> {}
+
+impl<'a> IntoIterator for &'a FloatHistogram {
+    type Item = ((f64, f64), f64);
+    type IntoIter = IterFloatHistogram<'a>;
+    fn into_iter(self) -> IterFloatHistogram<'a> {
+        IterFloatHistogram {
+            remaining_bin: &self.bin[..],
+            remaining_range: &self.range[..],
+        }
+    }
+}
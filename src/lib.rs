@@ -20,9 +20,9 @@
 //! include `"serde1"` in your list of features.
 //!
 //! Note that deserializing does not currently check for all invalid inputs.
-//! For example, if you deserialize a corrupted [`Variance`] it may return
-//! a negative value for variance, even though that is mathematically impossible.
-//! In a future minor release some of these checks may be added.
+//! The states of [`Variance`], [`Skewness`] and [`Kurtosis`] are validated
+//! (for example, a negative sum of squares is rejected with an
+//! [`InvalidEstimatorState`] error), but other estimators are not yet.
 //!
 //!
 //! ### Example
@@ -39,13 +39,23 @@
 //! ## Estimators
 //!
 //! * Mean ([`Mean`]) and its error ([`MeanWithError`]).
-//! * Weighted mean ([`WeightedMean`]) and its error
-//!   ([`WeightedMeanWithError`]).
+//! * Geometric mean ([`GeometricMean`]) and harmonic mean ([`HarmonicMean`]).
+//! * Weighted mean ([`WeightedMean`]), its error
+//!   ([`WeightedMeanWithError`]) and weighted variance ([`WeightedVariance`]).
 //! * Variance ([`Variance`]), skewness ([`Skewness`]) and kurtosis
 //!   ([`Kurtosis`]).
 //! * Arbitrary higher moments ([`define_moments`]).
-//! * Quantiles ([`Quantile`]).
+//! * Quantiles ([`Quantile`], [`MultiQuantile`]).
+//! * Trimmed mean ([`TrimmedMean`]).
+//! * Median absolute deviation ([`MedianAbsoluteDeviation`]).
 //! * Minimum ([`Min`]) and maximum ([`Max`]).
+//! * Exponentially weighted moving average ([`Ewma`]) and variance
+//!   ([`EwmaVariance`]).
+//! * Mean ([`RollingMean`]) and variance ([`RollingVariance`]) of a sliding
+//!   window.
+//! * Autocorrelation of a time series ([`Autocorrelation`]).
+//! * Circular mean of angles ([`CircularMean`]).
+//! * Summary statistics for reporting ([`Population`]).
 //!
 //!
 //! ## Estimating several statistics at once
@@ -57,6 +67,8 @@
 //! should be no overhead.
 //!
 //! You can avoid the boilerplate code by using the [`concatenate`] macro.
+//! For exploratory analysis, [`five_number_summary`] and [`full_summary`]
+//! calculate common statistics in a single pass.
 //!
 //! Note that calculating moments requires calculating the lower moments, so you
 //! only need to include the highest moment in your struct.
@@ -69,22 +81,44 @@
 //! `define_histogram!(..., 10)`) and the extension trait [`Histogram`]
 //! for the methods available to the generated struct.
 //!
+//! If the `std` feature is enabled, [`DynHistogram`] provides a histogram
+//! with a number of bins determined at runtime, and [`FloatHistogram`] a
+//! histogram of probabilities.
+//!
 //!
 //! [`Mean`]: ./struct.Mean.html
 //! [`MeanWithError`]: ./type.MeanWithError.html
+//! [`GeometricMean`]: ./struct.GeometricMean.html
+//! [`HarmonicMean`]: ./struct.HarmonicMean.html
 //! [`WeightedMean`]: ./struct.WeightedMean.html
 //! [`WeightedMeanWithError`]: ./struct.WeightedMeanWithError.html
+//! [`WeightedVariance`]: ./struct.WeightedVariance.html
 //! [`Variance`]: ./struct.Variance.html
 //! [`Skewness`]: ./struct.Skewness.html
 //! [`Kurtosis`]: ./struct.Kurtosis.html
 //! [`Quantile`]: ./struct.Quantile.html
+//! [`InvalidEstimatorState`]: ./struct.InvalidEstimatorState.html
+//! [`MultiQuantile`]: ./struct.MultiQuantile.html
+//! [`TrimmedMean`]: ./struct.TrimmedMean.html
+//! [`MedianAbsoluteDeviation`]: ./struct.MedianAbsoluteDeviation.html
 //! [`Min`]: ./struct.Min.html
 //! [`Max`]: ./struct.Max.html
+//! [`Ewma`]: ./struct.Ewma.html
+//! [`EwmaVariance`]: ./struct.EwmaVariance.html
+//! [`RollingMean`]: ./struct.RollingMean.html
+//! [`RollingVariance`]: ./struct.RollingVariance.html
+//! [`Autocorrelation`]: ./struct.Autocorrelation.html
+//! [`CircularMean`]: ./struct.CircularMean.html
+//! [`Population`]: ./struct.Population.html
 //! [`concatenate`]: ./macro.concatenate.html
+//! [`five_number_summary`]: ./fn.five_number_summary.html
+//! [`full_summary`]: ./fn.full_summary.html
 //! [`define_moments`]: ./macro.define_moments.html
 //! [`define_histogram`]: ./macro.define_histogram.html
 //! [`Histogram10`]: ./struct.Histogram10.html
 //! [`Histogram`]: ./trait.Histogram.html
+//! [`DynHistogram`]: ./struct.DynHistogram.html
+//! [`FloatHistogram`]: ./struct.FloatHistogram.html
 
 #![cfg_attr(doc_cfg, feature(doc_cfg))]
 #![allow(clippy::float_cmp, clippy::suspicious_operation_groupings)]
@@ -99,32 +133,92 @@
 mod macros;
 #[macro_use]
 mod moments;
+mod autocorrelation;
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+mod circular;
+mod ewma;
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+mod mad;
 mod minmax;
 #[cfg(any(feature = "std", feature = "libm"))]
 #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
 mod quantile;
+mod rolling;
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+mod summary;
 mod traits;
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+mod trimmed_mean;
 mod weighted_mean;
 #[macro_use]
 mod histogram;
 #[cfg(feature = "nightly")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "nightly")))]
 pub mod histogram_const;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+mod histogram_dyn;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+mod histogram_float;
 mod covariance;
 
 #[cfg(any(feature = "std", feature = "libm"))]
 #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
-pub use crate::moments::{Kurtosis, Skewness};
-pub use crate::moments::{Mean, MeanWithError, Variance};
+pub use crate::moments::{
+    cohen_d, cohen_d_confidence_interval, t_critical, GeometricMean, Kurtosis, Skewness,
+    StandardScores,
+};
+pub use crate::moments::{
+    HarmonicMean, InvalidEstimatorState, Mean, MeanWithError, OverflowError, Population, Variance,
+};
 
-pub use crate::histogram::{InvalidRangeError, SampleOutOfRangeError};
+pub use crate::autocorrelation::Autocorrelation;
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+pub use crate::circular::CircularMean;
+pub use crate::ewma::{Ewma, EwmaVariance};
+pub use crate::histogram::{
+    FromDataError, IncompatibleRangesError, InvalidRangeError, RebinError,
+    SampleOutOfRangeError,
+};
+#[doc(hidden)]
+pub use crate::histogram::{
+    fmt_bar_chart, fmt_table, histogram_check_same_ranges, histogram_data_range,
+    histogram_mean_variance, histogram_normalize_to_total, histogram_trim_range,
+};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::histogram_dyn::{DynHistogram, IterDynHistogram};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::histogram_float::{FloatHistogram, IterFloatHistogram};
 pub use crate::minmax::{Max, Min};
 #[cfg(any(feature = "std", feature = "libm"))]
 #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
-pub use crate::quantile::Quantile;
-pub use crate::traits::{Estimate, Histogram, Merge};
-pub use crate::weighted_mean::{WeightedMean, WeightedMeanWithError};
-pub use crate::covariance::Covariance;
+pub use crate::mad::MedianAbsoluteDeviation;
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+pub use crate::quantile::{InvalidQuantileError, MultiQuantile, Quantile};
+pub use crate::rolling::{RollingMean, RollingVariance};
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+pub use crate::summary::{five_number_summary, full_summary, FiveNumberSummary, FullSummary};
+pub use crate::traits::{Estimate, Histogram, Merge, Reset};
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+pub use crate::trimmed_mean::TrimmedMean;
+pub use crate::weighted_mean::{WeightedMean, WeightedMeanWithError, WeightedVariance};
+pub use crate::covariance::{
+    Covariance, LinearRegression, MismatchedSliceLengths, MovingCovariance, WeightedCovariance,
+};
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+pub use crate::covariance::partial_correlation;
 
 define_histogram!(hist, 10);
 pub use crate::hist::Histogram as Histogram10;
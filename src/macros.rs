@@ -1,7 +1,7 @@
 /// Assert that two numbers are almost equal to each other.
 ///
 /// On panic, this macro will print the values of the expressions with their
-/// debug representations.
+/// debug representations, followed by the optional custom message.
 #[macro_export]
 macro_rules! assert_almost_eq {
     ($a:expr, $b:expr, $prec:expr) => {
@@ -14,6 +14,99 @@ macro_rules! assert_almost_eq {
             );
         }
     };
+    ($a:expr, $b:expr, $prec:expr, $($arg:tt)+) => {
+        let diff = ($a - $b).abs();
+        if diff > $prec {
+            panic!(
+                "assertion failed: `abs(left - right) = {:.1e} < {:e}`, \
+                   (left: `{}`, right: `{}`): {}",
+                diff, $prec, $a, $b, format_args!($($arg)+)
+            );
+        }
+    };
+}
+
+/// Assert that two estimators agree on their sample size and statistics.
+///
+/// The sample sizes must be equal, the statistics must be almost equal to each
+/// other within the given precision. Statistics that are NaN for both
+/// estimators are considered equal. By default, `mean` and `sample_variance`
+/// are compared, other statistics can be given as a list of method names.
+///
+/// On panic, this macro will print the name of the diverging statistic and
+/// the values of both estimators.
+///
+///
+/// # Example
+///
+/// ```
+/// use average::{assert_estimators_eq, Merge, Variance};
+///
+/// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+/// let (left, right) = sequence.split_at(3);
+/// let total: Variance = sequence.iter().collect();
+/// let mut merged: Variance = left.iter().collect();
+/// merged.merge(&right.iter().collect());
+/// assert_estimators_eq!(total, merged, 1e-14);
+///
+/// let a: Variance = sequence.iter().collect();
+/// let b: Variance = sequence.iter().rev().collect();
+/// assert_estimators_eq!(a, b, 1e-14, [mean, population_variance]);
+/// ```
+#[macro_export]
+macro_rules! assert_estimators_eq {
+    (@statistic $a:ident, $b:ident, $prec:ident, $statistic:ident) => {
+        let (x, y): (f64, f64) = ($a.$statistic(), $b.$statistic());
+        assert_eq!(
+            x.is_nan(), y.is_nan(),
+            "estimators differ in `{}()`: (left: `{}`, right: `{}`)",
+            stringify!($statistic), x, y
+        );
+        if !x.is_nan() {
+            $crate::assert_almost_eq!(
+                x, y, $prec,
+                "estimators differ in `{}()`", stringify!($statistic)
+            );
+        }
+    };
+    ($a:expr, $b:expr, $prec:expr) => {
+        $crate::assert_estimators_eq!($a, $b, $prec, [mean, sample_variance]);
+    };
+    ($a:expr, $b:expr, $prec:expr, [$($statistic:ident),+ $(,)?]) => {
+        let (a, b, prec): (_, _, f64) = (&$a, &$b, $prec);
+        assert_eq!(a.len(), b.len(), "estimators differ in `len()`");
+        $(
+            $crate::assert_estimators_eq!(@statistic a, b, prec, $statistic);
+        )+
+    };
+}
+
+/// Assert that two kurtosis estimators agree on their sample size, mean,
+/// sample variance, skewness and kurtosis.
+///
+/// See [`assert_estimators_eq`](./macro.assert_estimators_eq.html).
+#[macro_export]
+macro_rules! assert_kurtosis_eq {
+    ($a:expr, $b:expr, $prec:expr) => {
+        $crate::assert_estimators_eq!(
+            $a, $b, $prec,
+            [mean, sample_variance, skewness, kurtosis]
+        );
+    };
+}
+
+/// Assert that two covariance estimators agree on their sample size, means,
+/// sample variances and sample covariance.
+///
+/// See [`assert_estimators_eq`](./macro.assert_estimators_eq.html).
+#[macro_export]
+macro_rules! assert_covariance_eq {
+    ($a:expr, $b:expr, $prec:expr) => {
+        $crate::assert_estimators_eq!(
+            $a, $b, $prec,
+            [mean_x, mean_y, sample_variance_x, sample_variance_y, sample_covariance]
+        );
+    };
 }
 
 /// Concatenate several iterative estimators into one.
@@ -32,7 +125,10 @@ macro_rules! assert_almost_eq {
 ///
 /// The following methods will be implemented: `new`, `add`, `$statistic`.
 ///
-/// The following traits will be implemented: `Default`, `FromIterator<f64>`.
+/// The following traits will be implemented: `Default`, `FromIterator<f64>`
+/// and, if all estimators implement it, `Merge`. Merging forwards to the
+/// `merge` method of each field, which is correct because all fields see the
+/// same samples.
 ///
 ///
 /// # Examples
@@ -42,16 +138,21 @@ macro_rules! assert_almost_eq {
 ///
 /// concatenate!(MinMax, [Min, min], [Max, max]);
 ///
-/// let s: MinMax = (1..6).map(f64::from).collect();
+/// let mut s: MinMax = (1..6).map(f64::from).collect();
 ///
 /// assert_eq!(s.min(), 1.0);
 /// assert_eq!(s.max(), 5.0);
+///
+/// // `MinMax` implements `Merge`, because `Min` and `Max` do.
+/// use average::Merge;
+/// s.merge(&(6..9).map(f64::from).collect());
+/// assert_eq!(s.max(), 8.0);
 /// ```
 ///
 /// The generated code looks roughly like this:
 ///
 /// ```
-/// # use average::{Min, Max, Estimate};
+/// # use average::{Min, Max, Estimate, Merge};
 /// #
 /// struct MinMax {
 ///     min: Min,
@@ -76,6 +177,13 @@ macro_rules! assert_almost_eq {
 ///         self.max.max()
 ///     }
 /// }
+///
+/// impl Merge for MinMax {
+///     fn merge(&mut self, other: &Self) {
+///         self.min.merge(&other.min);
+///         self.max.merge(&other.max);
+///     }
+/// }
 /// ```
 ///
 /// If you want to calculate the mean, variance and the median in one pass, you
@@ -88,8 +196,42 @@ macro_rules! assert_almost_eq {
 ///     [Variance, variance, mean, sample_variance],
 ///     [Quantile, quantile, quantile]);
 /// ```
+///
+/// The statistic `len` is treated specially: instead of a method returning
+/// `f64`, it generates `len(&self) -> u64` and `is_empty(&self) -> bool`,
+/// forwarding to the given field. Because all fields see the same samples, it
+/// is sufficient to specify it for one field:
+///
+/// ```
+/// use average::{Min, Max, Variance, Estimate, concatenate};
+///
+/// concatenate!(Stats, [Min, min, min], [Max, max, max], [Variance, variance, mean, len]);
+///
+/// let s: Stats = (1..6).map(f64::from).collect();
+///
+/// assert_eq!(s.len(), 5);
+/// assert!(!s.is_empty());
+/// assert_eq!(s.mean(), 3.0);
+/// ```
 #[macro_export]
 macro_rules! concatenate {
+    ( @method $field:ident, len ) => {
+        #[inline]
+        pub fn len(&self) -> u64 {
+            self.$field.len()
+        }
+
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+    };
+    ( @method $field:ident, $statistic:ident ) => {
+        #[inline]
+        pub fn $statistic(&self) -> f64 {
+            self.$field.$statistic()
+        }
+    };
     ( $visibility:vis $name:ident, $([$estimator:ident, $statistic:ident]),+ ) => {
         concatenate!($visibility $name, $([$estimator, $statistic, $statistic]),* );
     };
@@ -118,10 +260,7 @@ macro_rules! concatenate {
             }
 
             $( $(
-                #[inline]
-                pub fn $statistic(&self) -> f64 {
-                    self.$field.$statistic()
-                }
+                $crate::concatenate!(@method $field, $statistic);
             )* )*
         }
 
@@ -133,19 +272,20 @@ macro_rules! concatenate {
 
         $crate::impl_from_iterator!($name);
 
-        // This should be conditionally activated if all fields implement `Merge`.
-        // Could probably be implemented with specialization.
-        /*
-        impl $crate::Merge for $name {
+        // The higher-ranked bounds are never trivially true or false, so this
+        // compiles even if some fields do not implement `Merge`, in which case
+        // the implementation does not apply.
+        impl $crate::Merge for $name
+        where
+            $( for<'a> $estimator: $crate::Merge, )*
+        {
             #[inline]
             fn merge(&mut self, other: &Self) {
-                use $crate::Merge;
                 $(
-                    self.$field.merge(&other.$field);
+                    $crate::Merge::merge(&mut self.$field, &other.$field);
                 )*
             }
         }
-        */
     };
 }
 
@@ -276,3 +416,96 @@ macro_rules! impl_extend {
         }
     };
 }
+
+/// Implement `Sum<f64>` and `Sum<Self>` for an iterative estimator.
+///
+/// Summing estimators merges them, so the estimator has to implement `Merge`.
+#[macro_export]
+macro_rules! impl_sum {
+    ( $name:ident ) => {
+        impl ::core::iter::Sum<f64> for $name {
+            fn sum<T>(iter: T) -> $name
+            where
+                T: Iterator<Item = f64>,
+            {
+                let mut e = $name::new();
+                for i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
+
+        impl<'a> ::core::iter::Sum<&'a f64> for $name {
+            fn sum<T>(iter: T) -> $name
+            where
+                T: Iterator<Item = &'a f64>,
+            {
+                let mut e = $name::new();
+                for &i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
+
+        impl ::core::iter::Sum<$name> for $name {
+            fn sum<T>(iter: T) -> $name
+            where
+                T: Iterator<Item = $name>,
+            {
+                use $crate::Merge;
+
+                let mut e = $name::new();
+                for i in iter {
+                    e.merge(&i);
+                }
+                e
+            }
+        }
+
+        impl<'a> ::core::iter::Sum<&'a $name> for $name {
+            fn sum<T>(iter: T) -> $name
+            where
+                T: Iterator<Item = &'a $name>,
+            {
+                use $crate::Merge;
+
+                let mut e = $name::new();
+                for i in iter {
+                    e.merge(i);
+                }
+                e
+            }
+        }
+    };
+}
+
+/// Implement `AddAssign<&Self>` for an estimator by merging.
+///
+/// The estimator has to implement `Merge`.
+#[macro_export]
+macro_rules! impl_add_assign {
+    ( $name:ident ) => {
+        impl<'a> ::core::ops::AddAssign<&'a $name> for $name {
+            /// Merge another sample into this one.
+            #[inline]
+            fn add_assign(&mut self, other: &'a $name) {
+                $crate::Merge::merge(self, other);
+            }
+        }
+    };
+}
+
+/// Implement `Reset` for an estimator by replacing it with a new one.
+#[macro_export]
+macro_rules! impl_reset {
+    ( $name:ident ) => {
+        impl $crate::Reset for $name {
+            #[inline]
+            fn reset(&mut self) {
+                *self = $name::new();
+            }
+        }
+    };
+}
@@ -0,0 +1,96 @@
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+use super::{Estimate, Quantile};
+
+/// Estimate the median absolute deviation (MAD) of a sequence of numbers
+/// ("population").
+///
+/// The MAD is `median(|x_i - median(x)|)`, a robust estimator of the scale of
+/// a distribution. It is approximated in constant space by two
+/// [`Quantile`](./struct.Quantile.html) estimators: one for the median, and
+/// one for the absolute deviations of the samples from the median estimated
+/// at the time they are added.
+///
+/// For normally distributed samples, `1.4826 * mad()` is an estimator of the
+/// standard deviation.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::MedianAbsoluteDeviation;
+///
+/// let a: MedianAbsoluteDeviation = (1..=100).map(f64::from).collect();
+/// println!("The median absolute deviation is {}.", a.mad());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MedianAbsoluteDeviation {
+    /// Estimator of the median.
+    median: Quantile,
+    /// Estimator of the median of the absolute deviations.
+    deviation: Quantile,
+}
+
+impl MedianAbsoluteDeviation {
+    /// Create a new median absolute deviation estimator.
+    #[inline]
+    pub fn new() -> MedianAbsoluteDeviation {
+        MedianAbsoluteDeviation {
+            median: Quantile::new(0.5),
+            deviation: Quantile::new(0.5),
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.median.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.median.len()
+    }
+
+    /// Estimate the median of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn median(&self) -> f64 {
+        self.median.quantile()
+    }
+
+    /// Estimate the median absolute deviation of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn mad(&self) -> f64 {
+        self.deviation.quantile()
+    }
+}
+
+impl core::default::Default for MedianAbsoluteDeviation {
+    fn default() -> MedianAbsoluteDeviation {
+        MedianAbsoluteDeviation::new()
+    }
+}
+
+impl Estimate for MedianAbsoluteDeviation {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.median.add(x);
+        self.deviation.add((x - self.median.quantile()).abs());
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mad()
+    }
+}
+
+impl_from_iterator!(MedianAbsoluteDeviation);
+impl_extend!(MedianAbsoluteDeviation);
+impl_reset!(MedianAbsoluteDeviation);
@@ -52,12 +52,32 @@ impl Min {
     }
 }
 
+impl From<f64> for Min {
+    /// Create a new minimum estimator from a given value.
+    #[inline]
+    fn from(x: f64) -> Min {
+        Min::from_value(x)
+    }
+}
+
 impl core::default::Default for Min {
     fn default() -> Min {
         Min::new()
     }
 }
 
+impl PartialEq for Min {
+    /// Compare the minima of both estimators.
+    ///
+    /// The minimum does not depend on the order in which the samples were
+    /// added or merged. Following `f64`, an estimator with a NaN minimum is
+    /// not equal to any estimator.
+    #[inline]
+    fn eq(&self, other: &Min) -> bool {
+        self.x == other.x
+    }
+}
+
 impl_from_iterator!(Min);
 impl_from_par_iterator!(Min);
 impl_extend!(Min);
@@ -97,6 +117,9 @@ impl Merge for Min {
     }
 }
 
+impl_add_assign!(Min);
+impl_reset!(Min);
+
 /// Estimate the maximum of a sequence of numbers ("population").
 ///
 ///
@@ -136,12 +159,32 @@ impl Max {
     }
 }
 
+impl From<f64> for Max {
+    /// Create a new maximum estimator from a given value.
+    #[inline]
+    fn from(x: f64) -> Max {
+        Max::from_value(x)
+    }
+}
+
 impl core::default::Default for Max {
     fn default() -> Max {
         Max::new()
     }
 }
 
+impl PartialEq for Max {
+    /// Compare the maxima of both estimators.
+    ///
+    /// The maximum does not depend on the order in which the samples were
+    /// added or merged. Following `f64`, an estimator with a NaN maximum is
+    /// not equal to any estimator.
+    #[inline]
+    fn eq(&self, other: &Max) -> bool {
+        self.x == other.x
+    }
+}
+
 impl_from_iterator!(Max);
 impl_from_par_iterator!(Max);
 
@@ -179,3 +222,6 @@ impl Merge for Max {
         self.add(other.x);
     }
 }
+
+impl_add_assign!(Max);
+impl_reset!(Max);
@@ -0,0 +1,101 @@
+/// Estimate the geometric mean of a sequence of positive numbers
+/// ("population").
+///
+/// This is calculated as the exponential of the arithmetic mean of the
+/// logarithms of the samples, which is appropriate for data spanning several
+/// orders of magnitude.
+///
+/// The samples must be positive, it is up to the caller to handle zero or
+/// negative samples. Their logarithms are not finite, so the estimate becomes
+/// meaningless (usually NaN).
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::GeometricMean;
+///
+/// let a: GeometricMean = [1., 10., 100.].iter().collect();
+/// assert!((a.geometric_mean() - 10.).abs() < 1e-14);
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GeometricMean {
+    /// Estimator of the mean of the logarithms.
+    log_avg: Mean,
+}
+
+impl GeometricMean {
+    /// Create a new geometric mean estimator.
+    #[inline]
+    pub fn new() -> GeometricMean {
+        GeometricMean { log_avg: Mean::new() }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.log_avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.log_avg.len()
+    }
+
+    /// Estimate the geometric mean of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn geometric_mean(&self) -> f64 {
+        Float::exp(self.log_avg.mean())
+    }
+}
+
+impl core::default::Default for GeometricMean {
+    fn default() -> GeometricMean {
+        GeometricMean::new()
+    }
+}
+
+impl Estimate for GeometricMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.log_avg.add(Float::ln(x));
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.geometric_mean()
+    }
+}
+
+impl Merge for GeometricMean {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{GeometricMean, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let avg_total: GeometricMean = sequence.iter().collect();
+    /// let mut avg_left: GeometricMean = left.iter().collect();
+    /// let avg_right: GeometricMean = right.iter().collect();
+    /// avg_left.merge(&avg_right);
+    /// assert!((avg_total.geometric_mean() - avg_left.geometric_mean()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &GeometricMean) {
+        self.log_avg.merge(&other.log_avg);
+    }
+}
+
+impl_from_iterator!(GeometricMean);
+impl_from_par_iterator!(GeometricMean);
+impl_extend!(GeometricMean);
+impl_reset!(GeometricMean);
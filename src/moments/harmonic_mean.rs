@@ -0,0 +1,98 @@
+/// Estimate the harmonic mean of a sequence of numbers ("population").
+///
+/// This is the reciprocal of the arithmetic mean of the reciprocals of the
+/// samples, which is appropriate for averaging rates.
+///
+/// The samples should be nonzero, it is up to the caller to handle zero
+/// samples. Their reciprocals are infinite, so the estimate becomes
+/// meaningless (NaN or zero).
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::HarmonicMean;
+///
+/// let a: HarmonicMean = [1., 2., 4.].iter().collect();
+/// assert_eq!(a.harmonic_mean(), 12. / 7.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HarmonicMean {
+    /// Estimator of the mean of the reciprocals.
+    inv_avg: Mean,
+}
+
+impl HarmonicMean {
+    /// Create a new harmonic mean estimator.
+    #[inline]
+    pub fn new() -> HarmonicMean {
+        HarmonicMean { inv_avg: Mean::new() }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inv_avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.inv_avg.len()
+    }
+
+    /// Estimate the harmonic mean of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn harmonic_mean(&self) -> f64 {
+        1. / self.inv_avg.mean()
+    }
+}
+
+impl core::default::Default for HarmonicMean {
+    fn default() -> HarmonicMean {
+        HarmonicMean::new()
+    }
+}
+
+impl Estimate for HarmonicMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.inv_avg.add(1. / x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.harmonic_mean()
+    }
+}
+
+impl Merge for HarmonicMean {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{HarmonicMean, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let avg_total: HarmonicMean = sequence.iter().collect();
+    /// let mut avg_left: HarmonicMean = left.iter().collect();
+    /// let avg_right: HarmonicMean = right.iter().collect();
+    /// avg_left.merge(&avg_right);
+    /// assert!((avg_total.harmonic_mean() - avg_left.harmonic_mean()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &HarmonicMean) {
+        self.inv_avg.merge(&other.inv_avg);
+    }
+}
+
+impl_from_iterator!(HarmonicMean);
+impl_from_par_iterator!(HarmonicMean);
+impl_extend!(HarmonicMean);
+impl_reset!(HarmonicMean);
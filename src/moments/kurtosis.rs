@@ -1,9 +1,30 @@
+/// The mean, standard deviation, skewness and excess kurtosis of a sample.
+///
+/// See [`Kurtosis::to_standard_scores`].
+///
+/// [`Kurtosis::to_standard_scores`]: ./struct.Kurtosis.html#method.to_standard_scores
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StandardScores {
+    /// The mean, see [`Kurtosis::mean`](./struct.Kurtosis.html#method.mean).
+    pub mean: f64,
+    /// The sample standard deviation, i.e. the square root of
+    /// [`Kurtosis::sample_variance`](./struct.Kurtosis.html#method.sample_variance).
+    pub std_dev: f64,
+    /// The skewness, see [`Kurtosis::skewness`](./struct.Kurtosis.html#method.skewness).
+    pub skewness: f64,
+    /// The excess kurtosis, see [`Kurtosis::kurtosis`](./struct.Kurtosis.html#method.kurtosis).
+    pub excess_kurtosis: f64,
+    /// The sample size.
+    pub n: u64,
+}
+
 /// Estimate the arithmetic mean, the variance, the skewness and the kurtosis of
 /// a sequence of numbers ("population").
 ///
 /// This can be used to estimate the standard error of the mean.
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", derive(Serialize))]
 pub struct Kurtosis {
     /// Estimator of mean, variance and skewness.
     avg: Skewness,
@@ -21,6 +42,29 @@ impl Kurtosis {
         }
     }
 
+    /// Create a kurtosis estimator from a skewness estimator.
+    ///
+    /// The fourth moment of the observations already added to the skewness
+    /// estimator is unknown and set to zero. Until more observations are
+    /// added, `kurtosis()` returns 0, which is the convention for constant
+    /// samples. Afterwards, the missing fourth moment biases the kurtosis
+    /// downwards, so it is only accurate if the skewness estimator was empty.
+    #[inline]
+    pub fn from_skewness(s: Skewness) -> Kurtosis {
+        Kurtosis { avg: s, sum_4: 0. }
+    }
+
+    /// Create a kurtosis estimator from a variance estimator.
+    ///
+    /// The third and fourth moments of the observations already added to the
+    /// variance estimator are unknown and set to zero, see `from_skewness` and
+    /// `Skewness::from_variance`. Therefore, the skewness and the kurtosis are
+    /// only accurate if the variance estimator was empty.
+    #[inline]
+    pub fn from_variance(v: Variance) -> Kurtosis {
+        Kurtosis::from_skewness(Skewness::from_variance(v))
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -48,6 +92,16 @@ impl Kurtosis {
         self.avg.add_inner(delta, delta_n);
     }
 
+    /// Add all observations of a slice.
+    ///
+    /// The result is the same as calling `add` for each element.
+    #[inline]
+    pub fn add_slice(&mut self, data: &[f64]) {
+        for &x in data {
+            self.add(x);
+        }
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -68,6 +122,15 @@ impl Kurtosis {
         self.avg.len()
     }
 
+    /// Return the sample size as `f64`.
+    ///
+    /// Returns infinity if the sample size is larger than 2^53 and cannot be
+    /// represented exactly.
+    #[inline]
+    pub fn n_as_f64(&self) -> f64 {
+        len_as_f64(self.len())
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -118,6 +181,170 @@ impl Kurtosis {
         n * self.sum_4 / (self.avg.avg.sum_2 * self.avg.avg.sum_2) - 3.
     }
 
+    /// Estimate the excess kurtosis using the moment estimator
+    /// `g2 = m4/m2^2 - 3`.
+    ///
+    /// This is "type 1" of Joanes and Gill (1998) and the same as
+    /// `kurtosis()`. It is used by `scipy.stats.kurtosis` (by default) and
+    /// older textbooks. It is biased for small samples.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn kurtosis_g2(&self) -> f64 {
+        self.kurtosis()
+    }
+
+    /// Estimate the excess kurtosis using `G2 = (n - 1)/((n - 2)(n - 3)) *
+    /// ((n + 1) g2 + 6)`, where `g2` is the moment estimator.
+    ///
+    /// This is "type 2" of Joanes and Gill (1998), which is unbiased for
+    /// normally distributed populations. It is used by SAS, SPSS and Excel
+    /// (`KURT`).
+    ///
+    /// Returns NaN for samples of size 3 or less.
+    #[allow(non_snake_case)]
+    #[inline]
+    pub fn kurtosis_G2(&self) -> f64 {
+        if self.len() < 4 {
+            return f64::NAN;
+        }
+        let n = self.len().to_f64().unwrap();
+        (n - 1.) / ((n - 2.) * (n - 3.)) * ((n + 1.) * self.kurtosis_g2() + 6.)
+    }
+
+    /// Estimate the excess kurtosis using `b2 = m4/s^4 - 3 = (g2 + 3)(1 -
+    /// 1/n)^2 - 3`, where `s^2` is the sample variance and `g2` is the moment
+    /// estimator.
+    ///
+    /// This is "type 3" of Joanes and Gill (1998). It is used by MINITAB and
+    /// BMDP.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[inline]
+    pub fn kurtosis_b2(&self) -> f64 {
+        if self.len() < 2 {
+            return f64::NAN;
+        }
+        let n = self.len().to_f64().unwrap();
+        let factor = 1. - 1. / n;
+        (self.kurtosis_g2() + 3.) * factor * factor - 3.
+    }
+
+    /// Calculate the sample excess kurtosis.
+    ///
+    /// This uses the bias-corrected estimator `G2`, see `kurtosis_G2()`, while
+    /// `kurtosis()` is the biased moment estimator.
+    ///
+    /// Returns NaN for samples of size 3 or less.
+    #[inline]
+    pub fn sample_excess_kurtosis(&self) -> f64 {
+        self.kurtosis_G2()
+    }
+
+    /// Calculate the Jarque-Bera test statistic for normality,
+    /// `n/6 * (g1^2 + g2^2/4)`, where `g1` is the skewness and `g2` is the
+    /// excess kurtosis (both moment estimators).
+    ///
+    /// For a normally distributed population, the statistic is asymptotically
+    /// chi-squared distributed with two degrees of freedom.
+    ///
+    /// Returns NaN for samples of size 3 or less.
+    #[inline]
+    pub fn jarque_bera(&self) -> f64 {
+        if self.len() < 4 {
+            return f64::NAN;
+        }
+        let n = self.len().to_f64().unwrap();
+        let g1 = self.skewness();
+        let g2 = self.kurtosis();
+        n / 6. * (g1 * g1 + 0.25 * g2 * g2)
+    }
+
+    /// Calculate the p-value of the Jarque-Bera test for normality, i.e. the
+    /// probability of a statistic at least as large as `jarque_bera()` under
+    /// the chi-squared distribution with two degrees of freedom.
+    ///
+    /// The p-value is only asymptotically correct. It is inaccurate for small
+    /// samples, where it is too small and normality is rejected too often.
+    ///
+    /// Returns NaN for samples of size 3 or less.
+    #[inline]
+    pub fn jarque_bera_p_value(&self) -> f64 {
+        Float::exp(-0.5 * self.jarque_bera())
+    }
+
+    /// Calculate the mean, standard deviation, skewness and excess kurtosis
+    /// at once.
+    ///
+    /// This is more efficient than calling the corresponding methods
+    /// individually.
+    #[inline]
+    pub fn to_standard_scores(&self) -> StandardScores {
+        let n = self.len();
+        if n == 0 {
+            return StandardScores {
+                mean: f64::NAN,
+                std_dev: f64::NAN,
+                skewness: f64::NAN,
+                excess_kurtosis: f64::NAN,
+                n,
+            };
+        }
+        let n_f64 = n.to_f64().unwrap();
+        let sum_2 = self.avg.avg.sum_2;
+        let sum_3 = self.avg.sum_3;
+        let std_dev = if n < 2 {
+            f64::NAN
+        } else {
+            Float::sqrt(sum_2 / (n_f64 - 1.))
+        };
+        let skewness = if sum_3 == 0. {
+            0.
+        } else {
+            Float::sqrt(n_f64) * sum_3 / Float::sqrt(sum_2 * sum_2 * sum_2)
+        };
+        let excess_kurtosis = if self.sum_4 == 0. {
+            0.
+        } else {
+            n_f64 * self.sum_4 / (sum_2 * sum_2) - 3.
+        };
+        StandardScores {
+            mean: self.mean(),
+            std_dev,
+            skewness,
+            excess_kurtosis,
+            n,
+        }
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<'de> Deserialize<'de> for Kurtosis {
+    /// Deserialize the estimator, failing if its state is inconsistent.
+    fn deserialize<D>(deserializer: D) -> Result<Kurtosis, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Kurtosis")]
+        struct State {
+            avg: Skewness,
+            sum_4: f64,
+        }
+
+        let State { avg, sum_4 } = State::deserialize(deserializer)?;
+        let reason = if sum_4 < 0. {
+            Some("negative sum of fourth powers")
+        } else if avg.len() < 2 && sum_4 != 0. {
+            Some("nonzero sum of fourth powers for a sample of size less than 2")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => Err(serde::de::Error::custom(InvalidEstimatorState { reason })),
+            None => Ok(Kurtosis { avg, sum_4 }),
+        }
+    }
 }
 
 impl core::default::Default for Kurtosis {
@@ -166,6 +393,9 @@ impl Merge for Kurtosis {
     }
 }
 
+impl_add_assign!(Kurtosis);
+impl_reset!(Kurtosis);
+
 impl_from_iterator!(Kurtosis);
 impl_from_par_iterator!(Kurtosis);
 impl_extend!(Kurtosis);
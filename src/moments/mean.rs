@@ -25,6 +25,49 @@ impl Mean {
         Mean { avg: 0., n: 0 }
     }
 
+    /// Create a new mean estimator from a single sample.
+    #[inline]
+    pub fn new_with_sample(x: f64) -> Mean {
+        Mean { avg: x, n: 1 }
+    }
+
+    /// Add all observations of a slice.
+    ///
+    /// The result is the same as calling `add` for each element.
+    #[inline]
+    pub fn add_slice(&mut self, data: &[f64]) {
+        for &x in data {
+            self.add(x);
+        }
+    }
+
+    /// Create a mean estimator from a given mean and sample size.
+    #[inline]
+    pub(crate) fn from_parts(avg: f64, n: u64) -> Mean {
+        Mean { avg, n }
+    }
+
+    /// Calculate the difference `a - b` of two means.
+    ///
+    /// The sample size of the result is the smaller one of both samples, which
+    /// is a conservative choice for estimating errors. The difference is NaN
+    /// if one of the samples is empty.
+    #[inline]
+    pub fn difference(a: &Mean, b: &Mean) -> Mean {
+        Mean::from_parts(a.mean() - b.mean(), min(a.n, b.n))
+    }
+
+    /// Calculate the difference of this mean and another one.
+    ///
+    /// Returns `None` if one of the samples is empty. See `difference()`.
+    #[inline]
+    pub fn sub_mean(&self, other: &Mean) -> Option<Mean> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        Some(Mean::difference(self, other))
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -67,6 +110,49 @@ impl Mean {
         self.n
     }
 
+    /// Return the sample size as `f64`.
+    ///
+    /// Returns infinity if the sample size is larger than 2^53 and cannot be
+    /// represented exactly.
+    #[inline]
+    pub fn n_as_f64(&self) -> f64 {
+        len_as_f64(self.len())
+    }
+
+    /// Calculate the standard error of the mean, given the variance of the
+    /// population.
+    ///
+    /// This is useful if the variance of the population is known a priori,
+    /// because it does not have to be estimated from the sample.
+    ///
+    /// Returns NaN for an empty sample.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn standard_error_with_known_variance(&self, population_variance: f64) -> f64 {
+        if self.n == 0 {
+            return f64::NAN;
+        }
+        num_traits::Float::sqrt(population_variance / self.n.to_f64().unwrap())
+    }
+
+    /// Calculate the confidence interval `(mean - z*error, mean + z*error)`
+    /// of the mean, given the variance of the population and the critical
+    /// value `z` of the standard normal distribution (e.g. 1.96 for 95%).
+    ///
+    /// Returns NaN for an empty sample.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn confidence_interval_with_known_variance(
+        &self,
+        population_variance: f64,
+        z: f64,
+    ) -> (f64, f64) {
+        let error = z * self.standard_error_with_known_variance(population_variance);
+        let mean = self.mean();
+        (mean - error, mean + error)
+    }
 }
 
 impl core::default::Default for Mean {
@@ -75,6 +161,31 @@ impl core::default::Default for Mean {
     }
 }
 
+impl core::fmt::Display for Mean {
+    /// Format the sample size and the mean, e.g. `Mean(n=5, μ=3)`.
+    ///
+    /// The precision of the formatter is used for the mean.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "Mean(n={}, μ={:.*})", self.len(), precision, self.mean()),
+            None => write!(f, "Mean(n={}, μ={})", self.len(), self.mean()),
+        }
+    }
+}
+
+impl PartialEq for Mean {
+    /// Compare the sample sizes and the means of both estimators.
+    ///
+    /// The mean is subject to rounding errors, so estimators of the same
+    /// samples might compare unequal if the samples were added in a different
+    /// order or merged. Following `f64`, estimators with a NaN mean are not
+    /// equal.
+    #[inline]
+    fn eq(&self, other: &Mean) -> bool {
+        self.avg == other.avg && self.n == other.n
+    }
+}
+
 impl Estimate for Mean {
     #[inline]
     fn add(&mut self, sample: f64) {
@@ -131,6 +242,37 @@ impl Merge for Mean {
     }
 }
 
+impl_add_assign!(Mean);
+impl_reset!(Mean);
+
+impl From<f64> for Mean {
+    /// Create a mean estimator from a single sample.
+    #[inline]
+    fn from(x: f64) -> Mean {
+        Mean::new_with_sample(x)
+    }
+}
+
+impl From<Mean> for f64 {
+    /// Return the estimated mean.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    fn from(mean: Mean) -> f64 {
+        mean.mean()
+    }
+}
+
+impl core::ops::Neg for Mean {
+    type Output = Mean;
+
+    /// Negate the estimated mean, as if all samples had been negated.
+    #[inline]
+    fn neg(self) -> Mean {
+        Mean { avg: -self.avg, n: self.n }
+    }
+}
+
 impl_from_iterator!(Mean);
 impl_from_par_iterator!(Mean);
 impl_extend!(Mean);
@@ -1,19 +1,76 @@
+use core::cmp::min;
+
 use num_traits::ToPrimitive;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
-use super::{Estimate, Merge};
+use super::{Estimate, Max, Merge, Min};
 
 include!("mean.rs");
 include!("variance.rs");
+include!("population.rs");
+include!("harmonic_mean.rs");
 #[cfg(any(feature = "std", feature = "libm"))]
 include!("skewness.rs");
 #[cfg(any(feature = "std", feature = "libm"))]
 include!("kurtosis.rs");
+#[cfg(any(feature = "std", feature = "libm"))]
+include!("geometric_mean.rs");
 
 /// Alias for `Variance`.
 pub type MeanWithError = Variance;
 
+/// The sample size of an estimator overflowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    /// The name of the field that overflowed.
+    pub field: &'static str,
+    /// The value of the field of the estimator that was updated.
+    pub left: u64,
+    /// The value that was added to the field.
+    pub right: u64,
+}
+
+impl core::fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "overflow of `{}` when adding {} to {}",
+            self.field, self.right, self.left
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverflowError {}
+
+/// The internal state of a deserialized estimator is inconsistent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEstimatorState {
+    /// A description of the inconsistency.
+    pub reason: &'static str,
+}
+
+impl core::fmt::Display for InvalidEstimatorState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid estimator state: {}", self.reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidEstimatorState {}
+
+/// Convert a sample size to `f64`, returning infinity if it cannot be
+/// represented exactly.
+#[inline]
+fn len_as_f64(n: u64) -> f64 {
+    const MAX_EXACT: u64 = 1 << f64::MANTISSA_DIGITS;
+    if n > MAX_EXACT {
+        return f64::INFINITY;
+    }
+    n.to_f64().unwrap()
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! define_moments_common {
@@ -100,6 +157,48 @@ macro_rules! define_moments_common {
                 }
             }
 
+            /// Estimate the `p`th raw moment `E[X^p]` of the population.
+            ///
+            /// This is calculated from the central moments and the mean via
+            /// `E[X^p] = sum_k binomial(p, k) m_k mean^(p - k)`.
+            ///
+            /// If `p` > 0, returns NaN for an empty sample. Panics if `p` is
+            /// larger than `MAX_MOMENT`.
+            #[inline]
+            pub fn raw_moment(&self, p: usize) -> f64 {
+                let mean = self.mean();
+                let mut result = 0.;
+                for (k, binom) in IterBinomial::new(p as u64).enumerate() {
+                    result += binom.to_f64().unwrap() * self.central_moment(k) * pow(mean, p - k);
+                }
+                result
+            }
+
+            /// Estimate the cumulants `k_1, ..., k_MAX_MOMENT` of the
+            /// population.
+            ///
+            /// They are calculated from the central moments `m_r` with the
+            /// recurrence `k_r = m_r - sum_{j=1}^{r-1} binomial(r - 1, j - 1)
+            /// k_j m_{r-j}`, using `k_1 = 0` for the centered population and
+            /// replacing it by the mean afterwards. Thus `k_1` is the mean,
+            /// `k_2` the population variance and `k_3` the third central
+            /// moment.
+            ///
+            /// Returns NaN for an empty sample.
+            #[inline]
+            pub fn cumulants(&self) -> [f64; MAX_MOMENT] {
+                let mut kappa = [0.; MAX_MOMENT];
+                for r in 2..=MAX_MOMENT {
+                    let mut k_r = self.central_moment(r);
+                    for (j, binom) in (1..r).zip(IterBinomial::new((r - 1) as u64)) {
+                        k_r -= binom.to_f64().unwrap() * kappa[j - 1] * self.central_moment(r - j);
+                    }
+                    kappa[r - 1] = k_r;
+                }
+                kappa[0] = self.mean();
+                kappa
+            }
+
             /// Estimate the `p`th standardized moment of the population.
             #[cfg(any(feature = "std", feature = "libm"))]
             #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
@@ -152,22 +251,73 @@ macro_rules! define_moments_common {
                         / Float::powf(n * (self.central_moment(2) / (n - 1.)), 1.5);
                 }
                 // Adjusted Fisher-Pearson standardized moment coefficient
-                Float::sqrt(n * (n - 1.)) / (n * (n - 2.))
-                    * Float::powf(self.central_moment(3) / (self.central_moment(2) / n), 1.5)
+                Float::sqrt(n * (n - 1.)) / (n - 2.)
+                    * self.central_moment(3) / Float::powf(self.central_moment(2), 1.5)
             }
 
             /// Calculate the sample excess kurtosis.
+            ///
+            /// This uses the estimator `G2`, see `kurtosis_G2()`.
             /// 
             /// Returns NaN for samples of size 3 or less.
             #[inline]
             pub fn sample_excess_kurtosis(&self) -> f64 {
+                self.kurtosis_G2()
+            }
+
+            /// Estimate the excess kurtosis using the moment estimator
+            /// `g2 = m4/m2^2 - 3`.
+            ///
+            /// This is "type 1" of Joanes and Gill (1998). It is used by
+            /// `scipy.stats.kurtosis` (by default) and older textbooks. It is
+            /// biased for small samples.
+            ///
+            /// Returns NaN for an empty sample.
+            #[inline]
+            pub fn kurtosis_g2(&self) -> f64 {
+                if self.n == 0 {
+                    return f64::NAN;
+                }
+                let m4 = self.central_moment(4);
+                if m4 == 0. {
+                    return 0.;
+                }
+                m4 / pow(self.central_moment(2), 2) - 3.
+            }
+
+            /// Estimate the excess kurtosis using `G2 = (n - 1)/((n - 2)(n - 3))
+            /// * ((n + 1) g2 + 6)`, where `g2` is the moment estimator.
+            ///
+            /// This is "type 2" of Joanes and Gill (1998), which is unbiased for
+            /// normally distributed populations. It is used by SAS, SPSS and
+            /// Excel (`KURT`).
+            ///
+            /// Returns NaN for samples of size 3 or less.
+            #[allow(non_snake_case)]
+            #[inline]
+            pub fn kurtosis_G2(&self) -> f64 {
                 if self.n < 4 {
                     return f64::NAN;
                 }
                 let n = self.n.to_f64().unwrap();
-                (n + 1.) * n * self.central_moment(4)
-                    / ((n - 1.) * (n - 2.) * (n - 3.) * pow(self.central_moment(2), 2))
-                    - 3. * pow(n - 1., 2) / ((n - 2.) * (n - 3.))
+                (n - 1.) / ((n - 2.) * (n - 3.)) * ((n + 1.) * self.kurtosis_g2() + 6.)
+            }
+
+            /// Estimate the excess kurtosis using `b2 = m4/s^4 - 3 = (g2 + 3)(1 -
+            /// 1/n)^2 - 3`, where `s^2` is the sample variance and `g2` is the
+            /// moment estimator.
+            ///
+            /// This is "type 3" of Joanes and Gill (1998). It is used by MINITAB
+            /// and BMDP.
+            ///
+            /// Returns NaN for samples of size 1 or less.
+            #[inline]
+            pub fn kurtosis_b2(&self) -> f64 {
+                if self.n < 2 {
+                    return f64::NAN;
+                }
+                let n = self.n.to_f64().unwrap();
+                (self.kurtosis_g2() + 3.) * pow(1. - 1. / n, 2) - 3.
             }
 
             /// Add an observation sampled from the population.
@@ -204,6 +354,16 @@ macro_rules! define_moments_common {
                     }
                 }
             }
+
+            /// Add all observations of a slice.
+            ///
+            /// The result is the same as calling `add` for each element.
+            #[inline]
+            pub fn add_slice(&mut self, data: &[f64]) {
+                for &x in data {
+                    self.add(x);
+                }
+            }
         }
 
         impl $crate::Merge for $name {
@@ -263,6 +423,8 @@ macro_rules! define_moments_common {
         $crate::impl_from_iterator!($name);
         $crate::impl_from_par_iterator!($name);
         $crate::impl_extend!($name);
+        $crate::impl_reset!($name);
+        $crate::impl_sum!($name);
     };
 }
 
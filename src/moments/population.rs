@@ -0,0 +1,187 @@
+/// Estimate the sum, mean, variance, minimum and maximum of a sequence of
+/// numbers ("population").
+///
+/// This is useful for reporting summary statistics.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Population;
+///
+/// let a: Population = (1..6).map(f64::from).collect();
+/// assert_eq!(a.sum(), 15.);
+/// assert_eq!(a.mean(), 3.);
+/// assert_eq!(a.sample_variance(), 2.5);
+/// assert_eq!(a.min(), 1.);
+/// assert_eq!(a.max(), 5.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Population {
+    /// Estimator of the mean and variance.
+    var: Variance,
+    /// Naive sum of the samples.
+    sum: f64,
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the maximum.
+    max: Max,
+}
+
+impl Population {
+    /// Create a new population estimator.
+    #[inline]
+    pub fn new() -> Population {
+        Population {
+            var: Variance::new(),
+            sum: 0.,
+            min: Min::new(),
+            max: Max::new(),
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.var.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.var.len()
+    }
+
+    /// Return the sum of the sample.
+    ///
+    /// This is accumulated naively and is not used for calculating the mean.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.var.mean()
+    }
+
+    /// Calculate the sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        self.var.sample_variance()
+    }
+
+    /// Calculate the population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        self.var.population_variance()
+    }
+
+    /// Calculate the sample standard deviation.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn standard_deviation(&self) -> f64 {
+        num_traits::Float::sqrt(self.sample_variance())
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn standard_error(&self) -> f64 {
+        self.var.error()
+    }
+
+    /// Return the minimum of the sample.
+    ///
+    /// Returns `f64::INFINITY` for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Return the maximum of the sample.
+    ///
+    /// Returns `f64::NEG_INFINITY` for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+
+    /// Return the estimator of the mean and variance.
+    #[inline]
+    pub fn variance(&self) -> &Variance {
+        &self.var
+    }
+}
+
+impl core::default::Default for Population {
+    fn default() -> Population {
+        Population::new()
+    }
+}
+
+impl Estimate for Population {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.var.add(x);
+        self.sum += x;
+        self.min.add(x);
+        self.max.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for Population {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Merge, Population};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let total: Population = sequence.iter().collect();
+    /// let mut merged: Population = left.iter().collect();
+    /// merged.merge(&right.iter().collect());
+    /// assert_eq!(total.sum(), merged.sum());
+    /// assert_eq!(total.max(), merged.max());
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Population) {
+        self.var.merge(&other.var);
+        self.sum += other.sum;
+        self.min.merge(&other.min);
+        self.max.merge(&other.max);
+    }
+}
+
+impl_from_iterator!(Population);
+impl_from_par_iterator!(Population);
+impl_extend!(Population);
+impl_reset!(Population);
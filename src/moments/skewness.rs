@@ -6,7 +6,7 @@ use num_traits::Float;
 /// This can be used to estimate the standard error of the mean.
 #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", derive(Serialize))]
 pub struct Skewness {
     /// Estimator of mean and variance.
     avg: MeanWithError,
@@ -24,6 +24,17 @@ impl Skewness {
         }
     }
 
+    /// Create a skewness estimator from a variance estimator.
+    ///
+    /// The third moment of the observations already added to the variance
+    /// estimator is unknown and set to zero, as for a symmetric sample.
+    /// Therefore, the skewness is only accurate if those observations were
+    /// symmetric around their mean.
+    #[inline]
+    pub fn from_variance(v: Variance) -> Skewness {
+        Skewness { avg: v, sum_3: 0. }
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -49,6 +60,16 @@ impl Skewness {
         self.avg.add_inner(delta_n);
     }
 
+    /// Add all observations of a slice.
+    ///
+    /// The result is the same as calling `add` for each element.
+    #[inline]
+    pub fn add_slice(&mut self, data: &[f64]) {
+        for &x in data {
+            self.add(x);
+        }
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -69,6 +90,15 @@ impl Skewness {
         self.avg.len()
     }
 
+    /// Return the sample size as `f64`.
+    ///
+    /// Returns infinity if the sample size is larger than 2^53 and cannot be
+    /// represented exactly.
+    #[inline]
+    pub fn n_as_f64(&self) -> f64 {
+        len_as_f64(self.len())
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -96,6 +126,9 @@ impl Skewness {
     }
 
     /// Estimate the skewness of the population.
+    ///
+    /// This is the biased (population) skewness `g1` of the sample. See
+    /// `sample_skewness()` for an estimator with finite-sample correction.
     /// 
     /// Returns NaN for an empty sample.
     #[inline]
@@ -111,6 +144,45 @@ impl Skewness {
         debug_assert_ne!(sum_2, 0.);
         Float::sqrt(n) * self.sum_3 / Float::sqrt(sum_2*sum_2*sum_2)
     }
+
+    /// Calculate the sample skewness.
+    ///
+    /// This uses the adjusted Fisher-Pearson standardized moment coefficient
+    /// `G1 = sqrt(n*(n - 1)) / (n - 2) * g1`, where `g1` is the biased
+    /// skewness returned by `skewness()`.
+    ///
+    /// Returns NaN for samples of size 2 or less.
+    #[inline]
+    pub fn sample_skewness(&self) -> f64 {
+        if self.len() < 3 {
+            return f64::NAN;
+        }
+        let n = self.len().to_f64().unwrap();
+        Float::sqrt(n * (n - 1.)) / (n - 2.) * self.skewness()
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<'de> Deserialize<'de> for Skewness {
+    /// Deserialize the estimator, failing if its state is inconsistent.
+    fn deserialize<D>(deserializer: D) -> Result<Skewness, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Skewness")]
+        struct State {
+            avg: MeanWithError,
+            sum_3: f64,
+        }
+
+        let State { avg, sum_3 } = State::deserialize(deserializer)?;
+        if avg.len() < 3 && sum_3 != 0. {
+            let reason = "nonzero sum of cubes for a sample of size less than 3";
+            return Err(serde::de::Error::custom(InvalidEstimatorState { reason }));
+        }
+        Ok(Skewness { avg, sum_3 })
+    }
 }
 
 impl Default for Skewness {
@@ -156,6 +228,9 @@ impl Merge for Skewness {
     }
 }
 
+impl_add_assign!(Skewness);
+impl_reset!(Skewness);
+
 impl_from_iterator!(Skewness);
 impl_from_par_iterator!(Skewness);
 impl_extend!(Skewness);
@@ -13,7 +13,7 @@
 /// println!("The mean is {} ± {}.", a.mean(), a.error());
 /// ```
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", derive(Serialize))]
 pub struct Variance {
     /// Estimator of average.
     avg: Mean,
@@ -28,6 +28,115 @@ impl Variance {
         Variance { avg: Mean::new(), sum_2: 0. }
     }
 
+    /// Calculate the variance of the given sample with the (corrected)
+    /// two-pass algorithm.
+    ///
+    /// This first calculates the mean and then the sum of squared deviations
+    /// from it, which is more accurate than adding the observations one by one
+    /// for ill-conditioned samples. The result can be used like any other
+    /// estimator, for example more observations can be added.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let a = Variance::from_two_pass(&[1., 2., 3., 4., 5.]);
+    /// assert_eq!(a.mean(), 3.);
+    /// assert_eq!(a.sample_variance(), 2.5);
+    /// ```
+    pub fn from_two_pass(data: &[f64]) -> Variance {
+        if data.is_empty() {
+            return Variance::new();
+        }
+        let n = data.len().to_f64().unwrap();
+        let mean = data.iter().sum::<f64>() / n;
+        let mut sum = 0.;
+        let mut sum_2 = 0.;
+        for &x in data {
+            let delta = x - mean;
+            sum += delta;
+            sum_2 += delta * delta;
+        }
+        // The sum of the deviations vanishes in exact arithmetic. Subtracting
+        // it compensates for the rounding error of the mean, see Chan, Golub
+        // and LeVeque (1983).
+        sum_2 -= sum * sum / n;
+        Variance {
+            avg: Mean::from_parts(mean, data.len().to_u64().unwrap()),
+            sum_2: sum_2.max(0.),
+        }
+    }
+
+    /// Center the given sample in place by subtracting its mean from each
+    /// element, and calculate the variance of the centered sample.
+    ///
+    /// The mean of the result vanishes up to rounding errors. This is a common
+    /// preprocessing step, for instance for calculating higher moments or for
+    /// regressions.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let mut data = [1., 2., 3., 4., 5.];
+    /// let a = Variance::center_and_compute(&mut data);
+    /// assert_eq!(data, [-2., -1., 0., 1., 2.]);
+    /// assert_eq!(a.mean(), 0.);
+    /// assert_eq!(a.sample_variance(), 2.5);
+    /// ```
+    pub fn center_and_compute(data: &mut [f64]) -> Variance {
+        let mean = data.iter().collect::<Variance>().mean();
+        for x in data.iter_mut() {
+            *x -= mean;
+        }
+        data.iter().collect()
+    }
+
+    /// Create a variance estimator from pre-aggregated data, given the sum of
+    /// the observations, the sum of their squared deviations from their mean
+    /// and their number.
+    ///
+    /// Returns an empty estimator if `n` is zero. Since the sum of squared
+    /// deviations cannot be negative, and is zero for a single observation,
+    /// `sum_sq_dev` is replaced with zero if it is negative or `n` is one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// // The sample [1, 2, 3, 4, 5] has the mean 3.
+    /// let a = Variance::from_aggregated(15., 10., 5);
+    /// assert_eq!(a.mean(), 3.);
+    /// assert_eq!(a.sample_variance(), 2.5);
+    /// ```
+    #[inline]
+    pub fn from_aggregated(sum: f64, sum_sq_dev: f64, n: u64) -> Variance {
+        if n == 0 {
+            return Variance::new();
+        }
+        let sum_2 = if n < 2 || sum_sq_dev < 0. { 0. } else { sum_sq_dev };
+        Variance {
+            avg: Mean::from_parts(sum / n.to_f64().unwrap(), n),
+            sum_2,
+        }
+    }
+
+    /// Add pre-aggregated data, given the sum of the observations, the sum of
+    /// their squared deviations from their mean and their number.
+    ///
+    /// This is equivalent to merging `Variance::from_aggregated(sum,
+    /// sum_sq_dev, n)`.
+    #[inline]
+    pub fn add_aggregated(&mut self, sum: f64, sum_sq_dev: f64, n: u64) {
+        self.merge(&Variance::from_aggregated(sum, sum_sq_dev, n));
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -52,6 +161,44 @@ impl Variance {
         self.sum_2 += delta_n * delta_n * n * (n - 1.);
     }
 
+    /// Replace the observation `x_old` by `x_new`, keeping the sample size.
+    ///
+    /// This is useful for a sliding window of fixed size, where the oldest
+    /// observation is removed for each new one. It is equivalent to removing
+    /// `x_old` and adding `x_new`, but updates the mean and the sum of squares
+    /// in one step, without going through an estimate of a smaller sample.
+    ///
+    /// `x_old` is assumed to be part of the sample, otherwise the results are
+    /// meaningless.
+    ///
+    /// Panics if the sample is empty.
+    #[inline]
+    pub fn update_with_removed(&mut self, x_old: f64, x_new: f64) {
+        assert!(!self.is_empty(), "Cannot remove a sample from an empty estimator");
+        // This combines Welford's update with the removal identity given by
+        // West in 1979.
+        let n = self.avg.len().to_f64().unwrap();
+        let mean_old = self.avg.mean();
+        self.avg.add_inner((x_new - x_old) / n);
+        let mean_new = self.avg.mean();
+        self.sum_2 += (x_new - x_old) * ((x_new - mean_new) + (x_old - mean_old));
+        if n < 2. || self.sum_2 < 0. {
+            // Avoid negative variances due to rounding errors, and a nonzero
+            // sum of squares for a single observation.
+            self.sum_2 = 0.;
+        }
+    }
+
+    /// Add all observations of a slice.
+    ///
+    /// The result is the same as calling `add` for each element.
+    #[inline]
+    pub fn add_slice(&mut self, data: &[f64]) {
+        for &x in data {
+            self.add(x);
+        }
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -72,6 +219,15 @@ impl Variance {
         self.avg.len()
     }
 
+    /// Return the sample size as `f64`.
+    ///
+    /// Returns infinity if the sample size is larger than 2^53 and cannot be
+    /// represented exactly.
+    #[inline]
+    pub fn n_as_f64(&self) -> f64 {
+        len_as_f64(self.len())
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -99,9 +255,48 @@ impl Variance {
         self.sum_2 / n.to_f64().unwrap()
     }
 
+    /// Calculate the sample standard deviation, i.e. the square root of the
+    /// sample variance.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn sample_standard_deviation(&self) -> f64 {
+        num_traits::Float::sqrt(self.sample_variance())
+    }
+
+    /// Calculate the population standard deviation of the sample, i.e. the
+    /// square root of the population variance.
+    ///
+    /// Returns NaN for an empty sample.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn population_standard_deviation(&self) -> f64 {
+        num_traits::Float::sqrt(self.population_variance())
+    }
+
+    /// Calculate the coefficient of variation, i.e. the sample standard
+    /// deviation divided by the mean.
+    ///
+    /// Returns NaN for samples of size 1 or less and if the mean is zero.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> f64 {
+        let mean = self.mean();
+        if mean == 0. {
+            return f64::NAN;
+        }
+        self.sample_standard_deviation() / mean
+    }
+
     /// Estimate the variance of the mean of the population.
     /// 
-    /// Returns NaN for an empty sample.
+    /// Returns NaN for an empty sample and 0 for a sample of size 1. See
+    /// `variance_of_mean_unbiased()` for a variant that does not assume the
+    /// variance to vanish for a single sample.
     #[inline]
     pub fn variance_of_mean(&self) -> f64 {
         let n = self.avg.len();
@@ -114,6 +309,21 @@ impl Variance {
         self.sample_variance() / n.to_f64().unwrap()
     }
 
+    /// Estimate the variance of the mean of the population.
+    ///
+    /// This is an unbiased estimator, which cannot be calculated from a
+    /// single sample.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[inline]
+    pub fn variance_of_mean_unbiased(&self) -> f64 {
+        let n = self.avg.len();
+        if n < 2 {
+            return f64::NAN;
+        }
+        self.sample_variance() / n.to_f64().unwrap()
+    }
+
     /// Estimate the standard error of the mean of the population.
     /// 
     /// Returns NaN for an empty sample.
@@ -124,6 +334,178 @@ impl Variance {
         num_traits::Float::sqrt(self.variance_of_mean())
     }
 
+    /// Estimate the standard error of the mean using the delete-one jackknife.
+    ///
+    /// The jackknife estimate is `sqrt((n - 1)/n * sum_i (mean_i - mean)^2)`,
+    /// where `mean_i` is the mean without observation `i`. Because
+    /// `mean_i - mean = (mean - x_i)/(n - 1)`, this simplifies to
+    /// `sqrt(sample_variance / n)`, which is exactly `error()` for samples of
+    /// size 2 or more. The jackknife only differs for nonlinear statistics.
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn jackknife_std_error(&self) -> f64 {
+        num_traits::Float::sqrt(self.variance_of_mean_unbiased())
+    }
+
+    /// Estimate the standard error of the mean as obtained by bootstrapping,
+    /// in the limit of infinitely many resamples.
+    ///
+    /// Resampling with replacement from the empirical distribution gives a
+    /// variance of the mean of `population_variance / n`, so this is
+    /// `sqrt((n - 1)/n) * error()`. The small bias compared to `error()` is
+    /// the well-known downward bias of the bootstrap for small samples.
+    ///
+    /// Returns NaN for an empty sample.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn bootstrap_std_error_approx(&self) -> f64 {
+        let n = self.len().to_f64().unwrap();
+        num_traits::Float::sqrt(self.population_variance() / n)
+    }
+
+    /// Estimate the difference `a - b` of the means of two independent
+    /// populations.
+    ///
+    /// The variance of the mean of the result is the sum of the variances of
+    /// the means, `var(a)/n_a + var(b)/n_b`, so `error()` returns the standard
+    /// error used by Welch's t-test. The sample size of the result is the
+    /// smaller one of both samples.
+    ///
+    /// Returns an empty estimator if one of the samples has a size of 1 or
+    /// less.
+    #[inline]
+    pub fn combined_variance(a: &Variance, b: &Variance) -> Variance {
+        let n = min(a.len(), b.len());
+        if n < 2 {
+            return Variance::new();
+        }
+        let variance_of_mean = a.variance_of_mean() + b.variance_of_mean();
+        let n_f64 = n.to_f64().unwrap();
+        Variance {
+            avg: Mean::difference(&a.avg, &b.avg),
+            sum_2: variance_of_mean * n_f64 * (n_f64 - 1.),
+        }
+    }
+
+    /// Calculate the two-sided confidence interval of the mean for the given
+    /// confidence level (e.g. 0.95), using the critical value of Student's
+    /// t-distribution.
+    ///
+    /// This is appropriate for small samples of a normally distributed
+    /// population. See [`t_critical`].
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    ///
+    /// [`t_critical`]: ./fn.t_critical.html
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn confidence_interval_t(&self, confidence: f64) -> (f64, f64) {
+        let n = self.len();
+        if n < 2 {
+            return (f64::NAN, f64::NAN);
+        }
+        let error = t_critical(n - 1, confidence) * self.error();
+        let mean = self.mean();
+        (mean - error, mean + error)
+    }
+
+    /// Calculate the one-sample t-statistic `(mean - mu0) / error` for the
+    /// hypothesis that the mean of the population is `mu0`.
+    ///
+    /// Under the hypothesis, this follows Student's t-distribution with
+    /// `n - 1` degrees of freedom for a normally distributed population. The
+    /// critical values can be calculated with [`t_critical`].
+    ///
+    /// Returns NaN for samples of size 1 or less.
+    ///
+    /// [`t_critical`]: ./fn.t_critical.html
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn t_statistic(&self, mu0: f64) -> f64 {
+        if self.len() < 2 {
+            return f64::NAN;
+        }
+        (self.mean() - mu0) / self.error()
+    }
+
+    /// Calculate the t-statistic of Welch's t-test for the hypothesis that
+    /// the means of both populations are equal.
+    ///
+    /// This is `(mean_a - mean_b) / sqrt(var_a/n_a + var_b/n_b)`, which does
+    /// not assume equal variances. Under the hypothesis, it approximately
+    /// follows Student's t-distribution with
+    /// `(var_a/n_a + var_b/n_b)^2 / ((var_a/n_a)^2/(n_a - 1) + (var_b/n_b)^2/(n_b - 1))`
+    /// degrees of freedom (Welch–Satterthwaite equation), which can be rounded
+    /// down for [`t_critical`].
+    ///
+    /// Returns NaN if one of the samples has a size of 1 or less.
+    ///
+    /// [`t_critical`]: ./fn.t_critical.html
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn t_statistic_two_sample(&self, other: &Variance) -> f64 {
+        if self.len() < 2 || other.len() < 2 {
+            return f64::NAN;
+        }
+        let variance_of_difference =
+            self.variance_of_mean_unbiased() + other.variance_of_mean_unbiased();
+        (self.mean() - other.mean()) / num_traits::Float::sqrt(variance_of_difference)
+    }
+
+    /// Merge another sample into this one, failing if the sample size
+    /// overflows.
+    ///
+    /// In this case, the estimator is not modified.
+    #[inline]
+    pub fn try_merge(&mut self, other: &Variance) -> Result<(), OverflowError> {
+        if self.len().checked_add(other.len()).is_none() {
+            return Err(OverflowError {
+                field: "n",
+                left: self.len(),
+                right: other.len(),
+            });
+        }
+        self.merge(other);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<'de> Deserialize<'de> for Variance {
+    /// Deserialize the estimator, failing if its state is inconsistent.
+    fn deserialize<D>(deserializer: D) -> Result<Variance, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Variance")]
+        struct State {
+            avg: Mean,
+            sum_2: f64,
+        }
+
+        let State { avg, sum_2 } = State::deserialize(deserializer)?;
+        let reason = if sum_2 < 0. {
+            Some("negative sum of squares")
+        } else if avg.len() < 2 && sum_2 != 0. {
+            Some("nonzero sum of squares for a sample of size less than 2")
+        } else if !avg.is_empty() && avg.mean().is_nan() {
+            Some("mean is NaN for a nonempty sample")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => Err(serde::de::Error::custom(InvalidEstimatorState { reason })),
+            None => Ok(Variance { avg, sum_2 }),
+        }
+    }
 }
 
 impl core::default::Default for Variance {
@@ -132,6 +514,34 @@ impl core::default::Default for Variance {
     }
 }
 
+impl core::fmt::Display for Variance {
+    /// Format the sample size, the mean and the sample variance, e.g.
+    /// `Variance(n=5, μ=3, s²=2.5)`.
+    ///
+    /// The precision of the formatter is used for the mean and the variance.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (n, mean, variance) = (self.len(), self.mean(), self.sample_variance());
+        match f.precision() {
+            Some(p) => write!(f, "Variance(n={}, μ={:.*}, s²={:.*})", n, p, mean, p, variance),
+            None => write!(f, "Variance(n={}, μ={}, s²={})", n, mean, variance),
+        }
+    }
+}
+
+impl PartialEq for Variance {
+    /// Compare the sample sizes, the means and the sums of squared deviations
+    /// of both estimators.
+    ///
+    /// Both sums are subject to rounding errors, so estimators of the same
+    /// samples might compare unequal if the samples were added in a different
+    /// order or merged. Following `f64`, estimators containing NaN are not
+    /// equal.
+    #[inline]
+    fn eq(&self, other: &Variance) -> bool {
+        self.avg == other.avg && self.sum_2 == other.sum_2
+    }
+}
+
 impl Estimate for Variance {
     #[inline]
     fn add(&mut self, sample: f64) {
@@ -186,6 +596,245 @@ impl Merge for Variance {
     }
 }
 
+impl_add_assign!(Variance);
+impl_reset!(Variance);
+
+impl core::ops::Neg for Variance {
+    type Output = Variance;
+
+    /// Negate the estimated mean, as if all samples had been negated.
+    ///
+    /// The variance is not affected.
+    #[inline]
+    fn neg(self) -> Variance {
+        Variance { avg: -self.avg, sum_2: self.sum_2 }
+    }
+}
+
 impl_from_iterator!(Variance);
 impl_from_par_iterator!(Variance);
 impl_extend!(Variance);
+
+/// Calculate the critical value of Student's t-distribution with `df` degrees
+/// of freedom for a two-sided confidence interval with the given confidence
+/// level (e.g. 0.95).
+///
+/// The quantile is approximated by Hill's algorithm (1970), which is refined
+/// by a Newton step using the exact distribution function for `df <= 120`.
+/// The relative error is less than 1e-6.
+///
+/// Returns NaN if `df` is zero or if `confidence` is not between 0 and 1.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::t_critical;
+///
+/// assert!((t_critical(9, 0.95) - 2.262157).abs() < 1e-6);
+/// ```
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+pub fn t_critical(df: u64, confidence: f64) -> f64 {
+    use core::f64::consts::PI;
+    use num_traits::Float;
+
+    if df == 0 || !(0. ..1.).contains(&confidence) {
+        return f64::NAN;
+    }
+    if confidence == 0. {
+        return 0.;
+    }
+    // Two-sided tail probability.
+    let p = 1. - confidence;
+    if df == 1 {
+        return Float::tan((1. - p) * PI / 2.);
+    }
+    if df == 2 {
+        return Float::sqrt(2. / (p * (2. - p)) - 2.);
+    }
+
+    // G. W. Hill, Algorithm 396: Student's t-quantiles, Communications of the
+    // ACM 13 (1970), 619-620.
+    let n = df.to_f64().unwrap();
+    let a = 1. / (n - 0.5);
+    let b = 48. / (a * a);
+    let mut c = ((20700. * a / b - 98.) * a - 16.) * a + 96.36;
+    let d = ((94.5 / (b + c) - 3.) / b + 1.) * Float::sqrt(a * PI / 2.) * n;
+    let mut x = d * p;
+    let mut y = Float::powf(x, 2. / n);
+    if y > 0.05 + a {
+        // Asymptotic inverse expansion about the normal distribution.
+        x = normal_quantile(1. - 0.5 * p);
+        y = x * x;
+        if df < 5 {
+            c += 0.3 * (n - 4.5) * (x + 0.6);
+        }
+        c += (((0.05 * d * x - 5.) * x - 7.) * x - 2.) * x + b;
+        y = (((((0.4 * y + 6.3) * y + 36.) * y + 94.5) / c - y - 3.) / b + 1.) * x;
+        y = a * y * y;
+        y = if y > 0.002 { Float::exp_m1(y) } else { 0.5 * y * y + y };
+    } else {
+        y = ((1. / (((n + 6.) / (n * y) - 0.089 * d - 0.822) * (n + 2.) * 3.) + 0.5 / (n + 4.))
+            * y
+            - 1.)
+            * (n + 1.)
+            / (n + 2.)
+            + 1. / y;
+    }
+    let mut t = Float::sqrt(n * y);
+
+    if df <= 120 {
+        t -= (student_t_interval(t, df) - confidence) / (2. * student_t_density(t, df));
+    }
+    t
+}
+
+/// Calculate Cohen's d, the difference of the means of two samples in units
+/// of their pooled standard deviation.
+///
+/// This is `(mean_a - mean_b) / s`, where the pooled standard deviation is
+/// `s = sqrt(((n_a - 1) var_a + (n_b - 1) var_b) / (n_a + n_b - 2))`.
+///
+/// Returns NaN if one of the samples has a size of 1 or less.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{cohen_d, Variance};
+///
+/// let a: Variance = [4., 5., 6.].iter().collect();
+/// let b: Variance = [3., 4., 5.].iter().collect();
+/// assert_eq!(cohen_d(&a, &b), 1.);
+/// ```
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+#[inline]
+pub fn cohen_d(a: &Variance, b: &Variance) -> f64 {
+    if a.len() < 2 || b.len() < 2 {
+        return f64::NAN;
+    }
+    let df = (a.len() + b.len() - 2).to_f64().unwrap();
+    let pooled_variance = (a.sum_2 + b.sum_2) / df;
+    (a.mean() - b.mean()) / num_traits::Float::sqrt(pooled_variance)
+}
+
+/// Calculate the two-sided confidence interval of Cohen's d for the given
+/// confidence level (e.g. 0.95).
+///
+/// This uses the normal approximation of the distribution of d (Hedges and
+/// Olkin, 1985) with the standard error
+/// `sqrt((n_a + n_b) / (n_a n_b) + d^2 / (2 (n_a + n_b)))`, which is
+/// appropriate for normally distributed populations with equal variances.
+/// See [`cohen_d`].
+///
+/// Returns NaN if one of the samples has a size of 1 or less, or if
+/// `confidence` is not between 0 and 1.
+///
+/// [`cohen_d`]: ./fn.cohen_d.html
+#[cfg(any(feature = "std", feature = "libm"))]
+#[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+#[inline]
+pub fn cohen_d_confidence_interval(a: &Variance, b: &Variance, confidence: f64) -> (f64, f64) {
+    let d = cohen_d(a, b);
+    if d.is_nan() || !(0. ..1.).contains(&confidence) {
+        return (f64::NAN, f64::NAN);
+    }
+    let n_a = a.len().to_f64().unwrap();
+    let n_b = b.len().to_f64().unwrap();
+    let n = n_a + n_b;
+    let error = num_traits::Float::sqrt(n / (n_a * n_b) + d * d / (2. * n));
+    let z = normal_quantile(1. - 0.5 * (1. - confidence));
+    (d - z * error, d + z * error)
+}
+
+/// Calculate the probability `P(|T| < t)` for Student's t-distribution with an
+/// integer number of degrees of freedom.
+///
+/// See Abramowitz and Stegun, 26.7.3 and 26.7.4.
+#[cfg(any(feature = "std", feature = "libm"))]
+fn student_t_interval(t: f64, df: u64) -> f64 {
+    use core::f64::consts::FRAC_2_PI;
+    use num_traits::Float;
+
+    let theta = Float::atan(t / Float::sqrt(df.to_f64().unwrap()));
+    let cos = Float::cos(theta);
+    let cos_2 = cos * cos;
+    let (mut term, mut k) = if df % 2 == 1 { (cos, 3) } else { (1., 2) };
+    let mut sum = term;
+    while k + 1 < df {
+        term *= cos_2 * (k - 1).to_f64().unwrap() / k.to_f64().unwrap();
+        sum += term;
+        k += 2;
+    }
+    if df % 2 == 1 {
+        if df == 1 {
+            return FRAC_2_PI * theta;
+        }
+        FRAC_2_PI * (theta + Float::sin(theta) * sum)
+    } else {
+        Float::sin(theta) * sum
+    }
+}
+
+/// Calculate the probability density of Student's t-distribution with an
+/// integer number of degrees of freedom.
+#[cfg(any(feature = "std", feature = "libm"))]
+fn student_t_density(t: f64, df: u64) -> f64 {
+    use core::f64::consts::PI;
+    use num_traits::Float;
+
+    // Calculate `Γ((df + 1)/2) / Γ(df/2)` by recursion.
+    let (mut gamma_ratio, mut k) = if df % 2 == 1 {
+        (1. / Float::sqrt(PI), 1)
+    } else {
+        (Float::sqrt(PI) / 2., 2)
+    };
+    while k < df {
+        gamma_ratio *= (k + 1).to_f64().unwrap() / k.to_f64().unwrap();
+        k += 2;
+    }
+    let n = df.to_f64().unwrap();
+    gamma_ratio / Float::sqrt(n * PI) * Float::powf(1. + t * t / n, -(n + 1.) / 2.)
+}
+
+/// Calculate the quantile of the standard normal distribution.
+///
+/// This uses the rational approximation by Acklam, which has a relative error
+/// less than 1.15e-9.
+#[cfg(any(feature = "std", feature = "libm"))]
+fn normal_quantile(p: f64) -> f64 {
+    use num_traits::Float;
+
+    const A: [f64; 6] = [
+        -3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+        1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+        6.680131188771972e+01, -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+        -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p < P_LOW {
+        let q = Float::sqrt(-2. * Float::ln(p));
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    } else if p <= 1. - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    } else {
+        -normal_quantile(1. - p)
+    }
+}
@@ -6,7 +6,23 @@ use num_traits::{Float, ToPrimitive};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
-use super::Estimate;
+use super::{Estimate, Reset};
+
+/// The quantile level `p` is not between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidQuantileError {
+    /// The invalid quantile level.
+    pub p: f64,
+}
+
+impl core::fmt::Display for InvalidQuantileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid quantile level: {} is not between 0 and 1", self.p)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidQuantileError {}
 
 /// Estimate the p-quantile of a sequence of numbers ("population").
 ///
@@ -38,16 +54,27 @@ pub struct Quantile {
 impl Quantile {
     /// Create a new p-quantile estimator.
     ///
-    /// Panics if `p` is not between 0 and 1.
+    /// Panics if `p` is not between 0 and 1. See `new_checked` for a
+    /// non-panicking alternative.
     #[inline]
     pub fn new(p: f64) -> Quantile {
-        assert!((0. ..=1.).contains(&p));
-        Quantile {
+        Quantile::new_checked(p).unwrap()
+    }
+
+    /// Create a new p-quantile estimator.
+    ///
+    /// Fails if `p` is not between 0 and 1.
+    #[inline]
+    pub fn new_checked(p: f64) -> Result<Quantile, InvalidQuantileError> {
+        if !(0. ..=1.).contains(&p) {
+            return Err(InvalidQuantileError { p });
+        }
+        Ok(Quantile {
             q: [0.; 5],
             n: [1, 2, 3, 4, 0],
             m: [1., 1. + 2. * p, 1. + 4. * p, 3. + 2. * p, 5.],
             dm: [0., p / 2., p, (1. + p) / 2., 1.],
-        }
+        })
     }
 
     /// Return the value of `p` for this p-quantile.
@@ -56,6 +83,67 @@ impl Quantile {
         self.dm[2]
     }
 
+    /// Combine several p-quantile estimators into one by averaging them.
+    ///
+    /// The heights of the inner markers are averaged, weighted by the sample
+    /// sizes, while the outer markers are set to the minimum and maximum of
+    /// all samples. The marker positions are reset to the desired positions for
+    /// the combined sample size. Estimators with less than 5 samples are merged
+    /// by adding their samples.
+    ///
+    /// This is only an approximation, but it can be useful for estimating
+    /// quantiles of large samples in a distributed way.
+    ///
+    /// Panics if `others` is empty or if the estimators do not have the same
+    /// `p`.
+    pub fn merge_by_averaging(others: &[Quantile]) -> Quantile {
+        assert!(!others.is_empty(), "Need at least one estimator to merge");
+        let p = others[0].p();
+        assert!(
+            others.iter().all(|o| o.p() == p),
+            "Only estimators of the same quantile can be merged"
+        );
+
+        let mut result = Quantile::new(p);
+        let mut total: i64 = 0;
+        let mut q = [0.; 5];
+        q[0] = f64::INFINITY;
+        q[4] = f64::NEG_INFINITY;
+        for o in others.iter().filter(|o| o.n[4] >= 5) {
+            let weight = o.n[4].to_f64().unwrap();
+            total += o.n[4];
+            q[0] = q[0].min(o.q[0]);
+            for (h, &o_h) in q[1..4].iter_mut().zip(&o.q[1..4]) {
+                *h += weight * o_h;
+            }
+            q[4] = q[4].max(o.q[4]);
+        }
+        if total > 0 {
+            let total_f64 = total.to_f64().unwrap();
+            for h in &mut q[1..4] {
+                *h /= total_f64;
+            }
+            result.q = q;
+            result.n[4] = total;
+            for i in 0..5 {
+                result.m[i] = 1. + (total_f64 - 1.) * result.dm[i];
+            }
+            // The positions have to be increasing and within the sample.
+            result.n[0] = 1;
+            for i in 1..4 {
+                let desired = i64::conv_nearest(result.m[i]);
+                let max = total - 4 + i64::conv(i);
+                result.n[i] = desired.max(result.n[i - 1] + 1).min(max);
+            }
+        }
+        for o in others.iter().filter(|o| o.n[4] < 5) {
+            for &x in &o.q[..usize::conv(o.n[4])] {
+                result.add(x);
+            }
+        }
+        result
+    }
+
     /// Parabolic prediction for marker height.
     #[inline]
     fn parabolic(&self, i: usize, d: f64) -> f64 {
@@ -113,6 +201,42 @@ impl Quantile {
         self.q[index]
     }
 
+    /// Estimate the fraction of the population above `threshold`.
+    ///
+    /// This is an approximation of `1 - CDF(threshold)`, obtained by linearly
+    /// interpolating between the positions of the five P² markers. It is most
+    /// accurate close to the p-quantile. For fewer than five samples, the
+    /// fraction of observed samples above the threshold is returned.
+    ///
+    /// Returns 1 for thresholds below the smallest observed sample, 0 for
+    /// thresholds above the largest one, and NaN for an empty sample or a NaN
+    /// threshold.
+    pub fn exceedance_probability(&self, threshold: f64) -> f64 {
+        if self.is_empty() || threshold.is_nan() {
+            return f64::NAN;
+        }
+        if self.len() < 5 {
+            let len = usize::conv(self.len());
+            let above = self.q[..len].iter().filter(|&&x| x > threshold).count();
+            return f64::conv(above) / f64::conv(len);
+        }
+        if threshold <= self.q[0] {
+            return 1.;
+        }
+        if threshold >= self.q[4] {
+            return 0.;
+        }
+        // Marker `i` is at position `n[i]`, so the CDF at `q[i]` is
+        // approximately `(n[i] - 1) / (n[4] - 1)`.
+        let i = (0..4).rev().find(|&i| self.q[i] <= threshold).unwrap();
+        let pos_left = f64::conv(self.n[i]);
+        let pos_right = f64::conv(self.n[i + 1]);
+        let t = (threshold - self.q[i]) / (self.q[i + 1] - self.q[i]);
+        let pos = pos_left + t * (pos_right - pos_left);
+        let cdf = (pos - 1.) / (f64::conv(self.n[4]) - 1.);
+        1. - cdf
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
@@ -134,6 +258,41 @@ impl core::default::Default for Quantile {
     }
 }
 
+impl core::fmt::Display for Quantile {
+    /// Format the probability, the sample size and the estimated quantile,
+    /// e.g. `Quantile(p=0.5, n=20, estimate=4.25)`.
+    ///
+    /// The precision of the formatter is used for the estimate.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (p, n, estimate) = (self.p(), self.len(), self.quantile());
+        match f.precision() {
+            Some(precision) => write!(
+                f,
+                "Quantile(p={}, n={}, estimate={:.*})",
+                p, n, precision, estimate
+            ),
+            None => write!(f, "Quantile(p={}, n={}, estimate={})", p, n, estimate),
+        }
+    }
+}
+
+impl Reset for Quantile {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Quantile::new(self.p());
+    }
+}
+
+impl core::convert::TryFrom<f64> for Quantile {
+    type Error = InvalidQuantileError;
+
+    /// Create a new p-quantile estimator, see `Quantile::new_checked`.
+    #[inline]
+    fn try_from(p: f64) -> Result<Quantile, InvalidQuantileError> {
+        Quantile::new_checked(p)
+    }
+}
+
 impl Estimate for Quantile {
     #[inline]
     fn add(&mut self, x: f64) {
@@ -198,6 +357,161 @@ impl Estimate for Quantile {
     }
 }
 
+/// Estimate several quantiles of a sequence of numbers ("population") in one
+/// pass.
+///
+/// One [`Quantile`] estimator is used for each of the `N` requested levels,
+/// so the caveats of the P² algorithm apply to each estimate.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Estimate, MultiQuantile};
+///
+/// let mut a = MultiQuantile::new(&[0.1, 0.5, 0.9]);
+/// for i in 1..=100 {
+///     a.add(f64::from(i));
+/// }
+/// println!("The median is {}.", a.quantile(1));
+/// println!("The 10% and 90% quantiles are {:?}.", (a.quantile(0), a.quantile(2)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiQuantile<const N: usize = 5> {
+    /// Estimators of the quantiles.
+    estimators: [Quantile; N],
+    /// The current estimates of the quantiles.
+    values: [f64; N],
+}
+
+impl<const N: usize> MultiQuantile<N> {
+    /// Create a new estimator for the p-quantiles given by `ps`.
+    ///
+    /// Panics if `N` is zero or if one of the `ps` is not between 0 and 1.
+    #[inline]
+    pub fn new(ps: &[f64; N]) -> MultiQuantile<N> {
+        assert!(N > 0, "At least one quantile level is required");
+        MultiQuantile {
+            estimators: ps.map(Quantile::new),
+            values: [f64::NAN; N],
+        }
+    }
+
+    /// Return the value of `p` for the `idx`-th quantile.
+    ///
+    /// Panics if `idx` is out of bounds.
+    #[inline]
+    pub fn p(&self, idx: usize) -> f64 {
+        self.estimators[idx].p()
+    }
+
+    /// Estimate the `idx`-th quantile of the population.
+    ///
+    /// Returns NaN for an empty sample. Panics if `idx` is out of bounds.
+    #[inline]
+    pub fn quantile(&self, idx: usize) -> f64 {
+        self.values[idx]
+    }
+
+    /// Estimate all quantiles of the population, in the same order as the
+    /// levels given to `new`.
+    ///
+    /// All estimates are NaN for an empty sample.
+    #[inline]
+    pub fn quantiles(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.estimators[0].len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl core::default::Default for MultiQuantile {
+    /// Create a new estimator of the minimum, the quartiles and the maximum,
+    /// i.e. of the p-quantiles for `p = 0, 0.25, 0.5, 0.75, 1`.
+    fn default() -> MultiQuantile {
+        MultiQuantile::new(&[0., 0.25, 0.5, 0.75, 1.])
+    }
+}
+
+impl<const N: usize> Reset for MultiQuantile<N> {
+    #[inline]
+    fn reset(&mut self) {
+        for estimator in self.estimators.iter_mut() {
+            estimator.reset();
+        }
+        self.values = [f64::NAN; N];
+    }
+}
+
+impl<const N: usize> Estimate for MultiQuantile<N> {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        for (estimator, value) in self.estimators.iter_mut().zip(self.values.iter_mut()) {
+            estimator.add(x);
+            *value = estimator.quantile();
+        }
+    }
+
+    /// Return the estimate of the middle level, which is the median for the
+    /// default levels.
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.quantile(N / 2)
+    }
+}
+
+impl core::iter::FromIterator<f64> for MultiQuantile {
+    fn from_iter<T>(iter: T) -> MultiQuantile
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut e = MultiQuantile::default();
+        for i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for MultiQuantile {
+    fn from_iter<T>(iter: T) -> MultiQuantile
+    where
+        T: IntoIterator<Item = &'a f64>,
+    {
+        let mut e = MultiQuantile::default();
+        for &i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl<const N: usize> core::iter::Extend<f64> for MultiQuantile<N> {
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        for i in iter {
+            self.add(i);
+        }
+    }
+}
+
+impl<'a, const N: usize> core::iter::Extend<&'a f64> for MultiQuantile<N> {
+    fn extend<T: IntoIterator<Item = &'a f64>>(&mut self, iter: T) {
+        for &i in iter {
+            self.add(i);
+        }
+    }
+}
+
 #[test]
 fn reference() {
     let observations = [
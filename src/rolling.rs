@@ -0,0 +1,315 @@
+use super::{Estimate, Reset, Variance};
+
+/// Estimate the arithmetic mean of the last `N` numbers of a sequence.
+///
+/// The last `N` numbers are stored in a ring buffer, such that the oldest
+/// number can be subtracted from a running sum when a new one is added. To
+/// keep rounding errors from accumulating, the sum is recalculated from the
+/// buffer whenever the window was replaced completely, so each update takes
+/// amortized constant time.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::RollingMean;
+///
+/// let mut a = RollingMean::<3>::new();
+/// for &x in &[10., 1., 2., 3.] {
+///     a.add(x);
+/// }
+/// assert!(a.is_full());
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMean<const N: usize> {
+    /// The last `N` numbers.
+    window: [f64; N],
+    /// The index of the oldest number, which is replaced next.
+    next: usize,
+    /// The number of observations in the window.
+    n: usize,
+    /// The sum of the numbers in the window.
+    sum: f64,
+}
+
+impl<const N: usize> RollingMean<N> {
+    /// Create a new rolling mean estimator.
+    ///
+    /// Panics if `N` is zero.
+    #[inline]
+    pub fn new() -> RollingMean<N> {
+        assert!(N > 0, "The window must not be empty");
+        RollingMean {
+            window: [0.; N],
+            next: 0,
+            n: 0,
+            sum: 0.,
+        }
+    }
+
+    /// Add an observation, removing the oldest one if the window is full.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        if self.is_full() {
+            self.sum -= self.window[self.next];
+        } else {
+            self.n += 1;
+        }
+        self.sum += x;
+        self.window[self.next] = x;
+        self.next = (self.next + 1) % N;
+        if self.next == 0 {
+            // Otherwise a large number would leave its rounding errors in the
+            // sum after leaving the window.
+            self.sum = self.window.iter().sum();
+        }
+    }
+
+    /// Calculate the mean of the window.
+    ///
+    /// Returns NaN for an empty window.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        self.sum / self.n as f64
+    }
+
+    /// Return the number of observations in the window.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n as u64
+    }
+
+    /// Determine whether the window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Determine whether the window is full, such that adding an observation
+    /// removes the oldest one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.n == N
+    }
+}
+
+impl<const N: usize> core::default::Default for RollingMean<N> {
+    fn default() -> RollingMean<N> {
+        RollingMean::new()
+    }
+}
+
+impl<const N: usize> Reset for RollingMean<N> {
+    #[inline]
+    fn reset(&mut self) {
+        *self = RollingMean::new();
+    }
+}
+
+impl<const N: usize> Estimate for RollingMean<N> {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+/// Estimate the arithmetic mean and the variance of the last `N` numbers of a
+/// sequence.
+///
+/// The last `N` numbers are stored in a ring buffer. When the window is full,
+/// the oldest number is replaced by the new one in a single step using
+/// [`Variance::update_with_removed`]. To keep rounding errors from
+/// accumulating, the estimator is recalculated from the buffer whenever the
+/// window was replaced completely, so each update takes amortized constant
+/// time.
+///
+/// [`Variance::update_with_removed`]: ./struct.Variance.html#method.update_with_removed
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::RollingVariance;
+///
+/// let mut a = RollingVariance::<3>::new();
+/// for &x in &[10., 1., 2., 3.] {
+///     a.add(x);
+/// }
+/// assert!((a.mean() - 2.).abs() < 1e-14);
+/// assert!((a.sample_variance() - 1.).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingVariance<const N: usize> {
+    /// The last `N` numbers.
+    window: [f64; N],
+    /// The index of the oldest number, which is replaced next.
+    next: usize,
+    /// The estimator of the numbers in the window.
+    var: Variance,
+}
+
+impl<const N: usize> RollingVariance<N> {
+    /// Create a new rolling variance estimator.
+    ///
+    /// Panics if `N` is zero.
+    #[inline]
+    pub fn new() -> RollingVariance<N> {
+        assert!(N > 0, "The window must not be empty");
+        RollingVariance {
+            window: [0.; N],
+            next: 0,
+            var: Variance::new(),
+        }
+    }
+
+    /// Add an observation, removing the oldest one if the window is full.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        if self.is_full() {
+            self.var.update_with_removed(self.window[self.next], x);
+        } else {
+            self.var.add(x);
+        }
+        self.window[self.next] = x;
+        self.next = (self.next + 1) % N;
+        if self.next == 0 {
+            // Otherwise a large number would leave its rounding errors in the
+            // moments after leaving the window.
+            self.var = self.window.iter().collect();
+        }
+    }
+
+    /// Calculate the mean of the window.
+    ///
+    /// Returns NaN for an empty window.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.var.mean()
+    }
+
+    /// Calculate the sample variance of the window.
+    ///
+    /// Returns NaN for windows with 1 observation or less.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        self.var.sample_variance()
+    }
+
+    /// Calculate the population variance of the window.
+    ///
+    /// Returns NaN for an empty window.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        self.var.population_variance()
+    }
+
+    /// Return the number of observations in the window.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.var.len()
+    }
+
+    /// Determine whether the window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.var.is_empty()
+    }
+
+    /// Determine whether the window is full, such that adding an observation
+    /// removes the oldest one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.var.len() == N as u64
+    }
+
+    /// Return the estimator of the observations in the window.
+    #[inline]
+    pub fn estimator(&self) -> &Variance {
+        &self.var
+    }
+}
+
+impl<const N: usize> core::default::Default for RollingVariance<N> {
+    fn default() -> RollingVariance<N> {
+        RollingVariance::new()
+    }
+}
+
+impl<const N: usize> Reset for RollingVariance<N> {
+    #[inline]
+    fn reset(&mut self) {
+        *self = RollingVariance::new();
+    }
+}
+
+impl<const N: usize> Estimate for RollingVariance<N> {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.sample_variance()
+    }
+}
+
+macro_rules! impl_rolling_iter {
+    ( $name:ident ) => {
+        impl<const N: usize> core::iter::FromIterator<f64> for $name<N> {
+            fn from_iter<T>(iter: T) -> $name<N>
+            where
+                T: IntoIterator<Item = f64>,
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a, const N: usize> core::iter::FromIterator<&'a f64> for $name<N> {
+            fn from_iter<T>(iter: T) -> $name<N>
+            where
+                T: IntoIterator<Item = &'a f64>,
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<const N: usize> core::iter::Extend<f64> for $name<N> {
+            fn extend<T>(&mut self, iter: T)
+            where
+                T: IntoIterator<Item = f64>,
+            {
+                for x in iter {
+                    self.add(x);
+                }
+            }
+        }
+
+        impl<'a, const N: usize> core::iter::Extend<&'a f64> for $name<N> {
+            fn extend<T>(&mut self, iter: T)
+            where
+                T: IntoIterator<Item = &'a f64>,
+            {
+                for &x in iter {
+                    self.add(x);
+                }
+            }
+        }
+    };
+}
+
+impl_rolling_iter!(RollingMean);
+impl_rolling_iter!(RollingVariance);
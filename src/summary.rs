@@ -0,0 +1,207 @@
+use core::fmt;
+
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+use easy_cast::{Conv, ConvFloat};
+use float_ord::sort as sort_floats;
+
+use crate::{Estimate, Kurtosis, Max, Min, Quantile};
+
+/// The five-number summary of a sample: the minimum, the quartiles and the
+/// maximum.
+///
+/// See [`five_number_summary`](./fn.five_number_summary.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FiveNumberSummary {
+    /// The minimum.
+    pub min: f64,
+    /// The first quartile, estimated by the P² algorithm for more than five
+    /// samples.
+    pub q1: f64,
+    /// The median, estimated by the P² algorithm for more than five
+    /// samples.
+    pub median: f64,
+    /// The third quartile, estimated by the P² algorithm for more than five
+    /// samples.
+    pub q3: f64,
+    /// The maximum.
+    pub max: f64,
+}
+
+impl fmt::Display for FiveNumberSummary {
+    /// Format the summary like the boundaries of a box plot, e.g.
+    /// `1 |-[2 | 3 | 4]-| 5`.
+    ///
+    /// The precision of the formatter is used for all numbers.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = [self.min, self.q1, self.median, self.q3, self.max];
+        let separators = ["", " |-[", " | ", " | ", "]-| "];
+        for (separator, value) in separators.iter().zip(values.iter()) {
+            f.write_str(separator)?;
+            match f.precision() {
+                Some(precision) => write!(f, "{:.*}", precision, value)?,
+                None => write!(f, "{}", value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The five-number summary and the first four moments of a sample.
+///
+/// See [`full_summary`](./fn.full_summary.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct FullSummary {
+    /// The minimum, the quartiles and the maximum.
+    pub five_number: FiveNumberSummary,
+    /// The mean.
+    pub mean: f64,
+    /// The sample variance.
+    pub sample_variance: f64,
+    /// The skewness.
+    pub skewness: f64,
+    /// The excess kurtosis.
+    pub kurtosis: f64,
+    /// The sample size.
+    pub n: u64,
+}
+
+/// Calculate the five-number summary (minimum, quartiles and maximum) of a
+/// sequence of numbers in a single pass.
+///
+/// For more than five samples, the quartiles are estimated with
+/// [`Quantile`](./struct.Quantile.html). For up to five samples, they are
+/// calculated exactly by interpolating linearly between the sorted samples.
+/// All values are NaN for an empty sequence.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::five_number_summary;
+///
+/// let s = five_number_summary((1..=5).map(f64::from));
+/// assert_eq!(s.min, 1.);
+/// assert_eq!(s.q1, 2.);
+/// assert_eq!(s.median, 3.);
+/// assert_eq!(s.q3, 4.);
+/// assert_eq!(s.max, 5.);
+/// ```
+pub fn five_number_summary<I>(data: I) -> FiveNumberSummary
+where
+    I: IntoIterator<Item = f64>,
+{
+    let mut estimator = FiveNumberEstimator::new();
+    for x in data {
+        estimator.add(x);
+    }
+    estimator.summary()
+}
+
+/// Calculate the five-number summary, the mean, the sample variance, the
+/// skewness and the excess kurtosis of a sequence of numbers in a single pass.
+///
+/// See [`five_number_summary`](./fn.five_number_summary.html) and
+/// [`Kurtosis`](./struct.Kurtosis.html).
+pub fn full_summary<I>(data: I) -> FullSummary
+where
+    I: IntoIterator<Item = f64>,
+{
+    let mut estimator = FiveNumberEstimator::new();
+    let mut moments = Kurtosis::new();
+    for x in data {
+        estimator.add(x);
+        moments.add(x);
+    }
+    FullSummary {
+        five_number: estimator.summary(),
+        mean: moments.mean(),
+        sample_variance: moments.sample_variance(),
+        skewness: moments.skewness(),
+        kurtosis: moments.kurtosis(),
+        n: moments.len(),
+    }
+}
+
+/// Calculate the `p`-quantile of sorted samples by interpolating linearly
+/// between them.
+fn interpolate_sorted(sorted: &[f64], p: f64) -> f64 {
+    let h = f64::conv(sorted.len() - 1) * p;
+    let i = usize::conv_trunc(h);
+    match sorted.get(i + 1) {
+        Some(&next) => sorted[i] + (h - f64::conv(i)) * (next - sorted[i]),
+        None => sorted[i],
+    }
+}
+
+/// Estimate the five-number summary of a sequence of numbers.
+struct FiveNumberEstimator {
+    min: Min,
+    q1: Quantile,
+    median: Quantile,
+    q3: Quantile,
+    max: Max,
+    /// The first five samples, for calculating the quartiles of small samples
+    /// exactly.
+    first: [f64; 5],
+}
+
+impl FiveNumberEstimator {
+    fn new() -> FiveNumberEstimator {
+        FiveNumberEstimator {
+            min: Min::new(),
+            q1: Quantile::new(0.25),
+            median: Quantile::new(0.5),
+            q3: Quantile::new(0.75),
+            max: Max::new(),
+            first: [0.; 5],
+        }
+    }
+
+    fn add(&mut self, x: f64) {
+        if let Some(slot) = self.first.get_mut(usize::conv(self.median.len())) {
+            *slot = x;
+        }
+        self.min.add(x);
+        self.q1.add(x);
+        self.median.add(x);
+        self.q3.add(x);
+        self.max.add(x);
+    }
+
+    fn summary(&self) -> FiveNumberSummary {
+        if self.median.is_empty() {
+            return FiveNumberSummary {
+                min: f64::NAN,
+                q1: f64::NAN,
+                median: f64::NAN,
+                q3: f64::NAN,
+                max: f64::NAN,
+            };
+        }
+        let len = usize::conv(self.median.len());
+        if len <= self.first.len() {
+            // The P² markers are not adjusted before the sixth sample.
+            let mut sorted = self.first;
+            let sorted = &mut sorted[..len];
+            sort_floats(sorted);
+            return FiveNumberSummary {
+                min: self.min.min(),
+                q1: interpolate_sorted(sorted, 0.25),
+                median: interpolate_sorted(sorted, 0.5),
+                q3: interpolate_sorted(sorted, 0.75),
+                max: self.max.max(),
+            };
+        }
+        FiveNumberSummary {
+            min: self.min.min(),
+            q1: self.q1.quantile(),
+            median: self.median.quantile(),
+            q3: self.q3.quantile(),
+            max: self.max.max(),
+        }
+    }
+}
@@ -41,6 +41,24 @@ pub trait Merge {
     fn merge(&mut self, other: &Self);
 }
 
+/// Reset an estimator to its initial state.
+pub trait Reset {
+    /// Remove all observations, keeping the configuration of the estimator
+    /// (such as quantile levels or histogram ranges).
+    ///
+    /// Afterwards, the estimator behaves as if it was newly created:
+    /// ```
+    /// use average::{Estimate, Mean, Reset};
+    ///
+    /// let mut avg: Mean = [1., 2., 3.].iter().collect();
+    /// avg.reset();
+    /// assert!(avg.is_empty());
+    /// avg.add(5.);
+    /// assert_eq!(avg.mean(), 5.);
+    /// ```
+    fn reset(&mut self);
+}
+
 /// Calculate the multinomial variance. Relevant for histograms.
 #[inline(always)]
 fn multinomial_variance(n: f64, n_tot_inv: f64) -> f64 {
@@ -89,6 +107,528 @@ where
         }
     }
 
+    /// Return an iterator over the bins divided by the total count, i.e. the
+    /// probabilities of the bins.
+    ///
+    /// The items are 0 for an empty histogram.
+    #[inline]
+    fn bins_as_probabilities(&self) -> IterProbabilities<<&Self as IntoIterator>::IntoIter> {
+        let total: u64 = self.bins().iter().sum();
+        IterProbabilities {
+            histogram_iter: self.into_iter(),
+            total_inv: if total == 0 { 0. } else { 1. / (total as f64) },
+        }
+    }
+
+    /// Return an iterator over the bins divided by the total count and the
+    /// bin widths, i.e. the probability densities of the bins.
+    ///
+    /// The items are 0 for an empty histogram.
+    #[inline]
+    fn bins_as_densities(&self) -> IterDensities<<&Self as IntoIterator>::IntoIter> {
+        let total: u64 = self.bins().iter().sum();
+        IterDensities {
+            histogram_iter: self.into_iter(),
+            total_inv: if total == 0 { 0. } else { 1. / (total as f64) },
+        }
+    }
+
+    /// Return an iterator over the cumulative bin counts.
+    ///
+    /// The `i`-th item is the sum of the counts of the bins `0..=i`, so the
+    /// last item equals the total count.
+    #[inline]
+    fn cumulative_bins(&self) -> IterCumulative<'_> {
+        IterCumulative {
+            bins: self.bins().iter(),
+            sum: 0,
+        }
+    }
+
+    /// Return an iterator over the cumulative bin counts divided by the total
+    /// count.
+    ///
+    /// This is the empirical cumulative distribution function evaluated at the
+    /// upper bin boundaries. The items are NaN for an empty histogram.
+    #[inline]
+    fn cumulative_fractions(&self) -> IterCumulativeFractions<'_> {
+        let total: u64 = self.bins().iter().sum();
+        IterCumulativeFractions {
+            cumulative: self.cumulative_bins(),
+            total: total as f64,
+        }
+    }
+
+    /// Return an iterator over the probability mass function, i.e. the bin
+    /// counts divided by the total count.
+    ///
+    /// This is the same as `bins_as_probabilities`. The items sum to 1 for a
+    /// non-empty histogram and are 0 for an empty histogram.
+    #[inline]
+    fn pmf(&self) -> IterProbabilities<<&Self as IntoIterator>::IntoIter> {
+        self.bins_as_probabilities()
+    }
+
+    /// Return an iterator over the cumulative distribution function, i.e. the
+    /// running fraction of samples up to and including each bin.
+    ///
+    /// This is the same as `cumulative_fractions`. The last item is exactly 1
+    /// for a non-empty histogram. The items are NaN for an empty histogram.
+    #[inline]
+    fn cdf(&self) -> IterCumulativeFractions<'_> {
+        self.cumulative_fractions()
+    }
+
+    /// Estimate the mean of the sample from the bin centers, weighted by the
+    /// bin counts.
+    ///
+    /// Returns NaN for an empty histogram.
+    #[inline]
+    fn histogram_mean(&self) -> f64 {
+        crate::histogram::histogram_mean_variance(self).0
+    }
+
+    /// Estimate the population variance of the sample from the bin centers,
+    /// weighted by the bin counts.
+    ///
+    /// This neglects the spread of the samples within the bins. Returns NaN
+    /// for an empty histogram.
+    #[inline]
+    fn histogram_variance(&self) -> f64 {
+        crate::histogram::histogram_mean_variance(self).1
+    }
+
+    /// Return the range and count of the bin with the largest count.
+    ///
+    /// On ties, the first such bin is returned. Returns `None` if all bins are
+    /// empty.
+    #[inline]
+    fn max_bin(&self) -> Option<((f64, f64), u64)> {
+        let mut result: Option<((f64, f64), u64)> = None;
+        for (range, count) in self.into_iter() {
+            if count > 0 && result.map_or(true, |(_, max)| count > max) {
+                result = Some((range, count));
+            }
+        }
+        result
+    }
+
+    /// Return the range and count of the bin with the smallest non-zero count.
+    ///
+    /// On ties, the first such bin is returned. Returns `None` if all bins are
+    /// empty.
+    #[inline]
+    fn min_bin(&self) -> Option<((f64, f64), u64)> {
+        let mut result: Option<((f64, f64), u64)> = None;
+        for (range, count) in self.into_iter() {
+            if count > 0 && result.map_or(true, |(_, min)| count < min) {
+                result = Some((range, count));
+            }
+        }
+        result
+    }
+
+    /// Return the largest bin count.
+    ///
+    /// Returns `None` if all bins are empty.
+    #[inline]
+    fn max_bin_count(&self) -> Option<u64> {
+        self.bins().iter().copied().max().filter(|&count| count > 0)
+    }
+
+    /// Return the smallest non-zero bin count.
+    ///
+    /// Returns `None` if all bins are empty.
+    #[inline]
+    fn min_nonzero_bin_count(&self) -> Option<u64> {
+        self.bins().iter().copied().filter(|&count| count > 0).min()
+    }
+
+    /// Return the range of the bin with the largest count, i.e. of the mode.
+    ///
+    /// On ties, the first such bin is returned. Returns `(NaN, NaN)` if all
+    /// bins are empty.
+    #[inline]
+    fn mode_range(&self) -> (f64, f64) {
+        self.max_bin().map_or((f64::NAN, f64::NAN), |(range, _)| range)
+    }
+
+    /// Return the center of the bin with the largest count, i.e. of the mode.
+    ///
+    /// On ties, the first such bin is used. Returns NaN if all bins are empty.
+    #[inline]
+    fn mode_center(&self) -> f64 {
+        let (a, b) = self.mode_range();
+        0.5 * (a + b)
+    }
+
+    /// Return the fraction of samples below `x` (the percentile rank of `x`).
+    ///
+    /// This evaluates the empirical cumulative distribution function at `x`,
+    /// interpolating linearly within the bin containing `x`. Samples in bins of
+    /// infinite width are only counted once `x` is above the bin.
+    ///
+    /// Returns 0 for `x` below the range and 1 for `x` above the range of the
+    /// histogram. Returns NaN for an empty histogram or if `x` is NaN.
+    #[inline]
+    fn percentile_of_score(&self, x: f64) -> f64 {
+        let total: u64 = self.bins().iter().sum();
+        if total == 0 || x.is_nan() {
+            return f64::NAN;
+        }
+        let mut below = 0.;
+        for ((a, b), count) in self.into_iter() {
+            if x >= b {
+                below += count as f64;
+                continue;
+            }
+            let width = b - a;
+            if x > a && width.is_finite() {
+                below += count as f64 * (x - a) / width;
+            }
+            break;
+        }
+        below / (total as f64)
+    }
+
+    /// Return the total count, i.e. the sum of all bin counts.
+    #[inline]
+    fn total_count(&self) -> u64 {
+        self.bins().iter().sum()
+    }
+
+    /// Determine whether all bins are empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.bins().iter().all(|&count| count == 0)
+    }
+
+    /// Return the fraction of samples in the bins up to and including the bin
+    /// containing `x`.
+    ///
+    /// Unlike `percentile_of_score`, this does not interpolate within the bin
+    /// containing `x`.
+    ///
+    /// Returns 0 for `x` below the range and 1 for `x` above the range of the
+    /// histogram. Returns NaN for an empty histogram or if `x` is NaN.
+    #[inline]
+    fn fraction_below(&self, x: f64) -> f64 {
+        let total = self.total_count();
+        if total == 0 || x.is_nan() {
+            return f64::NAN;
+        }
+        let mut below = 0;
+        for ((a, _), count) in self.into_iter() {
+            if x < a {
+                break;
+            }
+            below += count;
+        }
+        below as f64 / total as f64
+    }
+
+    /// Estimate the value below which a fraction `p` of the samples falls.
+    ///
+    /// The samples are assumed to be uniformly distributed within the bins, so
+    /// the result is interpolated linearly within the bin containing the
+    /// `p`-th percentile. This is an approximation: the true percentile of
+    /// the samples lies in the same bin, so the error is bounded by the width
+    /// of the bin. For bins with an infinite boundary, the finite boundary is
+    /// returned instead.
+    ///
+    /// `p` is clamped to the interval from 0 to 1. Returns NaN for an empty
+    /// histogram or if `p` is NaN.
+    #[inline]
+    fn find_percentile(&self, p: f64) -> f64 {
+        let total = self.total_count();
+        if total == 0 || p.is_nan() {
+            return f64::NAN;
+        }
+        let target = p.clamp(0., 1.) * total as f64;
+        let mut below = 0.;
+        for ((a, b), count) in self.into_iter() {
+            if count == 0 {
+                continue;
+            }
+            let count = count as f64;
+            if below + count >= target {
+                if !a.is_finite() {
+                    return b;
+                }
+                if !b.is_finite() {
+                    return a;
+                }
+                return a + (target - below) / count * (b - a);
+            }
+            below += count;
+        }
+        unreachable!("the loop always reaches the total count")
+    }
+
+    /// Estimate the Shannon entropy (in nats) of the distribution from the
+    /// bin counts.
+    ///
+    /// This is `-sum(p_i * ln(p_i))`, where `p_i` is the fraction of samples
+    /// in bin `i`. Empty bins do not contribute. The widths of the bins are not
+    /// taken into account.
+    ///
+    /// Returns NaN for an empty histogram.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    fn entropy(&self) -> f64 {
+        let total = self.total_count();
+        if total == 0 {
+            return f64::NAN;
+        }
+        let total_inv = 1. / total as f64;
+        -self
+            .bins()
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 * total_inv;
+                p * num_traits::Float::ln(p)
+            })
+            .sum::<f64>()
+    }
+
+    /// Estimate the Kullback-Leibler divergence (in nats) of the distribution
+    /// `Q` given by `other` from the distribution `P` given by `self`.
+    ///
+    /// This is `sum(p_i * ln(p_i / q_i))`, where `p_i` and `q_i` are the
+    /// fractions of samples in bin `i`. Bins where `p_i` is zero do not
+    /// contribute. The result is infinite if `q_i` is zero for a bin where
+    /// `p_i` is not.
+    ///
+    /// Returns NaN if one of the histograms is empty. Panics if the histograms
+    /// have different ranges.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    fn kl_divergence<H>(&self, other: &H) -> f64
+    where
+        H: Histogram,
+        for<'a> &'a H: IntoIterator<Item = ((f64, f64), u64)>,
+    {
+        crate::histogram::histogram_assert_same_ranges(self, other);
+        crate::histogram::histogram_kl_divergence(self.bins(), other.bins())
+    }
+
+    /// Estimate the Jensen-Shannon divergence (in nats) of the distributions
+    /// given by `self` and `other`.
+    ///
+    /// This is the symmetrized `(KL(P || M) + KL(Q || M)) / 2`, where `M` is
+    /// the mixture `(P + Q) / 2`. Unlike the Kullback-Leibler divergence, it
+    /// is always finite, with values between 0 and `ln(2)`.
+    ///
+    /// Returns NaN if one of the histograms is empty. Panics if the histograms
+    /// have different ranges.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    fn js_divergence<H>(&self, other: &H) -> f64
+    where
+        H: Histogram,
+        for<'a> &'a H: IntoIterator<Item = ((f64, f64), u64)>,
+    {
+        crate::histogram::histogram_assert_same_ranges(self, other);
+        crate::histogram::histogram_js_divergence(self.bins(), other.bins())
+    }
+
+    /// Transform the histogram of `x` into a histogram of `f(x)` with
+    /// `n_output_bins` bins of constant width.
+    ///
+    /// The new histogram spans the images of the finite bin boundaries. The
+    /// count of each bin is moved to the bin containing the image of its
+    /// center, so the total count is preserved (except for bins whose center
+    /// is mapped to NaN, which are dropped), and the Jacobian `|f'(x)|` of
+    /// the transformation is accounted for by the widths of the new bins.
+    /// This is an approximation, which is better for narrow bins.
+    ///
+    /// Panics if `n_output_bins` is zero.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    fn apply_transform<F>(&self, f: F, n_output_bins: usize) -> crate::DynHistogram
+    where
+        F: Fn(f64) -> f64,
+    {
+        crate::histogram::histogram_apply_transform(self, f, n_output_bins)
+    }
+
+    /// Downsample the histogram to `new_n` bins by summing the counts of
+    /// consecutive bins.
+    ///
+    /// Returns the `new_n + 1` ranges and the `new_n` counts of the new bins.
+    /// Fails if `new_n` is zero, larger than the current number of bins, or
+    /// does not divide it.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn rebin(
+        &self,
+        new_n: usize,
+    ) -> Result<(std::vec::Vec<f64>, std::vec::Vec<u64>), crate::RebinError> {
+        let n = self.bins().len();
+        if new_n == 0 {
+            return Err(crate::RebinError::ZeroBins);
+        }
+        if new_n > n {
+            return Err(crate::RebinError::TooManyBins);
+        }
+        if n % new_n != 0 {
+            return Err(crate::RebinError::NotDivisible);
+        }
+        let factor = n / new_n;
+        let mut ranges = std::vec::Vec::with_capacity(new_n + 1);
+        let mut counts = std::vec::Vec::with_capacity(new_n);
+        for (i, ((a, b), count)) in self.into_iter().enumerate() {
+            if i % factor == 0 {
+                ranges.push(a);
+                counts.push(0);
+            }
+            *counts.last_mut().unwrap() += count;
+            if i + 1 == n {
+                ranges.push(b);
+            }
+        }
+        Ok((ranges, counts))
+    }
+
+    /// Smooth the bin counts by convolving them with a Gaussian kernel.
+    ///
+    /// The standard deviation `sigma` of the kernel is given in units of bins,
+    /// and the kernel is truncated at `3 * sigma` or at the number of bins,
+    /// whichever is smaller. It is normalized, so the total count is preserved
+    /// except for the counts smoothed beyond the outermost bins. The widths of
+    /// the bins are not taken into account.
+    ///
+    /// Returns one smoothed count per bin. All of them are NaN if `sigma` is
+    /// not positive or not finite.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    fn smooth_gaussian(&self, sigma: f64) -> std::vec::Vec<f64> {
+        crate::histogram::histogram_smooth_gaussian(self.bins(), sigma)
+    }
+
+    /// Calculate the expected bin counts of `total` samples drawn from a
+    /// distribution with the probability density function `pdf`.
+    ///
+    /// The density is integrated over each bin using the midpoint rule, which
+    /// is an approximation that is better for narrow bins. Bins of infinite
+    /// width yield NaN.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    fn expected_counts<F>(&self, pdf: F, total: u64) -> std::vec::Vec<f64>
+    where
+        F: Fn(f64) -> f64,
+    {
+        crate::histogram::histogram_expected_counts(self, pdf, total)
+    }
+
+    /// Calculate Pearson's chi-squared statistic comparing the bin counts to
+    /// the ones expected for a distribution with the probability density
+    /// function `pdf`.
+    ///
+    /// The expected counts are calculated by `expected_counts` for the total
+    /// count of the histogram. Bins where both the observed and the expected
+    /// count are zero are skipped. The result is infinite if a sample falls
+    /// into a bin with an expected count of zero.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    fn chi_squared_vs_distribution<F>(&self, pdf: F) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        crate::histogram::histogram_chi_squared_vs_distribution(self, pdf)
+    }
+
+    /// Calculate Pearson's chi-squared statistic for the goodness of fit of
+    /// the bin counts to the expected fractions of the total count.
+    ///
+    /// `expected_fractions` must have one entry per bin, and the entries
+    /// should sum to 1. The statistic is `sum((observed - expected)^2 /
+    /// expected)`, where `expected` is the fraction times the total count.
+    /// Under the hypothesis that the samples follow the expected fractions, it
+    /// approximately follows a chi-squared distribution with `n_bins - 1`
+    /// degrees of freedom, from which the p-value can be obtained.
+    ///
+    /// Returns NaN if the length of `expected_fractions` differs from the
+    /// number of bins, or if an expected count is zero (in particular for an
+    /// empty histogram).
+    #[inline]
+    fn chi_squared_test(&self, expected_fractions: &[f64]) -> f64 {
+        crate::histogram::histogram_chi_squared_test(self.bins(), expected_fractions)
+    }
+
+    /// Return a histogram of the probabilities of the bins, i.e. the counts
+    /// divided by the total count.
+    ///
+    /// The bins are NaN for an empty histogram.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn to_probability_histogram(&self) -> crate::FloatHistogram {
+        let total: u64 = self.bins().iter().sum();
+        let total_inv = 1. / (total as f64);
+        let mut range = std::vec::Vec::with_capacity(self.bins().len() + 1);
+        let mut bin = std::vec::Vec::with_capacity(self.bins().len());
+        for ((a, b), count) in self.into_iter() {
+            if range.is_empty() {
+                range.push(a);
+            }
+            range.push(b);
+            bin.push(count as f64 * total_inv);
+        }
+        crate::FloatHistogram::from_parts(range, bin)
+    }
+
+    /// Return a histogram of the probability densities of the bins, i.e. the
+    /// counts divided by the total count and the bin widths.
+    ///
+    /// The bins are NaN for an empty histogram.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn to_density_histogram(&self) -> crate::FloatHistogram {
+        self.to_probability_histogram().into_density()
+    }
+
+    /// Draw a random sample from the distribution given by the histogram.
+    ///
+    /// A bin is chosen with a probability proportional to its count, and the
+    /// sample is drawn uniformly from the range of the bin. For bins with an
+    /// infinite boundary, the finite boundary is returned instead.
+    ///
+    /// Returns `None` for an empty histogram.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    fn sample<R>(&self, rng: &mut R) -> Option<f64>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let total: u64 = self.bins().iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut k = rng.gen_range(0..total);
+        for ((a, b), count) in self.into_iter() {
+            if k >= count {
+                k -= count;
+                continue;
+            }
+            return Some(match (a.is_finite(), b.is_finite()) {
+                _ if a == b => a,
+                (true, true) => rng.gen_range(a..b),
+                (true, false) => a,
+                (false, true) => b,
+                (false, false) => f64::NAN,
+            });
+        }
+        unreachable!("The sum of the bins is larger than the total")
+    }
+
     /// Return an iterator over the bin variances.
     ///
     /// This is more efficient than calling `variance()` for each bin.
@@ -123,8 +663,30 @@ where
             .next()
             .map(|((a, b), count)| (count as f64) / (b - a))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
 }
 
+impl<T> DoubleEndedIterator for IterNormalized<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|((a, b), count)| (count as f64) / (b - a))
+    }
+}
+
+impl<T> ExactSizeIterator for IterNormalized<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
 /// Iterate over the widths of the bins.
 #[derive(Debug, Clone)]
 pub struct IterWidths<T>
@@ -144,8 +706,28 @@ where
     fn next(&mut self) -> Option<f64> {
         self.histogram_iter.next().map(|((a, b), _)| b - a)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterWidths<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter.next_back().map(|((a, b), _)| b - a)
+    }
 }
 
+impl<T> ExactSizeIterator for IterWidths<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
 /// Iterate over the bin centers.
 #[derive(Debug, Clone)]
 pub struct IterBinCenters<T>
@@ -165,8 +747,28 @@ where
     fn next(&mut self) -> Option<f64> {
         self.histogram_iter.next().map(|((a, b), _)| 0.5 * (a + b))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
 }
 
+impl<T> DoubleEndedIterator for IterBinCenters<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter.next_back().map(|((a, b), _)| 0.5 * (a + b))
+    }
+}
+
+impl<T> ExactSizeIterator for IterBinCenters<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
 /// Iterate over the variances.
 #[derive(Debug, Clone)]
 pub struct IterVariances<T>
@@ -189,4 +791,169 @@ where
             .next()
             .map(|(_, n)| multinomial_variance(n as f64, self.sum_inv))
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterVariances<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|(_, n)| multinomial_variance(n as f64, self.sum_inv))
+    }
+}
+
+impl<T> ExactSizeIterator for IterVariances<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
+/// Iterate over the probabilities of the bins.
+#[derive(Debug, Clone)]
+pub struct IterProbabilities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    histogram_iter: T,
+    total_inv: f64,
+}
+
+impl<T> Iterator for IterProbabilities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next()
+            .map(|(_, count)| (count as f64) * self.total_inv)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterProbabilities<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|(_, count)| (count as f64) * self.total_inv)
+    }
+}
+
+impl<T> ExactSizeIterator for IterProbabilities<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
+/// Iterate over the probability densities of the bins.
+#[derive(Debug, Clone)]
+pub struct IterDensities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    histogram_iter: T,
+    total_inv: f64,
+}
+
+impl<T> Iterator for IterDensities<T>
+where
+    T: Iterator<Item = ((f64, f64), u64)>,
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next()
+            .map(|((a, b), count)| (count as f64) * self.total_inv / (b - a))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.histogram_iter.size_hint()
+    }
 }
+
+impl<T> DoubleEndedIterator for IterDensities<T>
+where
+    T: DoubleEndedIterator<Item = ((f64, f64), u64)>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<f64> {
+        self.histogram_iter
+            .next_back()
+            .map(|((a, b), count)| (count as f64) * self.total_inv / (b - a))
+    }
+}
+
+impl<T> ExactSizeIterator for IterDensities<T>
+where
+    T: ExactSizeIterator<Item = ((f64, f64), u64)>,
+{}
+
+/// Iterate over the cumulative bin counts.
+#[derive(Debug, Clone)]
+pub struct IterCumulative<'a> {
+    bins: core::slice::Iter<'a, u64>,
+    sum: u64,
+}
+
+impl<'a> Iterator for IterCumulative<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        self.bins.next().map(|&count| {
+            self.sum += count;
+            self.sum
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bins.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for IterCumulative<'a> {}
+
+/// Iterate over the cumulative bin counts divided by the total count.
+#[derive(Debug, Clone)]
+pub struct IterCumulativeFractions<'a> {
+    cumulative: IterCumulative<'a>,
+    total: f64,
+}
+
+impl<'a> Iterator for IterCumulativeFractions<'a> {
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.cumulative
+            .next()
+            .map(|sum| (sum as f64) / self.total)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cumulative.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for IterCumulativeFractions<'a> {}
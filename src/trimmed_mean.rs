@@ -0,0 +1,146 @@
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+use super::{Estimate, Mean, Quantile, Reset};
+
+/// Estimate the trimmed mean of a sequence of numbers ("population").
+///
+/// The bottom and top `alpha` fractions of the samples are discarded, which
+/// makes the estimate robust against outliers. The bounds of the interior are
+/// estimated online with two [`Quantile`](./struct.Quantile.html) estimators,
+/// and each sample is only taken into account if it lies within the bounds
+/// estimated at the time it is added. The result is therefore only an
+/// approximation of the trimmed mean of the whole sample.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::TrimmedMean;
+///
+/// let a: TrimmedMean = (1..=10).map(f64::from).collect();
+/// assert_eq!(a.len(), 10);
+/// println!("The trimmed mean is {}.", a.trimmed_mean());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TrimmedMean {
+    /// Estimator of the lower bound.
+    lower: Quantile,
+    /// Estimator of the upper bound.
+    upper: Quantile,
+    /// Estimator of the mean of the samples within the bounds.
+    interior: Mean,
+}
+
+impl TrimmedMean {
+    /// Create a new trimmed mean estimator discarding the bottom and top
+    /// `alpha` fractions of the samples.
+    ///
+    /// Panics if `alpha` is not in `[0, 0.5)`.
+    #[inline]
+    pub fn new(alpha: f64) -> TrimmedMean {
+        assert!(
+            (0. ..0.5).contains(&alpha),
+            "The trim fraction has to be in [0, 0.5)"
+        );
+        TrimmedMean {
+            lower: Quantile::new(alpha),
+            upper: Quantile::new(1. - alpha),
+            interior: Mean::new(),
+        }
+    }
+
+    /// Return the trim fraction `alpha`.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.lower.p()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// Return the sample size, including the discarded samples.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.lower.len()
+    }
+
+    /// Return the number of samples that were not discarded.
+    #[inline]
+    pub fn interior_len(&self) -> u64 {
+        self.interior.len()
+    }
+
+    /// Estimate the trimmed mean of the population.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn trimmed_mean(&self) -> f64 {
+        self.interior.mean()
+    }
+}
+
+impl core::default::Default for TrimmedMean {
+    /// Create a new estimator discarding the bottom and top 10 % of the
+    /// samples.
+    fn default() -> TrimmedMean {
+        TrimmedMean::new(0.1)
+    }
+}
+
+impl Reset for TrimmedMean {
+    #[inline]
+    fn reset(&mut self) {
+        *self = TrimmedMean::new(self.alpha());
+    }
+}
+
+impl Estimate for TrimmedMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.lower.add(x);
+        self.upper.add(x);
+        if self.lower.quantile() <= x && x <= self.upper.quantile() {
+            self.interior.add(x);
+        }
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.trimmed_mean()
+    }
+}
+
+impl core::iter::FromIterator<f64> for TrimmedMean {
+    /// Create a new estimator with the default trim fraction from the samples.
+    fn from_iter<T>(iter: T) -> TrimmedMean
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut e = TrimmedMean::default();
+        for i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a f64> for TrimmedMean {
+    /// Create a new estimator with the default trim fraction from the samples.
+    fn from_iter<T>(iter: T) -> TrimmedMean
+    where
+        T: IntoIterator<Item = &'a f64>,
+    {
+        let mut e = TrimmedMean::default();
+        for &i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl_extend!(TrimmedMean);
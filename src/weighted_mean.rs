@@ -1,4 +1,6 @@
-use super::{Estimate, MeanWithError, Merge};
+use num_traits::ToPrimitive;
+
+use super::{Estimate, Max, Mean, MeanWithError, Merge, Min};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +24,10 @@ pub struct WeightedMean {
     weight_sum: f64,
     /// Weighted mean value.
     weighted_avg: f64,
+    /// Largest weight.
+    max_weight: Max,
+    /// Smallest weight.
+    min_weight: Min,
 }
 
 impl WeightedMean {
@@ -30,6 +36,8 @@ impl WeightedMean {
         WeightedMean {
             weight_sum: 0.,
             weighted_avg: 0.,
+            max_weight: Max::new(),
+            min_weight: Min::new(),
         }
     }
 
@@ -43,6 +51,8 @@ impl WeightedMean {
         // and
         // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
         self.weight_sum += weight;
+        self.max_weight.add(weight);
+        self.min_weight.add(weight);
 
         let prev_avg = self.weighted_avg;
         self.weighted_avg = prev_avg + (weight / self.weight_sum) * (sample - prev_avg);
@@ -71,6 +81,63 @@ impl WeightedMean {
     pub fn mean(&self) -> f64 {
         if !self.is_empty() { self.weighted_avg } else { f64::NAN }
     }
+
+    /// Return the largest weight.
+    ///
+    /// Returns `f64::NEG_INFINITY` for an empty sample.
+    #[inline]
+    pub fn max_weight(&self) -> f64 {
+        self.max_weight.max()
+    }
+
+    /// Return the smallest weight.
+    ///
+    /// Returns `f64::INFINITY` for an empty sample.
+    #[inline]
+    pub fn min_weight(&self) -> f64 {
+        self.min_weight.min()
+    }
+
+    /// Return the difference between the largest and the smallest weight.
+    ///
+    /// Returns NaN for an empty sample.
+    #[inline]
+    pub fn weight_range(&self) -> f64 {
+        if self.min_weight() > self.max_weight() {
+            // No weights were added.
+            return f64::NAN;
+        }
+        self.max_weight() - self.min_weight()
+    }
+
+    /// Convert into an unweighted mean estimator.
+    ///
+    /// Each unit of the sum of weights is interpreted as one sample, the sum
+    /// of weights is rounded to the nearest integer. This is exact if all
+    /// weights were 1.
+    #[inline]
+    pub fn into_mean(self) -> Mean {
+        let n = (self.weight_sum + 0.5).to_u64().unwrap_or(0);
+        if n == 0 {
+            return Mean::new();
+        }
+        Mean::from_parts(self.weighted_avg, n)
+    }
+
+    /// Create a weighted mean estimator from an unweighted one, using a weight
+    /// of 1 for each sample.
+    #[inline]
+    pub fn from_mean(mean: Mean) -> WeightedMean {
+        if mean.is_empty() {
+            return WeightedMean::new();
+        }
+        WeightedMean {
+            weight_sum: mean.len().to_f64().unwrap(),
+            weighted_avg: mean.mean(),
+            max_weight: Max::from_value(1.),
+            min_weight: Min::from_value(1.),
+        }
+    }
 }
 
 impl core::default::Default for WeightedMean {
@@ -142,11 +209,14 @@ impl Merge for WeightedMean {
     /// ```
     #[inline]
     fn merge(&mut self, other: &WeightedMean) {
+        self.max_weight.merge(&other.max_weight);
+        self.min_weight.merge(&other.min_weight);
         if other.is_empty() {
             return;
         }
         if self.is_empty() {
-            *self = other.clone();
+            self.weight_sum = other.weight_sum;
+            self.weighted_avg = other.weighted_avg;
             return;
         }
         let total_weight_sum = self.weight_sum + other.weight_sum;
@@ -157,6 +227,9 @@ impl Merge for WeightedMean {
     }
 }
 
+impl_add_assign!(WeightedMean);
+impl_reset!(WeightedMean);
+
 /// Estimate the weighted and unweighted arithmetic mean and the unweighted
 /// variance of a sequence of numbers ("population").
 ///
@@ -177,10 +250,16 @@ impl Merge for WeightedMean {
 pub struct WeightedMeanWithError {
     /// Sum of the squares of the weights.
     weight_sum_sq: f64,
+    /// Sum of `w * ln(w)` over the weights, for calculating the entropy.
+    weight_sum_log: f64,
     /// Estimator of the weighted mean.
     weighted_avg: WeightedMean,
     /// Estimator of unweighted mean and its variance.
     unweighted_avg: MeanWithError,
+    /// Estimator of the weighted mean and variance of the logarithms of the
+    /// samples.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    log_avg: WeightedVariance,
 }
 
 impl WeightedMeanWithError {
@@ -189,8 +268,11 @@ impl WeightedMeanWithError {
     pub fn new() -> WeightedMeanWithError {
         WeightedMeanWithError {
             weight_sum_sq: 0.,
+            weight_sum_log: 0.,
             weighted_avg: WeightedMean::new(),
             unweighted_avg: MeanWithError::new(),
+            #[cfg(any(feature = "std", feature = "libm"))]
+            log_avg: WeightedVariance::new(),
         }
     }
 
@@ -205,6 +287,20 @@ impl WeightedMeanWithError {
         // and
         // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
         self.weight_sum_sq += weight * weight;
+        #[cfg(any(feature = "std", feature = "libm"))]
+        {
+            if weight != 0. {
+                self.weight_sum_log += weight * num_traits::Float::ln(weight);
+            }
+            // `ln(0) = -inf` would make the result depend on the order of the
+            // samples, so zero is treated like a negative sample.
+            let ln_sample = if sample == 0. {
+                f64::NAN
+            } else {
+                num_traits::Float::ln(sample)
+            };
+            self.log_avg.add(ln_sample, weight);
+        }
         self.weighted_avg.add(sample, weight);
         self.unweighted_avg.add(sample);
     }
@@ -316,6 +412,74 @@ impl WeightedMeanWithError {
     pub fn error(&self) -> f64 {
         num_traits::Float::sqrt(self.variance_of_weighted_mean())
     }
+
+    /// Calculate the Shannon entropy of the normalized weights.
+    ///
+    /// This is `-sum_i w_i/W * ln(w_i/W)`, where `W` is the sum of the
+    /// weights. It is maximal (`ln(n)`) if all weights are equal and zero if
+    /// only one weight is nonzero.
+    ///
+    /// Returns NaN if the sum of weights is zero.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn weight_entropy(&self) -> f64 {
+        let weight_sum = self.weighted_avg.sum_weights();
+        if weight_sum == 0. {
+            return f64::NAN;
+        }
+        // -sum_i w_i/W * ln(w_i/W) = ln(W) - sum_i w_i ln(w_i) / W
+        num_traits::Float::ln(weight_sum) - self.weight_sum_log / weight_sum
+    }
+
+    /// Calculate the entropy of the normalized weights divided by its maximum
+    /// `ln(n)`.
+    ///
+    /// The result is between 0 (concentrated weights) and 1 (equal weights).
+    /// Returns NaN if the sum of weights is zero or for samples of size 1 or
+    /// less.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn normalized_weight_entropy(&self) -> f64 {
+        if self.len() < 2 {
+            return f64::NAN;
+        }
+        let n = self.len().to_f64().unwrap();
+        self.weight_entropy() / num_traits::Float::ln(n)
+    }
+
+    /// Estimate the weighted geometric mean of the population.
+    ///
+    /// This is `exp(sum_i w_i ln(x_i) / W)`, the exponential of the weighted
+    /// mean of the logarithms of the samples, where `W` is the sum of the
+    /// weights. It is a natural estimate of the location of log-normally
+    /// distributed samples.
+    ///
+    /// Returns NaN for an empty sample, if the sum of weights is zero or if a
+    /// sample is zero or negative.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn geometric_mean(&self) -> f64 {
+        num_traits::Float::exp(self.log_avg.weighted_mean())
+    }
+
+    /// Calculate the weighted sample variance of the logarithms of the
+    /// samples.
+    ///
+    /// See `WeightedVariance::weighted_sample_variance` for the estimator.
+    /// For log-normally distributed samples, this estimates the variance
+    /// parameter `sigma^2` of the distribution.
+    ///
+    /// Returns NaN for samples of size 1 or less, if the sum of weights is
+    /// zero or if a sample is zero or negative.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[cfg_attr(doc_cfg, doc(cfg(any(feature = "std", feature = "libm"))))]
+    #[inline]
+    pub fn log_variance(&self) -> f64 {
+        self.log_avg.weighted_sample_variance()
+    }
 }
 
 impl Merge for WeightedMeanWithError {
@@ -341,11 +505,17 @@ impl Merge for WeightedMeanWithError {
     #[inline]
     fn merge(&mut self, other: &WeightedMeanWithError) {
         self.weight_sum_sq += other.weight_sum_sq;
+        self.weight_sum_log += other.weight_sum_log;
         self.weighted_avg.merge(&other.weighted_avg);
         self.unweighted_avg.merge(&other.unweighted_avg);
+        #[cfg(any(feature = "std", feature = "libm"))]
+        self.log_avg.merge(&other.log_avg);
     }
 }
 
+impl_add_assign!(WeightedMeanWithError);
+impl_reset!(WeightedMeanWithError);
+
 impl core::default::Default for WeightedMeanWithError {
     fn default() -> WeightedMeanWithError {
         WeightedMeanWithError::new()
@@ -393,3 +563,195 @@ impl<'a> core::iter::Extend<&'a (f64, f64)> for WeightedMeanWithError {
         }
     }
 }
+
+/// Estimate the weighted arithmetic mean and the weighted variance of a
+/// sequence of numbers ("population").
+///
+/// The weights are interpreted as reliability weights (for instance
+/// precisions, i.e. inverse variances) rather than as frequencies. The
+/// algorithm was suggested by West in 1979.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedVariance;
+///
+/// let a: WeightedVariance = (1..6).zip(1..6)
+///     .map(|(x, w)| (f64::from(x), f64::from(w))).collect();
+/// println!("The weighted variance is {}.", a.weighted_sample_variance());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct WeightedVariance {
+    /// Sum of the squares of the weights.
+    weight_sum_sq: f64,
+    /// Weighted sum of the squared deviations from the weighted mean.
+    sum_2: f64,
+    /// Estimator of the weighted mean.
+    weighted_avg: WeightedMean,
+}
+
+impl WeightedVariance {
+    /// Create a new weighted variance estimator.
+    #[inline]
+    pub fn new() -> WeightedVariance {
+        WeightedVariance {
+            weight_sum_sq: 0.,
+            sum_2: 0.,
+            weighted_avg: WeightedMean::new(),
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, sample: f64, weight: f64) {
+        // See West, D. H. D. (1979). "Updating mean and variance estimates: an
+        // improved method". Communications of the ACM 22 (9): 532–535.
+        let delta = sample - self.weighted_avg.weighted_avg;
+        self.weighted_avg.add(sample, weight);
+        self.weight_sum_sq += weight * weight;
+        self.sum_2 += weight * delta * (sample - self.weighted_avg.weighted_avg);
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// Might be a false positive if the sum of weights is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weighted_avg.is_empty()
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weighted_avg.sum_weights()
+    }
+
+    /// Estimate the weighted mean of the population.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn weighted_mean(&self) -> f64 {
+        self.weighted_avg.mean()
+    }
+
+    /// Calculate the weighted population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    ///
+    /// Returns NaN for an empty sample, or if the sum of weights is zero.
+    #[inline]
+    pub fn weighted_population_variance(&self) -> f64 {
+        if self.is_empty() {
+            return f64::NAN;
+        }
+        self.sum_2 / self.sum_weights()
+    }
+
+    /// Calculate the weighted sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population for
+    /// reliability weights. It reduces to the unweighted sample variance if
+    /// all weights are equal.
+    ///
+    /// Returns NaN for samples of size 1 or less, or if the sum of weights is
+    /// zero. Observations with zero weight do not count towards the size.
+    #[inline]
+    pub fn weighted_sample_variance(&self) -> f64 {
+        let weight_sum = self.sum_weights();
+        // For a single observation, the square of the sum of weights equals
+        // the sum of squared weights. Comparing them avoids dividing by a
+        // rounding error.
+        if weight_sum == 0. || weight_sum * weight_sum <= self.weight_sum_sq {
+            return f64::NAN;
+        }
+        self.sum_2 / (weight_sum - self.weight_sum_sq / weight_sum)
+    }
+}
+
+impl core::default::Default for WeightedVariance {
+    fn default() -> WeightedVariance {
+        WeightedVariance::new()
+    }
+}
+
+impl Merge for WeightedVariance {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{WeightedVariance, Merge};
+    ///
+    /// let weighted_sequence: &[(f64, f64)] = &[
+    ///     (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+    ///     (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9)];
+    /// let (left, right) = weighted_sequence.split_at(3);
+    /// let var_total: WeightedVariance = weighted_sequence.iter().collect();
+    /// let mut var_left: WeightedVariance = left.iter().collect();
+    /// let var_right: WeightedVariance = right.iter().collect();
+    /// var_left.merge(&var_right);
+    /// assert!((var_total.weighted_sample_variance() - var_left.weighted_sample_variance()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &WeightedVariance) {
+        if !self.is_empty() && !other.is_empty() {
+            let weight_self = self.sum_weights();
+            let weight_other = other.sum_weights();
+            let delta = other.weighted_avg.weighted_avg - self.weighted_avg.weighted_avg;
+            self.sum_2 += delta * delta * weight_self * weight_other / (weight_self + weight_other);
+        }
+        self.sum_2 += other.sum_2;
+        self.weight_sum_sq += other.weight_sum_sq;
+        self.weighted_avg.merge(&other.weighted_avg);
+    }
+}
+
+impl_add_assign!(WeightedVariance);
+impl_reset!(WeightedVariance);
+
+impl core::iter::FromIterator<(f64, f64)> for WeightedVariance {
+    fn from_iter<T>(iter: T) -> WeightedVariance
+    where
+        T: IntoIterator<Item = (f64, f64)>,
+    {
+        let mut a = WeightedVariance::new();
+        for (i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for WeightedVariance {
+    fn extend<T: IntoIterator<Item = (f64, f64)>>(&mut self, iter: T) {
+        for (i, w) in iter {
+            self.add(i, w);
+        }
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedVariance {
+    fn from_iter<T>(iter: T) -> WeightedVariance
+    where
+        T: IntoIterator<Item = &'a (f64, f64)>,
+    {
+        let mut a = WeightedVariance::new();
+        for &(i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for WeightedVariance {
+    fn extend<T: IntoIterator<Item = &'a (f64, f64)>>(&mut self, iter: T) {
+        for &(i, w) in iter {
+            self.add(i, w);
+        }
+    }
+}
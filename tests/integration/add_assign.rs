@@ -0,0 +1,82 @@
+use average::{
+    assert_almost_eq, Covariance, Max, Mean, Min, Variance, WeightedMean, WeightedMeanWithError,
+};
+
+const SEQUENCE: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+
+const PAIRS: &[(f64, f64)] = &[
+    (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+    (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9),
+];
+
+#[test]
+fn mean_variance_min_max() {
+    for mid in 0..=SEQUENCE.len() {
+        let (left, right) = SEQUENCE.split_at(mid);
+
+        let mut mean: Mean = left.iter().collect();
+        mean += &right.iter().collect();
+        assert_eq!(mean.len(), 9);
+        assert_almost_eq!(mean.mean(), 5., 1e-14);
+
+        let mut var: Variance = left.iter().collect();
+        var += &right.iter().collect();
+        assert_eq!(var.len(), 9);
+        assert_almost_eq!(var.sample_variance(), 7.5, 1e-14);
+
+        let mut min: Min = left.iter().collect();
+        min += &right.iter().collect();
+        assert_eq!(min.min(), 1.);
+
+        let mut max: Max = left.iter().collect();
+        max += &right.iter().collect();
+        assert_eq!(max.max(), 9.);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn skewness_kurtosis() {
+    use average::{Kurtosis, Skewness};
+
+    let total: Kurtosis = SEQUENCE.iter().collect();
+    for mid in 0..=SEQUENCE.len() {
+        let (left, right) = SEQUENCE.split_at(mid);
+
+        let mut skew: Skewness = left.iter().collect();
+        skew += &right.iter().collect();
+        assert_almost_eq!(skew.skewness(), 0., 1e-14);
+
+        let mut kurt: Kurtosis = left.iter().collect();
+        kurt += &right.iter().collect();
+        assert_eq!(kurt.len(), 9);
+        assert_almost_eq!(kurt.kurtosis(), total.kurtosis(), 1e-14);
+    }
+}
+
+#[test]
+fn covariance_weighted_mean() {
+    let cov_total: Covariance = PAIRS.iter().collect();
+    let avg_total: WeightedMean = PAIRS.iter().collect();
+    let err_total: WeightedMeanWithError = PAIRS.iter().collect();
+    for mid in 0..=PAIRS.len() {
+        let (left, right) = PAIRS.split_at(mid);
+
+        let mut cov: Covariance = left.iter().collect();
+        cov += &right.iter().collect();
+        assert_almost_eq!(cov.sample_covariance(), cov_total.sample_covariance(), 1e-14);
+
+        let mut avg: WeightedMean = left.iter().collect();
+        avg += &right.iter().collect();
+        assert_almost_eq!(avg.mean(), avg_total.mean(), 1e-14);
+
+        let mut err: WeightedMeanWithError = left.iter().collect();
+        err += &right.iter().collect();
+        assert_almost_eq!(err.weighted_mean(), err_total.weighted_mean(), 1e-14);
+        assert_almost_eq!(
+            err.variance_of_weighted_mean(),
+            err_total.variance_of_weighted_mean(),
+            1e-14
+        );
+    }
+}
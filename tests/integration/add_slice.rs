@@ -0,0 +1,69 @@
+use rand::{Rng, SeedableRng};
+
+use average::{Estimate, Mean, Moments4, Variance};
+
+fn data() -> Vec<f64> {
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    (0..1_000_000).map(|_| rng.gen_range(-10.0..100.0)).collect()
+}
+
+#[test]
+fn mean() {
+    let data = data();
+    let mut a = Mean::new();
+    a.add_slice(&data);
+    let mut b = Mean::new();
+    for &x in &data {
+        b.add(x);
+    }
+    assert_eq!(a.len(), 1_000_000);
+    assert_eq!(a.mean(), b.mean());
+}
+
+#[test]
+fn variance() {
+    let data = data();
+    let mut a = Variance::new();
+    a.add_slice(&data[..10]);
+    a.add_slice(&data[10..]);
+    let b: Variance = data.iter().collect();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.mean(), b.mean());
+    assert_eq!(a.sample_variance(), b.sample_variance());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn skewness_kurtosis() {
+    use average::{Kurtosis, Skewness};
+
+    let data = data();
+    let mut a = Skewness::new();
+    a.add_slice(&data);
+    let b: Skewness = data.iter().collect();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.sample_variance(), b.sample_variance());
+    assert_eq!(a.skewness(), b.skewness());
+
+    let mut a = Kurtosis::new();
+    a.add_slice(&data);
+    let b: Kurtosis = data.iter().collect();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.skewness(), b.skewness());
+    assert_eq!(a.kurtosis(), b.kurtosis());
+}
+
+#[test]
+fn moments() {
+    let data = data();
+    let mut a = Moments4::new();
+    a.add_slice(&data);
+    let mut b = Moments4::new();
+    for &x in &data {
+        b.add(x);
+    }
+    assert_eq!(a.len(), b.len());
+    for p in 0..=4 {
+        assert_eq!(a.central_moment(p), b.central_moment(p));
+    }
+}
@@ -0,0 +1,100 @@
+use average::{assert_almost_eq, Autocorrelation, Estimate, Reset};
+
+/// A noisy sequence with period 4, such that the autocorrelation is
+/// negative at lag 2 and positive at lag 4.
+const SEQUENCE: &[f64] = &[
+    1., 3., 2., -1., 1.5, 2.8, 2.2, -0.9, 0.7, 3.1, 1.8, -1.2, 1.1, 2.9,
+];
+
+/// Calculate the lag-`k` autocorrelation directly from the definition.
+fn naive_autocorrelation(x: &[f64], lag: usize) -> f64 {
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    let sum_2: f64 = x.iter().map(|&x| (x - mean) * (x - mean)).sum();
+    let sum_prod: f64 = x[lag..]
+        .iter()
+        .zip(x)
+        .map(|(&a, &b)| (a - mean) * (b - mean))
+        .sum();
+    sum_prod / sum_2
+}
+
+#[test]
+fn simple() {
+    let mut a = Autocorrelation::<4>::new();
+    assert!(a.is_empty());
+    assert_eq!(a.max_lag(), 4);
+    assert!(a.autocorrelation(0).is_nan());
+    for (i, &x) in SEQUENCE.iter().enumerate() {
+        a.add(x);
+        assert_eq!(a.len(), i as u64 + 1);
+        for lag in 0..=4 {
+            if lag > i {
+                assert!(a.autocorrelation(lag).is_nan());
+            } else if i > 0 {
+                let expected = naive_autocorrelation(&SEQUENCE[..=i], lag);
+                assert_almost_eq!(a.autocorrelation(lag), expected, 1e-12);
+            }
+        }
+    }
+    assert_eq!(a.autocorrelation(0), 1.);
+    assert!(a.autocorrelation(2) < -0.5);
+    assert!(a.autocorrelation(4) > 0.5);
+    assert_eq!(a.estimate(), a.autocorrelation(1));
+
+    let b: Autocorrelation<4> = SEQUENCE.iter().collect();
+    assert_eq!(b.autocorrelation(2), a.autocorrelation(2));
+
+    a.reset();
+    assert!(a.is_empty());
+    assert!(a.autocorrelation(1).is_nan());
+}
+
+#[test]
+fn default_max_lag() {
+    let a: Autocorrelation = SEQUENCE.iter().collect();
+    assert_eq!(a.max_lag(), 10);
+    assert_eq!(a.autocorrelation(0), 1.);
+    assert_almost_eq!(
+        a.autocorrelation(10),
+        naive_autocorrelation(SEQUENCE, 10),
+        1e-12
+    );
+}
+
+#[test]
+fn constant() {
+    let a: Autocorrelation<2> = [3., 3., 3., 3.].iter().collect();
+    assert_eq!(a.mean(), 3.);
+    assert!(a.autocorrelation(0).is_nan());
+    assert!(a.autocorrelation(1).is_nan());
+}
+
+#[test]
+#[should_panic(expected = "The lag must not be larger than the maximum lag")]
+fn lag_too_large() {
+    let a: Autocorrelation<2> = SEQUENCE.iter().collect();
+    a.autocorrelation(3);
+}
+
+#[test]
+fn ar1() {
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    // For the AR(1) process `x_t = phi x_{t-1} + e_t`, the lag-`k`
+    // autocorrelation is `phi^k`.
+    let phi = 0.7;
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let mut a = Autocorrelation::<3>::new();
+    let mut x = 0.;
+    for _ in 0..100_000 {
+        x = phi * x + normal.sample(&mut rng);
+        a.add(x + 1e3);
+    }
+    assert_eq!(a.autocorrelation(0), 1.);
+    assert_almost_eq!(a.autocorrelation(1), phi, 0.01);
+    assert_almost_eq!(a.autocorrelation(2), phi * phi, 0.01);
+    assert_almost_eq!(a.autocorrelation(3), phi * phi * phi, 0.01);
+}
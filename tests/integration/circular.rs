@@ -0,0 +1,53 @@
+use core::f64::consts::PI;
+
+use average::{assert_almost_eq, CircularMean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = CircularMean::new();
+    assert!(a.is_empty());
+    assert!(a.circular_mean().is_nan());
+    assert!(a.circular_variance().is_nan());
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_almost_eq!(a.circular_mean(), 1., 1e-15);
+    assert_almost_eq!(a.circular_variance(), 0., 1e-15);
+    assert_eq!(a.estimate(), a.circular_mean());
+}
+
+#[test]
+fn wrap_around() {
+    let a: CircularMean = [10f64.to_radians(), 350f64.to_radians()].iter().collect();
+    assert_almost_eq!(a.circular_mean(), 0., 1e-14);
+    assert_almost_eq!(a.mean_resultant_length(), 10f64.to_radians().cos(), 1e-15);
+
+    let b: CircularMean = [170f64.to_radians(), 190f64.to_radians()].iter().collect();
+    assert_almost_eq!(b.circular_mean().abs(), PI, 1e-14);
+
+    let c: CircularMean = [0., 2. * PI, 4. * PI].iter().collect();
+    assert_almost_eq!(c.circular_mean(), 0., 1e-14);
+    assert_almost_eq!(c.circular_variance(), 0., 1e-15);
+}
+
+#[test]
+fn spread_out() {
+    let a: CircularMean = [0., 0.5 * PI, PI, 1.5 * PI].iter().collect();
+    assert_almost_eq!(a.circular_variance(), 1., 1e-15);
+
+    let b: CircularMean = [0., PI].iter().collect();
+    assert_almost_eq!(b.mean_resultant_length(), 0., 1e-15);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[6.1, 0.2, 5.9, 0.4, 6.2, 0.1, 3.0, 6.0];
+    let total: CircularMean = sequence.iter().collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut a: CircularMean = left.iter().collect();
+        a.merge(&right.iter().collect());
+        assert_eq!(a.len(), total.len());
+        assert_almost_eq!(a.circular_mean(), total.circular_mean(), 1e-14);
+        assert_almost_eq!(a.circular_variance(), total.circular_variance(), 1e-14);
+    }
+}
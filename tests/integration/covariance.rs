@@ -1,4 +1,4 @@
-use average::Covariance;
+use average::{assert_covariance_eq, Covariance, Merge};
 
 #[test]
 fn simple() {
@@ -73,4 +73,460 @@ fn simple() {
     assert_eq!(cov.sample_covariance(), -2.5);
     #[cfg(any(feature = "std", feature = "libm"))]
     assert_eq!(cov.pearson(), -1.);
-}
\ No newline at end of file
+}
+
+#[test]
+fn merge() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 2.), (2., -1.), (3., 4.), (-4., 5.), (5.1, 6.3), (7.3, -8.), (9., 1.),
+    ];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut cov_total = Covariance::new();
+        let mut cov_left = Covariance::new();
+        let mut cov_right = Covariance::new();
+        for &(x, y) in sequence {
+            cov_total.add(x, y);
+        }
+        for &(x, y) in left {
+            cov_left.add(x, y);
+        }
+        for &(x, y) in right {
+            cov_right.add(x, y);
+        }
+        cov_left.merge(&cov_right);
+        assert_covariance_eq!(cov_total, cov_left, 1e-13);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn partial_correlation() {
+    use average::{assert_almost_eq, partial_correlation};
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    // `x` and `y` are only correlated via the confounder `z`.
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let (mut xy, mut xz, mut yz) = (Covariance::new(), Covariance::new(), Covariance::new());
+    for _ in 0..10_000 {
+        let z = normal.sample(&mut rng);
+        let x = 2. * z + normal.sample(&mut rng);
+        let y = -z + 0.5 * normal.sample(&mut rng);
+        xy.add(x, y);
+        xz.add(x, z);
+        yz.add(y, z);
+    }
+    assert!(xy.pearson() < -0.7);
+    assert_almost_eq!(partial_correlation(&xy, &xz, &yz), 0., 0.05);
+    assert_eq!(
+        xy.partial_correlation_with(&xz, &yz),
+        partial_correlation(&xy, &xz, &yz)
+    );
+
+    // Nothing is left to correlate if `z` is a linear function of `x`.
+    let (mut xy, mut xz, mut yz) = (Covariance::new(), Covariance::new(), Covariance::new());
+    for &(x, y) in &[(1., 2.), (2., 1.), (3., 5.), (4., 3.), (5., 6.)] {
+        let z = 3. * x + 1.;
+        xy.add(x, y);
+        xz.add(x, z);
+        yz.add(y, z);
+    }
+    assert!(partial_correlation(&xy, &xz, &yz).is_nan());
+    assert!(partial_correlation(&Covariance::new(), &xz, &yz).is_nan());
+}
+
+#[test]
+fn moving_covariance() {
+    use average::{assert_covariance_eq, MovingCovariance};
+
+    const W: usize = 4;
+    let sequence: &[(f64, f64)] = &[
+        (1., 2.), (2., -1.), (3., 4.), (-4., 5.), (5.1, 6.3), (7.3, -8.), (9., 1.), (1e3, 2e3),
+        (1., 1.), (2., 3.), (3., 2.), (4., 5.),
+    ];
+    let mut a = MovingCovariance::<W>::new();
+    assert!(a.is_empty());
+    assert!(a.covariance().is_nan());
+    for (i, &(x, y)) in sequence.iter().enumerate() {
+        a.add(x, y);
+        assert_eq!(a.is_full(), i + 1 >= W);
+        let mut expected = Covariance::new();
+        for &(x, y) in &sequence[(i + 1).saturating_sub(W)..=i] {
+            expected.add(x, y);
+        }
+        assert_eq!(a.len(), expected.len());
+        assert_covariance_eq!(*a.estimator(), expected, 1e-9);
+    }
+    // The large pair leaves rounding errors in the window.
+    average::assert_almost_eq!(a.covariance(), 5.5 / 3., 1e-9);
+    #[cfg(any(feature = "std", feature = "libm"))]
+    average::assert_almost_eq!(a.pearson(), 5.5 / f64::sqrt(5. * 8.75), 1e-9);
+}
+
+#[test]
+fn update_streaming() {
+    let mut a = Covariance::new();
+    for &(x, y) in &[(1., 5.), (2., 4.), (3., 3.)] {
+        a.add(x, y);
+    }
+    a.update_streaming((1., 5.), (4., 2.));
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean_x(), 3.);
+    assert_eq!(a.mean_y(), 3.);
+    assert_eq!(a.sample_variance_x(), 1.);
+    assert_eq!(a.sample_covariance(), -1.);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn z_score_and_mahalanobis_distance() {
+    use average::assert_almost_eq;
+
+    let mut cov = Covariance::new();
+    assert!(cov.z_score_x(1.).is_nan());
+    assert!(cov.mahalanobis_distance(1., 1.).is_nan());
+    for &(x, y) in &[(1., 2.), (2., 1.), (3., 5.), (4., 3.), (5., 4.)] {
+        cov.add(x, y);
+    }
+    let (sx, sy) = (cov.sample_variance_x().sqrt(), cov.sample_variance_y().sqrt());
+    assert_eq!(cov.z_score_x(3.), 0.);
+    assert_almost_eq!(cov.z_score_x(3. + 2. * sx), 2., 1e-14);
+    assert_almost_eq!(cov.z_score_y(3. - sy), -1., 1e-14);
+
+    assert_eq!(cov.mahalanobis_distance(3., 3.), 0.);
+    // Points one standard deviation away in both variables are closer along
+    // the correlation than against it.
+    let r = cov.pearson();
+    assert_eq!(r, 0.6);
+    assert_almost_eq!(
+        cov.mahalanobis_distance(3. + sx, 3. + sy),
+        (2. / (1. + r)).sqrt(),
+        1e-14
+    );
+    assert_almost_eq!(
+        cov.mahalanobis_distance(3. + sx, 3. - sy),
+        (2. / (1. - r)).sqrt(),
+        1e-14
+    );
+    // Without correlation, it is the Euclidean norm of the z-scores.
+    assert_almost_eq!(
+        cov.mahalanobis_distance(3. + sx, 3.),
+        (1. / (1. - r * r)).sqrt(),
+        1e-14
+    );
+
+    // Singular covariance matrix.
+    let mut singular = Covariance::new();
+    for &x in &[1., 2., 3.] {
+        singular.add(x, 2. * x);
+    }
+    assert!(singular.mahalanobis_distance(1., 1.).is_nan());
+}
+
+#[test]
+fn neg() {
+    let c: Covariance = [(1., 5.), (2., 4.), (3., 7.), (4., 6.)].iter().collect();
+    let mut d = -c.clone();
+    assert_eq!(d.mean_x(), -c.mean_x());
+    assert_eq!(d.mean_y(), -c.mean_y());
+    assert_eq!(d.sample_variance_x(), c.sample_variance_x());
+    assert_eq!(d.sample_variance_y(), c.sample_variance_y());
+    assert_eq!(d.sample_covariance(), c.sample_covariance());
+    d.add(-5., -8.);
+    let expected: Covariance = [(-1., -5.), (-2., -4.), (-3., -7.), (-4., -6.), (-5., -8.)]
+        .iter()
+        .collect();
+    assert_covariance_eq!(d, expected, 1e-14);
+}
+
+#[test]
+fn partial_eq() {
+    let data = [(1., 5.), (2., 4.), (3., 7.), (4., 6.)];
+    let a: Covariance = data.iter().collect();
+    let b: Covariance = data.iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(Covariance::new(), Covariance::default());
+
+    let c: Covariance = data[1..].iter().collect();
+    assert_ne!(a, c);
+    let d: Covariance = [(1., 5.), (2., 4.), (3., 7.), (4., 6.5)].iter().collect();
+    assert_ne!(a, d);
+
+    let nan: Covariance = [(1., f64::NAN)].iter().collect();
+    assert_ne!(nan, nan.clone());
+}
+
+#[test]
+fn add_from_slice() {
+    use average::MismatchedSliceLengths;
+
+    let xs = [1., 2., 3., 4.];
+    let ys = [5., 4., 7., 6.];
+    let mut a = Covariance::new();
+    a.add_from_slice(&xs, &ys).unwrap();
+    let mut b = Covariance::new();
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        b.add(x, y);
+    }
+    assert_eq!(a, b);
+    let mut c = Covariance::new();
+    c.extend_from_slices(&xs, &ys).unwrap();
+    assert_eq!(a, c);
+
+    let err = a.add_from_slice(&xs, &ys[1..]).unwrap_err();
+    assert_eq!(err, MismatchedSliceLengths { expected: 4, got: 3 });
+    assert_eq!(a, b);
+    let message = format!("{}", err);
+    assert!(message.contains('4') && message.contains('3'), "{}", message);
+    #[cfg(feature = "std")]
+    {
+        let err: Box<dyn std::error::Error> = err.into();
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[test]
+fn linear_regression_anscombe() {
+    use average::assert_almost_eq;
+
+    // Anscombe's quartet: four datasets with (almost) identical regression
+    // lines.
+    let x_1 = [10., 8., 13., 9., 11., 14., 6., 4., 12., 7., 5.];
+    let x_4 = [8., 8., 8., 8., 8., 8., 8., 19., 8., 8., 8.];
+    let datasets: [(&[f64], &[f64]); 4] = [
+        (&x_1, &[8.04, 6.95, 7.58, 8.81, 8.33, 9.96, 7.24, 4.26, 10.84, 4.82, 5.68]),
+        (&x_1, &[9.14, 8.14, 8.74, 8.77, 9.26, 8.10, 6.13, 3.10, 9.13, 7.26, 4.74]),
+        (&x_1, &[7.46, 6.77, 12.74, 7.11, 7.81, 8.84, 6.08, 5.39, 8.15, 6.42, 5.73]),
+        (&x_4, &[6.58, 5.76, 7.71, 8.84, 8.47, 7.04, 5.25, 12.50, 5.56, 7.91, 6.89]),
+    ];
+    for &(xs, ys) in &datasets {
+        let mut cov = Covariance::new();
+        cov.add_from_slice(xs, ys).unwrap();
+        assert_almost_eq!(cov.slope(), 0.5, 1e-3);
+        assert_almost_eq!(cov.intercept(), 3.0, 3e-3);
+        assert_almost_eq!(cov.r_squared(), 0.6665, 1e-3);
+        #[cfg(any(feature = "std", feature = "libm"))]
+        {
+            assert_almost_eq!(cov.r_squared(), cov.pearson() * cov.pearson(), 1e-14);
+            assert_almost_eq!(cov.standard_error_of_slope(), 0.1179, 1e-3);
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn linear_regression_slope_test() {
+    use average::assert_almost_eq;
+
+    // The first dataset of Anscombe's quartet.
+    let mut cov = Covariance::new();
+    cov.add_from_slice(
+        &[10., 8., 13., 9., 11., 14., 6., 4., 12., 7., 5.],
+        &[8.04, 6.95, 7.58, 8.81, 8.33, 9.96, 7.24, 4.26, 10.84, 4.82, 5.68],
+    )
+    .unwrap();
+    assert_eq!(cov.degrees_of_freedom(), 9);
+    assert_almost_eq!(cov.t_statistic_slope(), 4.2414552888928325, 1e-12);
+    let (lower, upper) = cov.confidence_interval_slope(0.95);
+    assert_almost_eq!(lower, 0.23337, 1e-5);
+    assert_almost_eq!(upper, 0.76681, 1e-5);
+
+    let mut cov = Covariance::new();
+    assert_eq!(cov.degrees_of_freedom(), 0);
+    cov.add(1., 2.);
+    cov.add(2., 4.);
+    assert_eq!(cov.degrees_of_freedom(), 0);
+    assert!(cov.t_statistic_slope().is_nan());
+    let (lower, upper) = cov.confidence_interval_slope(0.95);
+    assert!(lower.is_nan() && upper.is_nan());
+
+    let mut cov = Covariance::new();
+    for &y in &[1., 2., 3.] {
+        cov.add(1., y);
+    }
+    assert_eq!(cov.degrees_of_freedom(), 1);
+    assert!(cov.t_statistic_slope().is_nan());
+    let (lower, upper) = cov.confidence_interval_slope(0.95);
+    assert!(lower.is_nan() && upper.is_nan());
+
+    // Constant `y` has a zero slope, but no confidence interval.
+    let mut cov = Covariance::new();
+    for &x in &[1., 2., 3., 4.] {
+        cov.add(x, 5.);
+    }
+    assert_eq!(cov.slope(), 0.);
+    assert!(cov.t_statistic_slope().is_nan());
+    let (lower, upper) = cov.confidence_interval_slope(0.95);
+    assert!(lower.is_nan() && upper.is_nan());
+}
+
+#[test]
+fn linear_regression_degenerate() {
+    let mut cov = Covariance::new();
+    assert!(cov.slope().is_nan());
+    assert!(cov.intercept().is_nan());
+    assert!(cov.r_squared().is_nan());
+    cov.add(1., 2.);
+    assert!(cov.slope().is_nan());
+    assert!(cov.r_squared().is_nan());
+    cov.add(1., 3.);
+    assert!(cov.slope().is_nan());
+    assert!(cov.intercept().is_nan());
+    assert!(cov.r_squared().is_nan());
+
+    let mut cov = Covariance::new();
+    cov.add(1., 2.);
+    cov.add(2., 4.);
+    assert_eq!(cov.slope(), 2.);
+    assert_eq!(cov.intercept(), 0.);
+    assert_eq!(cov.r_squared(), 1.);
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert!(cov.standard_error_of_slope().is_nan());
+}
+
+#[test]
+fn linear_regression_prediction() {
+    use average::assert_almost_eq;
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    let noise = rand_distr::Normal::new(0., 0.5).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let cov: Covariance = (0..100)
+        .map(|i| {
+            let x = f64::from(i) / 10.;
+            (x, 2. * x + 1. + noise.sample(&mut rng))
+        })
+        .collect();
+
+    assert_almost_eq!(cov.slope(), 2., 0.1);
+    assert_almost_eq!(cov.intercept(), 1., 0.3);
+    assert_almost_eq!(cov.prediction(0.), cov.intercept(), 1e-14);
+    assert_almost_eq!(
+        cov.residual_variance(),
+        (1. - cov.r_squared()) * cov.population_variance_y(),
+        1e-12
+    );
+
+    #[cfg(any(feature = "std", feature = "libm"))]
+    {
+        assert_almost_eq!(cov.residual_std(), 0.5, 0.1);
+
+        // Standard error of the regression line at `x`.
+        let x = 15.;
+        let n = cov.len() as f64;
+        let sum_x_2 = cov.population_variance_x() * n;
+        let dx = x - cov.mean_x();
+        let error = cov.residual_std() * (1. / n + dx * dx / sum_x_2).sqrt();
+        assert!((cov.prediction(x) - (2. * x + 1.)).abs() < 2. * error);
+    }
+
+    let mut cov = Covariance::new();
+    assert!(cov.prediction(1.).is_nan());
+    assert!(cov.residual_variance().is_nan());
+    cov.add(1., 3.);
+    assert!(cov.prediction(1.).is_nan());
+    assert!(cov.residual_variance().is_nan());
+    cov.add(2., 3.);
+    assert_eq!(cov.prediction(5.), 3.);
+    assert_eq!(cov.residual_variance(), 0.);
+}
+
+#[test]
+fn weighted_covariance() {
+    use average::{assert_almost_eq, WeightedCovariance};
+
+    let a = WeightedCovariance::new();
+    assert!(a.is_empty());
+    assert_eq!(a.sum_weights(), 0.);
+    assert!(a.mean_x().is_nan());
+    assert!(a.weighted_covariance().is_nan());
+    assert!(a.weighted_variance_y().is_nan());
+
+    let pairs = [(1., 2.), (3., 5.), (5., 6.), (7., 7.), (9., 10.), (2., -1.)];
+    let cov: Covariance = pairs.iter().collect();
+    let weighted: WeightedCovariance = pairs.iter().map(|&(x, y)| (x, y, 0.5)).collect();
+    assert_eq!(weighted.sum_weights(), 3.);
+    assert_almost_eq!(weighted.mean_x(), cov.mean_x(), 1e-14);
+    assert_almost_eq!(weighted.mean_y(), cov.mean_y(), 1e-14);
+    assert_almost_eq!(weighted.weighted_variance_x(), cov.population_variance_x(), 1e-14);
+    assert_almost_eq!(weighted.weighted_variance_y(), cov.population_variance_y(), 1e-14);
+    assert_almost_eq!(weighted.weighted_covariance(), cov.population_covariance(), 1e-14);
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert_almost_eq!(weighted.weighted_pearson(), cov.pearson(), 1e-14);
+
+    // Integer weights are equivalent to repeated samples.
+    let weighted: WeightedCovariance = [(1., 2., 1.), (3., 5., 3.), (5., 4., 2.)].iter().collect();
+    let repeated: Covariance =
+        [(1., 2.), (3., 5.), (3., 5.), (3., 5.), (5., 4.), (5., 4.)].iter().collect();
+    assert_almost_eq!(weighted.weighted_covariance(), repeated.population_covariance(), 1e-14);
+    assert_almost_eq!(weighted.weighted_variance_x(), repeated.population_variance_x(), 1e-14);
+}
+
+#[test]
+fn weighted_covariance_merge() {
+    use average::{assert_almost_eq, Merge, WeightedCovariance};
+
+    let sequence: Vec<(f64, f64, f64)> = (0..20)
+        .map(|i| {
+            let x = f64::from(i);
+            (x, (x * 0.7).sin() + 0.3 * x, 0.1 + f64::from(i % 3))
+        })
+        .collect();
+    let total: WeightedCovariance = sequence.iter().collect();
+    for mid in 0..=sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut merged: WeightedCovariance = left.iter().collect();
+        let mut right: WeightedCovariance = right.iter().collect();
+        merged.merge(&right);
+        assert_almost_eq!(merged.sum_weights(), total.sum_weights(), 1e-13);
+        assert_almost_eq!(merged.mean_x(), total.mean_x(), 1e-13);
+        assert_almost_eq!(merged.mean_y(), total.mean_y(), 1e-13);
+        assert_almost_eq!(merged.weighted_variance_x(), total.weighted_variance_x(), 1e-13);
+        assert_almost_eq!(merged.weighted_variance_y(), total.weighted_variance_y(), 1e-13);
+        assert_almost_eq!(merged.weighted_covariance(), total.weighted_covariance(), 1e-13);
+
+        right.extend(left.iter());
+        assert_almost_eq!(right.weighted_covariance(), total.weighted_covariance(), 1e-13);
+    }
+}
+
+#[test]
+fn linear_regression() {
+    use average::{assert_almost_eq, LinearRegression};
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    let fit = LinearRegression::new();
+    assert!(fit.is_empty());
+    assert!(fit.slope().is_nan());
+    assert!(fit.predict(1.).is_nan());
+
+    let noise = rand_distr::Normal::new(0., 0.01).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(1);
+    let data: Vec<(f64, f64)> = (0..50)
+        .map(|i| {
+            let x = f64::from(i) / 5.;
+            (x, 3. * x - 2. + noise.sample(&mut rng))
+        })
+        .collect();
+    let fit: LinearRegression = data.iter().collect();
+    assert_eq!(fit.len(), 50);
+    assert_almost_eq!(fit.slope(), 3., 1e-2);
+    assert_almost_eq!(fit.intercept(), -2., 1e-2);
+    assert_almost_eq!(fit.predict(10.), 28., 1e-1);
+    assert!(fit.r_squared() > 0.999);
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert_almost_eq!(fit.residual_std(), 0.01, 5e-3);
+    assert_eq!(fit.covariance().len(), 50);
+
+    let (left, right) = data.split_at(20);
+    let mut merged: LinearRegression = left.iter().collect();
+    merged.merge(&right.iter().collect());
+    assert_almost_eq!(merged.slope(), fit.slope(), 1e-12);
+    let mut extended = LinearRegression::default();
+    extended.extend(data.iter().cloned());
+    assert_eq!(extended, fit);
+}
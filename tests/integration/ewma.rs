@@ -0,0 +1,74 @@
+use average::{assert_almost_eq, Estimate, Ewma, EwmaVariance};
+
+#[test]
+fn trivial() {
+    let mut a = Ewma::new(0.2);
+    assert!(a.is_empty());
+    assert!(a.value().is_nan());
+    a.update(3.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.value(), 3.);
+    assert_eq!(a.estimate(), 3.);
+
+    let mut v = EwmaVariance::new(0.2);
+    assert!(v.mean().is_nan());
+    assert!(v.variance().is_nan());
+    v.add(3.);
+    assert_eq!(v.mean(), 3.);
+    assert_eq!(v.variance(), 0.);
+}
+
+#[test]
+fn constant() {
+    let a: Ewma = core::iter::repeat(4.2).take(100).collect();
+    assert_eq!(a.alpha(), 0.1);
+    assert_eq!(a.len(), 100);
+    assert_eq!(a.value(), 4.2);
+
+    let v: EwmaVariance = core::iter::repeat(4.2).take(100).collect();
+    assert_eq!(v.mean(), 4.2);
+    assert_eq!(v.variance(), 0.);
+}
+
+#[test]
+fn simple() {
+    let mut a = Ewma::new(0.5);
+    a.extend(&[1., 3., 6.]);
+    // s = 1, 0.5 * 3 + 0.5 * 1 = 2, 0.5 * 6 + 0.5 * 2 = 4
+    assert_eq!(a.value(), 4.);
+
+    let mut v = EwmaVariance::new(0.5);
+    v.extend(&[1., 3., 6.]);
+    assert_eq!(v.mean(), 4.);
+    // v = 0, 0.5 * (0 + 0.5 * 2^2) = 1, 0.5 * (1 + 0.5 * 4^2) = 4.5
+    assert_eq!(v.variance(), 4.5);
+    assert_eq!(v.estimate(), 4.5);
+}
+
+#[test]
+fn step() {
+    // After a step, the average approaches the new level geometrically.
+    let mut a = Ewma::new(0.1);
+    a.add(0.);
+    for n in 1..=20 {
+        a.add(1.);
+        assert_almost_eq!(a.value(), 1. - 0.9f64.powi(n), 1e-14);
+    }
+}
+
+#[test]
+#[should_panic(expected = "The smoothing factor has to be in (0, 1]")]
+fn invalid_alpha() {
+    Ewma::new(0.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn with_serde() {
+    let mut a = EwmaVariance::new(0.5);
+    a.extend(&[1., 3., 6.]);
+    let b: EwmaVariance = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.mean(), b.mean());
+    assert_eq!(a.variance(), b.variance());
+    assert_eq!(a.len(), b.len());
+}
@@ -0,0 +1,61 @@
+use rand::SeedableRng;
+use rand_distr::Distribution;
+
+use average::{assert_almost_eq, Estimate, GeometricMean, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = GeometricMean::new();
+    assert!(a.is_empty());
+    assert!(a.geometric_mean().is_nan());
+    a.add(2.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.geometric_mean(), 2.);
+    a.add(8.);
+    assert_almost_eq!(a.geometric_mean(), 4., 1e-15);
+    assert_eq!(a.estimate(), a.geometric_mean());
+}
+
+#[test]
+fn zero_and_negative() {
+    let a: GeometricMean = [1., 0., 3.].iter().collect();
+    assert!(a.geometric_mean().is_nan());
+    let b: GeometricMean = [1., -2., 3.].iter().collect();
+    assert!(b.geometric_mean().is_nan());
+}
+
+#[test]
+fn log_normal() {
+    let distribution = rand_distr::LogNormal::new(2., 3.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let samples: Vec<f64> = (0..10_000).map(|_| distribution.sample(&mut rng)).collect();
+    let a: GeometricMean = samples.iter().collect();
+    let reference = (samples.iter().map(|x| x.ln()).sum::<f64>() / samples.len() as f64).exp();
+    assert_eq!(a.len(), 10_000);
+    assert_almost_eq!(a.geometric_mean() / reference, 1., 1e-12);
+    assert_almost_eq!(a.geometric_mean().ln(), 2., 0.1);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: GeometricMean = sequence.iter().collect();
+        let mut merged: GeometricMean = left.iter().collect();
+        let right: GeometricMean = right.iter().collect();
+        merged.merge(&right);
+        assert_eq!(merged.len(), total.len());
+        assert_almost_eq!(merged.geometric_mean(), total.geometric_mean(), 1e-14);
+    }
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: GeometricMean = [1., 10., 100.].iter().collect();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: GeometricMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.len(), 3);
+    assert_eq!(c.geometric_mean(), a.geometric_mean());
+}
@@ -0,0 +1,43 @@
+use average::{assert_almost_eq, Estimate, HarmonicMean, Merge};
+
+fn reference(data: &[f64]) -> f64 {
+    data.len() as f64 / data.iter().map(|x| 1. / x).sum::<f64>()
+}
+
+#[test]
+fn trivial() {
+    let mut a = HarmonicMean::new();
+    assert!(a.is_empty());
+    assert!(a.harmonic_mean().is_nan());
+    a.add(2.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.harmonic_mean(), 2.);
+    assert_eq!(a.estimate(), 2.);
+}
+
+#[test]
+fn simple() {
+    for n in 1..20 {
+        let data: Vec<f64> = (1..=n).map(f64::from).collect();
+        let a: HarmonicMean = data.iter().collect();
+        assert_eq!(a.len(), u64::from(n as u32));
+        assert_almost_eq!(a.harmonic_mean(), reference(&data), 1e-14);
+    }
+    let a: HarmonicMean = [40., 60.].iter().collect();
+    assert_almost_eq!(a.harmonic_mean(), 48., 1e-13);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 0..=sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: HarmonicMean = sequence.iter().collect();
+        let mut merged: HarmonicMean = left.iter().collect();
+        let right: HarmonicMean = right.iter().collect();
+        merged.merge(&right);
+        assert_eq!(merged.len(), total.len());
+        assert_almost_eq!(merged.harmonic_mean(), total.harmonic_mean(), 1e-14);
+        assert_almost_eq!(merged.harmonic_mean(), reference(sequence), 1e-14);
+    }
+}
@@ -3,10 +3,13 @@ use rand::SeedableRng;
 use rand_distr::Distribution;
 
 use average::{assert_almost_eq, define_histogram, Histogram, Merge};
-use average::{InvalidRangeError, SampleOutOfRangeError};
+use average::{FromDataError, InvalidRangeError, SampleOutOfRangeError};
 
 define_histogram!(hist10, 10);
 define_histogram!(hist100, 100);
+define_histogram!(hist10_u32, 10, count_type: u32);
+define_histogram!(hist2, 2);
+define_histogram!(hist3, 3);
 
 use hist10::Histogram as Histogram10;
 
@@ -19,6 +22,23 @@ fn with_const_width() {
     assert_eq!(h.bins(), &[10, 10, 10, 10, 10, 10, 10, 10, 10, 10]);
 }
 
+#[test]
+fn count_type() {
+    let mut h = hist10_u32::Histogram::with_const_width(-30., 70.);
+    for i in -30..70 {
+        h.add(f64::from(i)).unwrap();
+    }
+    h.add(-30.).unwrap();
+    assert_eq!(h.bins_raw(), &[11u32, 10, 10, 10, 10, 10, 10, 10, 10, 10]);
+    let bins: Vec<u64> = h.bins().collect();
+    assert_eq!(&bins, &[11, 10, 10, 10, 10, 10, 10, 10, 10, 10]);
+    let counts: Vec<u64> = h.iter().map(|(_, count)| count).collect();
+    assert_eq!(counts, bins);
+
+    h *= 2;
+    assert_eq!(h.bins_raw(), &[22u32, 20, 20, 20, 20, 20, 20, 20, 20, 20]);
+}
+
 #[test]
 fn from_ranges() {
     let mut h = Histogram10::from_ranges(
@@ -62,6 +82,211 @@ fn iter() {
     );
 }
 
+#[test]
+fn fold() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    h.add_slice(&[5., 15., 15., 35., 99.]);
+    let total = h.fold(0u64, |acc, (_, count)| acc + count);
+    assert_eq!(total, h.bins().iter().sum::<u64>());
+    let upper = h.fold(f64::NEG_INFINITY, |acc, ((_, b), _)| acc.max(b));
+    assert_eq!(upper, 100.);
+
+    let mut nonempty = 0;
+    h.for_each(|_, count| {
+        if count > 0 {
+            nonempty += 1;
+        }
+    });
+    assert_eq!(nonempty, 4);
+}
+
+#[test]
+fn iter_mut() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    h.add_slice(&[5., 15., 15., 35., 99.]);
+    let mut expected = h.clone();
+    expected *= 2;
+    for (_, count) in h.iter_mut() {
+        *count *= 2;
+    }
+    assert_eq!(h.bins(), expected.bins());
+
+    for ((a, _), count) in h.iter_mut() {
+        if a >= 10. {
+            *count = 0;
+        }
+    }
+    assert_eq!(h.bins(), &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    for (_, count) in h.iter_mut() {
+        *count = 0;
+    }
+    assert!(h.bins().iter().all(|&count| count == 0));
+}
+
+#[test]
+fn index() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for &x in &[1.5, 3.2, 3.7, 9.9] {
+        h.add(x).unwrap();
+    }
+    assert_eq!(h[3], h.bins()[3]);
+    assert_eq!(h[3], 2);
+    h[3] += 5;
+    h[0] = 1;
+    assert_eq!(h.bins(), &[1, 1, 0, 7, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+#[should_panic(expected = "histogram bin index 10 out of range [0, 10)")]
+fn index_out_of_range() {
+    let h = Histogram10::with_const_width(0., 10.);
+    let _ = h[10];
+}
+
+#[test]
+fn smooth() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for _ in 0..9 {
+        h.add(4.5).unwrap();
+    }
+    let s = h.smooth(1);
+    assert_eq!(s.bins(), &[0, 0, 0, 3, 3, 3, 0, 0, 0, 0]);
+    assert_eq!(s.ranges(), h.ranges());
+    assert_eq!(h.smooth(0).bins(), h.bins());
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for i in 0..100 {
+        h.add(f64::from(i) / 10.).unwrap();
+    }
+    assert_eq!(h.smooth(2).bins(), &[10; 10]);
+    assert_eq!(h.smooth(100).bins(), &[10; 10]);
+}
+
+#[test]
+fn normalize_to_total() {
+    let mut h = hist3::Histogram::with_const_width(0., 3.);
+    h[0] = 1;
+    h[1] = 2;
+    h[2] = 3;
+    assert_eq!(h.normalize_to_total(12).bins(), &[2, 4, 6]);
+    assert_eq!(h.normalize_to_total(6).bins(), &[1, 2, 3]);
+    // The counts are rounded to the nearest integers where possible.
+    assert_eq!(h.normalize_to_total(10).bins(), &[2, 3, 5]);
+    assert_eq!(h.normalize_to_total(5).bins(), &[1, 2, 2]);
+    // 1.5 and 4.5 have equal fractional parts, the more populated bin wins.
+    let mut tie = hist3::Histogram::with_const_width(0., 3.);
+    tie[0] = 1;
+    tie[1] = 3;
+    assert_eq!(tie.normalize_to_total(6).bins(), &[1, 5, 0]);
+    assert_eq!(tie.normalize_to_total(2).bins(), &[0, 2, 0]);
+    assert_eq!(h.normalize_to_total(0).bins(), &[0, 0, 0]);
+    for new_total in 0..100 {
+        let total: u64 = h.normalize_to_total(new_total).bins().iter().sum();
+        assert_eq!(total, new_total);
+    }
+
+    let mut h = hist3::Histogram::with_const_width(0., 3.);
+    h[0] = 2;
+    h[1] = 2;
+    assert_eq!(h.normalize_to_total(3).bins(), &[2, 1, 0]);
+
+    let empty = hist3::Histogram::with_const_width(0., 3.);
+    assert_eq!(empty.normalize_to_total(10).bins(), &[0, 0, 0]);
+
+    let mut h = hist10_u32::Histogram::with_const_width(0., 10.);
+    h.add(0.5).unwrap();
+    h.add(9.5).unwrap();
+    let bins: Vec<u64> = h.normalize_to_total(3).bins().collect();
+    assert_eq!(bins, [2, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn sample() {
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let mut h = hist100::Histogram::with_const_width(-3., 3.);
+    assert!(h.sample(&mut rng).is_none());
+    for _ in 0..100_000 {
+        let _ = h.add(normal.sample(&mut rng));
+    }
+
+    let mut resampled = hist100::Histogram::with_const_width(-3., 3.);
+    for _ in 0..100_000 {
+        let x = h.sample(&mut rng).unwrap();
+        assert!((-3. ..3.).contains(&x));
+        resampled.add(x).unwrap();
+    }
+    let total = h.bins().iter().sum::<u64>() as f64;
+    let kl_divergence: f64 = h
+        .bins()
+        .iter()
+        .zip(resampled.bins())
+        .filter(|&(&p, _)| p > 0)
+        .map(|(&p, &q)| {
+            let p = p as f64 / total;
+            let q = q as f64 / 100_000.;
+            p * (p / q).ln()
+        })
+        .sum();
+    assert!(kl_divergence < 0.01, "{}", kl_divergence);
+}
+
+#[test]
+fn binary_bar_chart() {
+    let mut h = hist3::Histogram::with_const_width(0., 30.);
+    for &(x, n) in &[(5., 80), (15., 40), (25., 5)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    assert_eq!(
+        format!("{:.8b}", h),
+        "[0.0, 10.0): ████████ (80)\n[10.0, 20.0): ████ (40)\n[20.0, 30.0): ▌ (5)"
+    );
+    let default = format!("{:b}", h);
+    let bars: Vec<usize> = default
+        .lines()
+        .map(|line| line.chars().filter(|&c| c == '█').count())
+        .collect();
+    assert_eq!(bars, [40, 20, 2]);
+
+    let empty = hist2::Histogram::with_const_width(0., 2.);
+    assert_eq!(format!("{:b}", empty), "[0.0, 1.0): (0)\n[1.0, 2.0): (0)");
+}
+
+#[test]
+fn display_table() {
+    let mut h = hist3::Histogram::with_const_width(0., 30.);
+    h[0] = 1;
+    h[1] = 5;
+    h[2] = 10;
+    assert_eq!(
+        format!("{:.10}", h),
+        "[ 0, 10) |  1 | #\n\
+         [10, 20) |  5 | #####\n\
+         [20, 30) | 10 | ##########"
+    );
+    let table = format!("{}", h);
+    let rows: Vec<&str> = table.lines().collect();
+    assert_eq!(rows.len(), 3);
+    for (row, count) in rows.iter().zip(&[1, 5, 10]) {
+        assert_eq!(row.split('|').nth(1).unwrap().trim(), count.to_string());
+    }
+    let bars: Vec<usize> = rows.iter().map(|row| row.matches('#').count()).collect();
+    assert_eq!(bars, [4, 20, 40]);
+
+    let inf = f64::INFINITY;
+    let mut h = hist3::Histogram::from_ranges([-inf, 0., 1.5, inf].iter().cloned()).unwrap();
+    h.add(-1.).unwrap();
+    assert_eq!(
+        format!("{}", h),
+        "[-inf,   0) | 1 | ########################################\n\
+         [   0, 1.5) | 0 |\n\
+         [ 1.5, inf) | 0 |"
+    );
+}
+
 #[test]
 fn normalized_bins() {
     let inf = f64::INFINITY;
@@ -128,6 +353,278 @@ fn from_ranges_infinity() {
     assert_eq!(h.bins(), &[2, 0, 0, 0, 0, 1, 0, 1, 0, 2]);
 }
 
+#[test]
+fn trim() {
+    let mut h = hist100::Histogram::with_const_width(0., 100.);
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.histogram_mean(), 50.);
+    assert_eq!(h.histogram_variance(), 833.25);
+
+    let t = h.trim(0.05, 0.05);
+    assert_eq!(t.bins().iter().sum::<u64>(), 90);
+    assert!(t.bins()[..5].iter().all(|&c| c == 0));
+    assert!(t.bins()[5..95].iter().all(|&c| c == 1));
+    assert!(t.bins()[95..].iter().all(|&c| c == 0));
+    assert_eq!(h.trim(0., 0.).bins(), h.bins());
+    assert_eq!(h.trim(0.5, 0.5).bins().iter().sum::<u64>(), 0);
+
+    // One standard deviation is about 28.9.
+    let t = h.trim_by_std(1.);
+    assert_eq!(t.bins().iter().sum::<u64>(), 58);
+    assert_eq!(t.bins()[21], 1);
+    assert_eq!(t.bins()[20], 0);
+    assert_eq!(h.trim_by_std(f64::INFINITY).bins(), h.bins());
+
+    let empty = Histogram10::with_const_width(0., 1.);
+    assert!(empty.histogram_mean().is_nan());
+    assert_eq!(empty.trim(0.1, 0.1).bins(), empty.bins());
+    assert_eq!(empty.trim_by_std(1.).bins(), empty.bins());
+}
+
+#[test]
+fn max_min_bin() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert_eq!(h.max_bin(), None);
+    assert_eq!(h.min_bin(), None);
+    assert_eq!(h.max_bin_count(), None);
+    assert_eq!(h.min_nonzero_bin_count(), None);
+    for &(x, n) in &[(1.5, 2), (3.5, 5), (6.5, 1), (8.5, 5), (9.5, 1)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    assert_eq!(h.max_bin(), Some(((3., 4.), 5)));
+    assert_eq!(h.min_bin(), Some(((6., 7.), 1)));
+    assert_eq!(h.max_bin_count(), Some(5));
+    assert_eq!(h.min_nonzero_bin_count(), Some(1));
+}
+
+#[test]
+fn mode() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert!(h.mode_center().is_nan());
+    let (a, b) = h.mode_range();
+    assert!(a.is_nan() && b.is_nan());
+    for &(x, n) in &[(1.5, 2), (3.5, 7), (6.5, 1), (8.5, 4)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    assert_eq!(h.mode_range(), (3., 4.));
+    assert_eq!(h.mode_center(), 3.5);
+    for _ in 0..3 {
+        h.add(8.5).unwrap();
+    }
+    // On ties, the leftmost bin is used.
+    assert_eq!(h.mode_range(), (3., 4.));
+    h.add(8.5).unwrap();
+    assert_eq!(h.mode_center(), 8.5);
+}
+
+#[test]
+fn percentile_of_score() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert!(h.percentile_of_score(50.).is_nan());
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.percentile_of_score(-1.), 0.);
+    assert_eq!(h.percentile_of_score(0.), 0.);
+    assert_eq!(h.percentile_of_score(30.), 0.3);
+    assert_almost_eq!(h.percentile_of_score(35.), 0.35, 1e-15);
+    assert_eq!(h.percentile_of_score(100.), 1.);
+    assert_eq!(h.percentile_of_score(1000.), 1.);
+    assert!(h.percentile_of_score(f64::NAN).is_nan());
+}
+
+#[test]
+fn total_count_fraction_below_find_percentile() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.total_count(), 0);
+    assert!(h.is_empty());
+    assert!(h.fraction_below(50.).is_nan());
+    assert!(h.find_percentile(0.5).is_nan());
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.total_count(), 100);
+    assert!(!h.is_empty());
+
+    assert_eq!(h.fraction_below(-1.), 0.);
+    assert_eq!(h.fraction_below(0.), 0.1);
+    assert_eq!(h.fraction_below(35.), 0.4);
+    assert_eq!(h.fraction_below(40.), 0.5);
+    assert_eq!(h.fraction_below(99.), 1.);
+    assert_eq!(h.fraction_below(1000.), 1.);
+    assert!(h.fraction_below(f64::NAN).is_nan());
+
+    assert_eq!(h.find_percentile(0.), 0.);
+    assert_eq!(h.find_percentile(0.3), 30.);
+    assert_eq!(h.find_percentile(0.5), 50.);
+    assert_almost_eq!(h.find_percentile(0.35), 35., 1e-12);
+    assert_almost_eq!(h.find_percentile(0.999), 99.9, 1e-12);
+    assert_eq!(h.find_percentile(1.), 100.);
+    assert_eq!(h.find_percentile(-0.1), 0.);
+    assert_eq!(h.find_percentile(1.1), 100.);
+    assert!(h.find_percentile(f64::NAN).is_nan());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn entropy_kl_divergence() {
+    let mut uniform = hist2::Histogram::with_const_width(0., 2.);
+    assert!(uniform.entropy().is_nan());
+    uniform.add(0.5).unwrap();
+    uniform.add(1.5).unwrap();
+    assert_almost_eq!(uniform.entropy(), core::f64::consts::LN_2, 1e-15);
+    assert_eq!(uniform.kl_divergence(&uniform), 0.);
+
+    let mut skewed = hist2::Histogram::with_const_width(0., 2.);
+    assert!(skewed.kl_divergence(&uniform).is_nan());
+    assert!(uniform.kl_divergence(&skewed).is_nan());
+    for _ in 0..3 {
+        skewed.add(0.5).unwrap();
+    }
+    assert_eq!(skewed.entropy(), 0.);
+    assert_eq!(skewed.kl_divergence(&uniform), core::f64::consts::LN_2);
+    assert_eq!(uniform.kl_divergence(&skewed), f64::INFINITY);
+    skewed.add(1.5).unwrap();
+    let expected_entropy = -(0.75 * 0.75f64.ln() + 0.25 * 0.25f64.ln());
+    assert_almost_eq!(skewed.entropy(), expected_entropy, 1e-15);
+    let expected_kl = 0.75 * 1.5f64.ln() + 0.25 * 0.5f64.ln();
+    assert_almost_eq!(skewed.kl_divergence(&uniform), expected_kl, 1e-15);
+}
+
+#[test]
+fn add_weighted() {
+    let mut a = Histogram10::with_const_width(0., 10.);
+    let mut b = Histogram10::with_const_width(0., 10.);
+    a.add_weighted(2.5, 3).unwrap();
+    a.add_weighted(7., 0).unwrap();
+    for _ in 0..3 {
+        b.add(2.5).unwrap();
+    }
+    assert_eq!(a.bins(), b.bins());
+    assert!(a.add_weighted(10., 1).is_err());
+
+    a.add_weighted_many(&[(0.5, 2), (9.5, 4)]).unwrap();
+    assert_eq!(a.bins(), &[2, 0, 3, 0, 0, 0, 0, 0, 0, 4]);
+    assert!(a.add_weighted_many(&[(1.5, 1), (-1., 1), (3.5, 1)]).is_err());
+    assert_eq!(a.bins(), &[2, 1, 3, 0, 0, 0, 0, 0, 0, 4]);
+
+    let mut c = hist10_u32::Histogram::with_const_width(0., 10.);
+    c.add_weighted(9.5, 7).unwrap();
+    assert_eq!(c.bins_raw()[9], 7u32);
+}
+
+#[test]
+fn chi_squared_test() {
+    let uniform = [0.1; 10];
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert!(h.chi_squared_test(&uniform).is_nan());
+    for i in 0..100 {
+        h.add(f64::from(i % 10)).unwrap();
+    }
+    assert_eq!(h.chi_squared_test(&uniform), 0.);
+    assert!(h.chi_squared_test(&uniform[..9]).is_nan());
+    let mut zero = uniform;
+    zero[0] = 0.;
+    zero[1] = 0.2;
+    assert!(h.chi_squared_test(&zero).is_nan());
+
+    // Moving 5 samples from the first to the second bin deviates from the
+    // expected counts of 10 by 5 twice, so the statistic is 2 * 5^2 / 10.
+    let mut g = Histogram10::with_const_width(0., 10.);
+    for i in 0..100 {
+        let x = if i < 50 && i % 10 == 0 { 1. } else { f64::from(i % 10) };
+        g.add(x).unwrap();
+    }
+    assert_almost_eq!(g.chi_squared_test(&uniform), 5., 1e-12);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn js_divergence() {
+    let mut uniform = hist2::Histogram::with_const_width(0., 2.);
+    let mut skewed = hist2::Histogram::with_const_width(0., 2.);
+    assert!(uniform.js_divergence(&skewed).is_nan());
+    uniform.add(0.5).unwrap();
+    uniform.add(1.5).unwrap();
+    assert!(uniform.js_divergence(&skewed).is_nan());
+    skewed.add(0.5).unwrap();
+    let mut right = hist2::Histogram::with_const_width(0., 2.);
+    right.add(1.5).unwrap();
+    // Disjoint distributions have the maximal divergence.
+    assert_almost_eq!(skewed.js_divergence(&right), core::f64::consts::LN_2, 1e-15);
+    assert_eq!(uniform.js_divergence(&uniform), 0.);
+
+    for _ in 0..2 {
+        skewed.add(0.5).unwrap();
+    }
+    skewed.add(1.5).unwrap();
+    // P = (3/4, 1/4), Q = (1/2, 1/2), M = (5/8, 3/8)
+    let expected = 0.5 * (0.75 * (0.75f64 / 0.625).ln() + 0.25 * (0.25f64 / 0.375).ln())
+        + 0.5 * (0.5 * (0.5f64 / 0.625).ln() + 0.5 * (0.5f64 / 0.375).ln());
+    assert_almost_eq!(skewed.js_divergence(&uniform), expected, 1e-15);
+    assert_almost_eq!(uniform.js_divergence(&skewed), expected, 1e-15);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+#[should_panic(expected = "Both histograms must have the same ranges")]
+fn divergence_different_ranges() {
+    let mut a = hist2::Histogram::with_const_width(0., 2.);
+    let mut b = hist2::Histogram::with_const_width(0., 4.);
+    a.add(0.5).unwrap();
+    b.add(0.5).unwrap();
+    a.js_divergence(&b);
+}
+
+#[test]
+fn pmf_cdf() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for i in 0..49 {
+        h.add(f64::from(i % 7)).unwrap();
+    }
+    assert_eq!(h.pmf().len(), 10);
+    assert_eq!(h.cdf().len(), 10);
+    let sum: f64 = h.pmf().sum();
+    assert_almost_eq!(sum, 1., 1e-15);
+    for (p, count) in h.pmf().zip(h.bins()) {
+        assert_eq!(p, *count as f64 / 49.);
+    }
+    assert_eq!(h.cdf().last(), Some(1.));
+    let mut previous = 0.;
+    for (fraction, p) in h.cdf().zip(h.pmf()) {
+        assert_almost_eq!(fraction, previous + p, 1e-15);
+        previous = fraction;
+    }
+
+    let empty = Histogram10::with_const_width(0., 10.);
+    assert!(empty.pmf().all(|p| p == 0.));
+    assert!(empty.cdf().all(f64::is_nan));
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn percentile_of_score_median() {
+    use average::{Estimate, Quantile};
+
+    let mut h = hist100::Histogram::with_const_width(-3., 3.);
+    let mut median = Quantile::new(0.5);
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    for _ in 0..100_000 {
+        let x = normal.sample(&mut rng);
+        if h.add(x).is_ok() {
+            median.add(x);
+        }
+    }
+    assert_almost_eq!(h.percentile_of_score(median.quantile()), 0.5, 0.01);
+}
+
 #[test]
 fn from_ranges_invalid() {
     assert_eq!(
@@ -154,6 +651,108 @@ fn from_ranges_invalid() {
     assert!(Histogram10::from_ranges(valid_empty_ranges.iter().cloned()).is_ok());
 }
 
+#[test]
+fn from_data() {
+    let data = [3.5, -1., 4.5, 1.5, 5.5, 9., 2.5, 6.5];
+    let h = Histogram10::from_data(&data).unwrap();
+    assert_eq!(h.range_min(), -1.);
+    assert!(h.range_max() > 9.);
+    assert_almost_eq!(h.range_max(), 9., 1e-14);
+    assert_eq!(h.bins(), &[1, 0, 1, 1, 1, 1, 1, 1, 0, 1]);
+    assert_eq!(h.total_count(), data.len() as u64);
+
+    let constant = Histogram10::from_data(&[2., 2.]).unwrap();
+    assert_eq!(constant.range_min(), 1.5);
+    assert_eq!(constant.range_max(), 2.5);
+    assert_eq!(constant.total_count(), 2);
+
+    let h = Histogram10::from_data_with_bins(&data, 10).unwrap();
+    assert_eq!(h.total_count(), data.len() as u64);
+    assert_eq!(
+        Histogram10::from_data_with_bins(&data, 4).unwrap_err(),
+        FromDataError::WrongNumberOfBins
+    );
+    assert_eq!(
+        Histogram10::from_data(&[]).unwrap_err(),
+        FromDataError::EmptyData
+    );
+    assert_eq!(
+        Histogram10::from_data(&[1., f64::NAN]).unwrap_err(),
+        FromDataError::NonFiniteData
+    );
+    assert_eq!(
+        Histogram10::from_data(&[1., f64::INFINITY]).unwrap_err(),
+        FromDataError::NonFiniteData
+    );
+    assert_eq!(
+        Histogram10::from_data(&[-1e308, 1e308]).unwrap_err(),
+        FromDataError::RangeOverflow
+    );
+
+    // Adding 0.5 to a large sample is lost to rounding.
+    let large = Histogram10::from_data(&[1e17, 1e17]).unwrap();
+    assert!(large.range_min() < 1e17 && 1e17 < large.range_max());
+    assert_eq!(large.total_count(), 2);
+    assert_eq!(
+        Histogram10::from_data(&[f64::MAX]).unwrap_err(),
+        FromDataError::RangeOverflow
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn errors() {
+    fn add_checked(h: &mut Histogram10, x: f64) -> Result<(), Box<dyn std::error::Error>> {
+        h.add(x)?;
+        Ok(())
+    }
+
+    let mut h = Histogram10::from_ranges((0..=10).map(f64::from)).unwrap();
+    assert!(add_checked(&mut h, 5.).is_ok());
+    let err = add_checked(&mut h, 11.).unwrap_err();
+    assert_eq!(err.to_string(), "sample out of range of the histogram");
+
+    let err: Box<dyn std::error::Error> = Histogram10::from_ranges([1., 0.].iter().cloned())
+        .unwrap_err()
+        .into();
+    assert_eq!(err.to_string(), "histogram ranges are not sorted");
+    let err: Box<dyn std::error::Error> = Box::new(InvalidRangeError::NotEnoughRanges);
+    assert_eq!(err.to_string(), "not enough histogram ranges");
+    let err: Box<dyn std::error::Error> = Histogram10::from_data(&[]).unwrap_err().into();
+    assert_eq!(err.to_string(), "no data to derive the histogram ranges from");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dyn_from_data() {
+    use average::DynHistogram;
+
+    let data: Vec<f64> = (0..100).map(f64::from).collect();
+    let h = DynHistogram::from_data(&data).unwrap();
+    // Sturges' rule: ceil(log2(100) + 1) = 8
+    assert_eq!(h.n_bins(), 8);
+    assert_eq!(h.range_min(), 0.);
+    assert_eq!(h.total_count(), 100);
+
+    let h = DynHistogram::from_data_with_bins(&data, 3).unwrap();
+    assert_eq!(h.n_bins(), 3);
+    assert_eq!(h.bins(), &[34, 33, 33]);
+
+    let single = DynHistogram::from_data(&[-0.5]).unwrap();
+    assert_eq!(single.n_bins(), 1);
+    assert_eq!(single.bins(), &[1]);
+    assert_eq!(
+        DynHistogram::from_data(&[]).unwrap_err(),
+        FromDataError::EmptyData
+    );
+    assert_eq!(
+        DynHistogram::from_data_with_bins(&[-1e308, 1e308], 3).unwrap_err(),
+        FromDataError::RangeOverflow
+    );
+    let large = DynHistogram::from_data(&[1e17, 1e17]).unwrap();
+    assert_eq!(large.total_count(), 2);
+}
+
 #[test]
 fn from_ranges_empty() {
     let mut h = Histogram10::from_ranges(
@@ -178,6 +777,23 @@ fn out_of_range() {
     assert_eq!(h.add(100.1), Err(SampleOutOfRangeError));
 }
 
+#[test]
+fn add_slice() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.add_slice(&[1.0, -999.0, 2.0]), 1);
+    assert_eq!(h.bins(), &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(h.add_slice(&[]), 0);
+
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(
+        h.add_slice_strict(&[1.0, -999.0, 2.0]),
+        Err((1, SampleOutOfRangeError))
+    );
+    assert_eq!(h.bins(), &[1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(h.add_slice_strict(&[15.0, 25.0]), Ok(()));
+    assert_eq!(h.bins(), &[1, 1, 1, 0, 0, 0, 0, 0, 0, 0]);
+}
+
 #[test]
 fn reset() {
     let mut h = Histogram10::with_const_width(0., 100.);
@@ -239,7 +855,6 @@ fn variance() {
     for _ in 0..1000000 {
         let _ = h.add(normal.sample(&mut rng));
     }
-    println!("{:?}", h);
     let sum: u64 = h.bins().iter().sum();
     let sum = sum as f64;
     for (i, v) in h.variances().enumerate() {
@@ -276,6 +891,140 @@ fn merge() {
     assert_eq!(h.bins(), h1.bins());
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn smooth_gaussian() {
+    let mut h = hist100::Histogram::with_const_width(0., 100.);
+    assert!(h.smooth_gaussian(0.).iter().all(|x| x.is_nan()));
+    assert!(h.smooth_gaussian(-1.).iter().all(|x| x.is_nan()));
+    assert!(h.smooth_gaussian(f64::NAN).iter().all(|x| x.is_nan()));
+    assert!(h.smooth_gaussian(f64::INFINITY).iter().all(|x| x.is_nan()));
+    assert!(h.smooth_gaussian(1.).iter().all(|&x| x == 0.));
+
+    // Smoothing a spike yields the kernel, which approximates a Gaussian.
+    for _ in 0..1000 {
+        h.add(50.5).unwrap();
+    }
+    let sigma = 3.;
+    let smoothed = h.smooth_gaussian(sigma);
+    assert_eq!(smoothed.len(), 100);
+    assert_almost_eq!(smoothed.iter().sum::<f64>(), 1000., 1e-9);
+    let normalization = 1000. / ((2. * core::f64::consts::PI).sqrt() * sigma);
+    for (i, &x) in smoothed.iter().enumerate() {
+        let k = i as f64 - 50.;
+        if k.abs() > 3. * sigma {
+            assert_eq!(x, 0.);
+        } else {
+            let expected = normalization * (-0.5 * (k / sigma).powi(2)).exp();
+            assert_almost_eq!(x / expected, 1., 5e-3);
+        }
+    }
+
+    // Counts are lost beyond the edges.
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h.add(0.5).unwrap();
+    let smoothed = h.smooth_gaussian(1.);
+    assert!(smoothed.iter().sum::<f64>() < 1.);
+    assert_eq!(smoothed[0], smoothed.iter().cloned().fold(0., f64::max));
+
+    // A huge kernel is truncated at the number of bins and is almost flat.
+    let smoothed = h.smooth_gaussian(1e300);
+    assert_eq!(smoothed.len(), 10);
+    for &x in &smoothed {
+        assert_almost_eq!(x, 1. / 19., 1e-15);
+    }
+}
+
+#[test]
+fn try_merge() {
+    use average::IncompatibleRangesError;
+
+    let mut h1 = Histogram10::with_const_width(0., 10.);
+    let mut h2 = Histogram10::with_const_width(0., 10.);
+    h1.add(1.5).unwrap();
+    h2.add(1.5).unwrap();
+    h2.add(8.5).unwrap();
+    assert_eq!(h1.try_merge(&h2), Ok(()));
+    assert_eq!(h1.bins(), &[0, 2, 0, 0, 0, 0, 0, 0, 1, 0]);
+
+    let mut ranges: Vec<f64> = (0..=10).map(f64::from).collect();
+    ranges[3] = 2.5;
+    let other = Histogram10::from_ranges(ranges).unwrap();
+    let before = h1.clone();
+    assert_eq!(
+        h1.try_merge(&other),
+        Err(IncompatibleRangesError {
+            index: 3,
+            expected: 3.,
+            got: 2.5
+        })
+    );
+    assert_eq!(h1.bins(), before.bins());
+}
+
+#[test]
+#[should_panic(expected = "Both histograms must have the same ranges")]
+fn merge_incompatible() {
+    let mut h1 = Histogram10::with_const_width(0., 10.);
+    let h2 = Histogram10::with_const_width(0., 20.);
+    h1.merge(&h2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic(expected = "Both histograms must have the same ranges: incompatible histogram ranges: expected 1 at index 1, got 2")]
+fn dyn_merge_incompatible() {
+    use average::DynHistogram;
+
+    let mut h1 = DynHistogram::with_const_width(0., 10., 10);
+    let h2 = DynHistogram::with_const_width(0., 20., 10);
+    h1.merge(&h2);
+}
+
+#[test]
+fn sub_assign() {
+    use average::IncompatibleRangesError;
+
+    let mut signal = Histogram10::with_const_width(0., 10.);
+    let mut background = Histogram10::with_const_width(0., 10.);
+    signal.add_weighted_many(&[(0.5, 5), (1.5, 3), (2.5, 1)]).unwrap();
+    background.add_weighted_many(&[(0.5, 2), (1.5, 3), (2.5, 4)]).unwrap();
+
+    let mut equal = signal.clone();
+    equal -= &signal;
+    assert!(equal.is_empty());
+
+    signal -= &background;
+    assert_eq!(signal.bins(), &[3, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mut small = hist10_u32::Histogram::with_const_width(0., 10.);
+    let mut large = hist10_u32::Histogram::with_const_width(0., 10.);
+    small.add(0.5).unwrap();
+    large.add_weighted(0.5, 10).unwrap();
+    small -= &large;
+    assert_eq!(small.bins_raw()[0], 0u32);
+
+    let other = Histogram10::with_const_width(0., 20.);
+    let before = signal.clone();
+    assert_eq!(
+        signal.try_sub_assign(&other),
+        Err(IncompatibleRangesError {
+            index: 1,
+            expected: 1.,
+            got: 2.
+        })
+    );
+    assert_eq!(signal.bins(), before.bins());
+}
+
+#[test]
+#[should_panic(expected = "Both histograms must have the same ranges")]
+fn sub_assign_incompatible() {
+    let mut h1 = Histogram10::with_const_width(0., 10.);
+    let h2 = Histogram10::with_const_width(0., 20.);
+    h1 -= &h2;
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn simple_serde() {
@@ -296,3 +1045,215 @@ fn simple_serde() {
     let c: Histogram10 = serde_json::from_str(&b).unwrap();
     assert_eq!(c.bins(), &[1, 0, 0, 0, 0, 0, 1, 0, 0, 2]);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn to_probability_histogram() {
+    let mut h = Histogram10::from_ranges(
+        [0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.7, 0.8, 0.9, 1.0, 2.0]
+            .iter()
+            .cloned(),
+    )
+    .unwrap();
+    for &x in &[0.05, 0.55, 0.6, 1.0, 1.5, 1.7, 1.8, 1.9] {
+        h.add(x).unwrap();
+    }
+    let p = h.to_probability_histogram();
+    assert_eq!(p.n_bins(), 10);
+    assert_eq!(p.ranges(), h.ranges());
+    assert_eq!(p.bins(), &[0.125, 0., 0., 0., 0., 0.25, 0., 0., 0., 0.625]);
+    assert_eq!(p.total(), 1.);
+
+    let d = h.to_density_histogram();
+    assert_eq!(d.ranges(), h.ranges());
+    assert_almost_eq!(d.bins()[0], 1.25, 1e-14);
+    assert_almost_eq!(d.bins()[5], 1.25, 1e-14);
+    assert_almost_eq!(d.bins()[9], 0.625, 1e-14);
+    let integral: f64 = d.iter().map(|((a, b), density)| density * (b - a)).sum();
+    assert_almost_eq!(integral, 1., 1e-14);
+    let integral: f64 = d.bins().iter().zip(d.widths()).map(|(d, w)| d * w).sum();
+    assert_almost_eq!(integral, 1., 1e-14);
+
+    let empty = Histogram10::with_const_width(0., 1.).to_probability_histogram();
+    assert!(empty.bins().iter().all(|p| p.is_nan()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn apply_transform() {
+    let mut h = hist100::Histogram::with_const_width(-3., 3.);
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    for _ in 0..100_000 {
+        let _ = h.add(normal.sample(&mut rng));
+    }
+    let total: u64 = h.bins().iter().sum();
+
+    let t = h.apply_transform(f64::exp, 400);
+    assert_eq!(t.n_bins(), 400);
+    assert_eq!(t.bins().iter().sum::<u64>(), total);
+    assert_almost_eq!(t.range_min(), (-3f64).exp(), 1e-12);
+    assert_almost_eq!(t.range_max(), 3f64.exp(), 1e-12);
+
+    // The log-normal distribution has its mode at exp(-1) and its median at 1.
+    let (mode, _) = t
+        .iter()
+        .fold(((0., 0.), 0), |acc, x| if x.1 > acc.1 { x } else { acc });
+    assert!(mode.0 <= (-1f64).exp() + 0.5 && (-1f64).exp() - 0.5 <= mode.1);
+    let below_one: u64 = t.iter().filter(|((_, b), _)| *b <= 1.).map(|(_, c)| c).sum();
+    assert_almost_eq!(below_one as f64 / total as f64, 0.5, 0.05);
+}
+
+#[test]
+fn cumulative_bins() {
+    let mut h = hist3::Histogram::with_const_width(0., 3.);
+    for &x in &[0.5, 1.5, 1.5, 2.5, 2.5, 2.5] {
+        h.add(x).unwrap();
+    }
+    let cumulative: Vec<u64> = h.cumulative_bins().collect();
+    assert_eq!(cumulative, [1, 3, 6]);
+    assert_eq!(*cumulative.last().unwrap(), h.bins().iter().sum::<u64>());
+    let fractions: Vec<f64> = h.cumulative_fractions().collect();
+    assert_eq!(fractions, [1. / 6., 0.5, 1.]);
+
+    let empty = hist3::Histogram::with_const_width(0., 3.);
+    assert_eq!(empty.cumulative_bins().collect::<Vec<_>>(), [0, 0, 0]);
+    assert!(empty.cumulative_fractions().all(f64::is_nan));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn expected_counts() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for i in 0..100 {
+        h.add(f64::from(i) / 10.).unwrap();
+    }
+    let uniform = |x: f64| if (0. ..10.).contains(&x) { 0.1 } else { 0. };
+    for e in h.expected_counts(uniform, 100) {
+        assert_almost_eq!(e, 10., 1e-14);
+    }
+    assert_almost_eq!(h.chi_squared_vs_distribution(uniform), 0., 1e-13);
+
+    // Concentrated density matching the data.
+    let mut g = Histogram10::with_const_width(0., 10.);
+    for _ in 0..50 {
+        g.add(4.5).unwrap();
+        g.add(5.5).unwrap();
+    }
+    let concentrated = |x: f64| if (4. ..6.).contains(&x) { 0.5 } else { 0. };
+    assert_almost_eq!(g.chi_squared_vs_distribution(concentrated), 0., 1e-13);
+    assert!(g.chi_squared_vs_distribution(uniform) > 100.);
+    assert_eq!(h.chi_squared_vs_distribution(concentrated), f64::INFINITY);
+
+    let inf = f64::INFINITY;
+    let k = Histogram10::from_ranges(
+        [-inf, 1., 2., 3., 4., 5., 6., 7., 8., 9., 10.].iter().cloned(),
+    )
+    .unwrap();
+    let expected = k.expected_counts(uniform, 100);
+    assert!(expected[0].is_nan());
+    assert_almost_eq!(expected[1], 10., 1e-14);
+}
+
+#[test]
+fn exact_size_and_double_ended_iterators() {
+    let mut h = hist3::Histogram::with_const_width(0., 3.);
+    h[0] = 1;
+    h[1] = 2;
+    h[2] = 3;
+
+    let mut iter = h.iter();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next_back(), Some(((2., 3.), 3)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some(((1., 2.), 2)));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next_back(), None);
+
+    let reversed: Vec<_> = h.iter().rev().collect();
+    assert_eq!(reversed, [((2., 3.), 3), ((1., 2.), 2), ((0., 1.), 1)]);
+    let centers: Vec<f64> = h.centers().rev().collect();
+    assert_eq!(centers, [2.5, 1.5, 0.5]);
+    let normalized: Vec<f64> = h.normalized_bins().rev().collect();
+    assert_eq!(normalized, [3., 2., 1.]);
+    let widths: Vec<f64> = h.widths().rev().collect();
+    assert_eq!(widths, [1., 1., 1.]);
+
+    assert_eq!(h.centers().len(), 3);
+    assert_eq!(h.normalized_bins().len(), 3);
+    assert_eq!(h.widths().len(), 3);
+    assert_eq!(h.variances().len(), 3);
+    assert_eq!(h.cumulative_bins().len(), 3);
+    assert_eq!(h.cumulative_fractions().len(), 3);
+
+    let mut iter = h.iter_mut();
+    assert_eq!(iter.len(), 3);
+    *iter.next_back().unwrap().1 = 4;
+    assert_eq!(iter.len(), 2);
+    assert_eq!(h[2], 4);
+}
+
+#[test]
+fn bins_as_probabilities() {
+    let mut h = Histogram10::from_ranges(
+        [0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.7, 0.8, 0.9, 1.0, 2.0]
+            .iter()
+            .cloned(),
+    )
+    .unwrap();
+    for &x in &[0.05, 0.15, 0.15, 0.6, 0.6, 0.6, 1.5] {
+        h.add(x).unwrap();
+    }
+    assert_eq!(h.bins_as_probabilities().len(), 10);
+    let sum: f64 = h.bins_as_probabilities().sum();
+    assert_almost_eq!(sum, 1., 1e-15);
+    let probabilities: Vec<f64> = h.bins_as_probabilities().collect();
+    assert_eq!(probabilities[1], 2. / 7.);
+
+    assert_eq!(h.bins_as_densities().len(), 10);
+    let integral: f64 = h.bins_as_densities().zip(h.widths()).map(|(d, w)| d * w).sum();
+    assert_almost_eq!(integral, 1., 1e-15);
+    let densities: Vec<f64> = h.bins_as_densities().collect();
+    assert_almost_eq!(densities[5], 3. / 7. / 0.2, 1e-14);
+
+    let empty = Histogram10::with_const_width(0., 1.);
+    assert!(empty.bins_as_probabilities().all(|p| p == 0.));
+    assert!(empty.bins_as_densities().all(|p| p == 0.));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rebin() {
+    use average::RebinError;
+
+    let mut h = hist100::Histogram::with_const_width(0., 100.);
+    for i in 0..100 {
+        for _ in 0..=(i % 7) {
+            h.add(f64::from(i) + 0.5).unwrap();
+        }
+    }
+    let (ranges, counts) = h.rebin(10).unwrap();
+    assert_eq!(ranges.len(), 11);
+    assert_eq!(counts.len(), 10);
+    for (i, &r) in ranges.iter().enumerate() {
+        assert_almost_eq!(r, 10. * i as f64, 1e-12);
+    }
+    for (i, &c) in counts.iter().enumerate() {
+        let expected: u64 = h.bins()[10 * i..10 * (i + 1)].iter().sum();
+        assert_eq!(c, expected);
+    }
+    assert_eq!(counts.iter().sum::<u64>(), h.total_count());
+
+    let (ranges, counts) = h.rebin(100).unwrap();
+    assert_eq!(ranges, h.ranges());
+    assert_eq!(counts, h.bins());
+    let (ranges, counts) = h.rebin(1).unwrap();
+    assert_eq!(ranges, [0., 100.]);
+    assert_eq!(counts, [h.total_count()]);
+
+    assert_eq!(h.rebin(0), Err(RebinError::ZeroBins));
+    assert_eq!(h.rebin(200), Err(RebinError::TooManyBins));
+    assert_eq!(h.rebin(30), Err(RebinError::NotDivisible));
+}
@@ -2,7 +2,7 @@ use core::iter::Iterator;
 use rand::SeedableRng;
 use rand_distr::Distribution;
 
-use average::histogram_const::{Histogram, InvalidRangeError, SampleOutOfRangeError};
+use average::histogram_const::{Histogram, InvalidRangeError, SampleOutOfRangeError, SaturationMode};
 use average::{assert_almost_eq, Merge};
 
 type Histogram10 = Histogram<10>;
@@ -59,6 +59,141 @@ fn iter() {
     );
 }
 
+#[test]
+fn fold() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    h.add_slice(&[5., 15., 15., 35., 99.]);
+    let total = h.fold(0u64, |acc, (_, count)| acc + count);
+    assert_eq!(total, h.bins().iter().sum::<u64>());
+    let upper = h.fold(f64::NEG_INFINITY, |acc, ((_, b), _)| acc.max(b));
+    assert_eq!(upper, 100.);
+
+    let mut nonempty = 0;
+    h.for_each(|_, count| {
+        if count > 0 {
+            nonempty += 1;
+        }
+    });
+    assert_eq!(nonempty, 4);
+}
+
+#[test]
+fn iter_mut() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    h.add_slice(&[5., 15., 15., 35., 99.]);
+    let mut expected = h.clone();
+    expected *= 2;
+    for (_, count) in h.iter_mut() {
+        *count *= 2;
+    }
+    assert_eq!(h.bins(), expected.bins());
+
+    for ((a, _), count) in h.iter_mut() {
+        if a >= 10. {
+            *count = 0;
+        }
+    }
+    assert_eq!(h.bins(), &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    for (_, count) in h.iter_mut() {
+        *count = 0;
+    }
+    assert!(h.bins().iter().all(|&count| count == 0));
+}
+
+#[test]
+fn index() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for &x in &[1.5, 3.2, 3.7, 9.9] {
+        h.add(x).unwrap();
+    }
+    assert_eq!(h[3], h.bins()[3]);
+    assert_eq!(h[3], 2);
+    h[3] += 5;
+    h[0] = 1;
+    assert_eq!(h.bins(), &[1, 1, 0, 7, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+#[should_panic(expected = "histogram bin index 10 out of range [0, 10)")]
+fn index_out_of_range() {
+    let h = Histogram10::with_const_width(0., 10.);
+    let _ = h[10];
+}
+
+#[test]
+fn smooth() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for _ in 0..9 {
+        h.add(4.5).unwrap();
+    }
+    let s = h.smooth(1);
+    assert_eq!(s.bins(), &[0, 0, 0, 3, 3, 3, 0, 0, 0, 0]);
+    assert_eq!(s.ranges(), h.ranges());
+    assert_eq!(h.smooth(0).bins(), h.bins());
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for i in 0..100 {
+        h.add(f64::from(i) / 10.).unwrap();
+    }
+    assert_eq!(h.smooth(2).bins(), &[10; 10]);
+    assert_eq!(h.smooth(100).bins(), &[10; 10]);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn sample() {
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let mut h = Histogram::<100>::with_const_width(-3., 3.);
+    assert!(h.sample(&mut rng).is_none());
+    for _ in 0..100_000 {
+        let _ = h.add(normal.sample(&mut rng));
+    }
+
+    let mut resampled = Histogram::<100>::with_const_width(-3., 3.);
+    for _ in 0..100_000 {
+        let x = h.sample(&mut rng).unwrap();
+        assert!((-3. ..3.).contains(&x));
+        resampled.add(x).unwrap();
+    }
+    let total = h.bins().iter().sum::<u64>() as f64;
+    let kl_divergence: f64 = h
+        .bins()
+        .iter()
+        .zip(resampled.bins())
+        .filter(|&(&p, _)| p > 0)
+        .map(|(&p, &q)| {
+            let p = p as f64 / total;
+            let q = q as f64 / 100_000.;
+            p * (p / q).ln()
+        })
+        .sum();
+    assert!(kl_divergence < 0.01, "{}", kl_divergence);
+}
+
+#[test]
+fn binary_bar_chart() {
+    let mut h = Histogram::<3>::with_const_width(0., 30.);
+    for &(x, n) in &[(5., 80), (15., 40), (25., 5)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    assert_eq!(
+        format!("{:.8b}", h),
+        "[0.0, 10.0): ████████ (80)\n[10.0, 20.0): ████ (40)\n[20.0, 30.0): ▌ (5)"
+    );
+    let default = format!("{:b}", h);
+    let bars: Vec<usize> = default
+        .lines()
+        .map(|line| line.chars().filter(|&c| c == '█').count())
+        .collect();
+    assert_eq!(bars, [40, 20, 2]);
+
+    let empty = Histogram::<2>::with_const_width(0., 2.);
+    assert_eq!(format!("{:b}", empty), "[0.0, 1.0): (0)\n[1.0, 2.0): (0)");
+}
+
 #[test]
 fn normalized_bins() {
     let inf = std::f64::INFINITY;
@@ -110,6 +245,348 @@ fn centers() {
     }
 }
 
+#[test]
+fn trim() {
+    let mut h = Histogram::<100>::with_const_width(0., 100.);
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.histogram_mean(), 50.);
+    assert_eq!(h.histogram_variance(), 833.25);
+
+    let t = h.trim(0.05, 0.05);
+    assert_eq!(t.bins().iter().sum::<u64>(), 90);
+    assert!(t.bins()[..5].iter().all(|&c| c == 0));
+    assert!(t.bins()[5..95].iter().all(|&c| c == 1));
+    assert!(t.bins()[95..].iter().all(|&c| c == 0));
+    assert_eq!(h.trim(0., 0.).bins(), h.bins());
+    assert_eq!(h.trim(0.5, 0.5).bins().iter().sum::<u64>(), 0);
+
+    // One standard deviation is about 28.9.
+    let t = h.trim_by_std(1.);
+    assert_eq!(t.bins().iter().sum::<u64>(), 58);
+    assert_eq!(t.bins()[21], 1);
+    assert_eq!(t.bins()[20], 0);
+    assert_eq!(h.trim_by_std(f64::INFINITY).bins(), h.bins());
+
+    let empty = Histogram10::with_const_width(0., 1.);
+    assert!(empty.histogram_mean().is_nan());
+    assert_eq!(empty.trim(0.1, 0.1).bins(), empty.bins());
+    assert_eq!(empty.trim_by_std(1.).bins(), empty.bins());
+}
+
+#[test]
+fn max_min_bin() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert_eq!(h.max_bin(), None);
+    assert_eq!(h.min_bin(), None);
+    assert_eq!(h.max_bin_count(), None);
+    assert_eq!(h.min_nonzero_bin_count(), None);
+    for &(x, n) in &[(1.5, 2), (3.5, 5), (6.5, 1), (8.5, 5), (9.5, 1)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    assert_eq!(h.max_bin(), Some(((3., 4.), 5)));
+    assert_eq!(h.min_bin(), Some(((6., 7.), 1)));
+    assert_eq!(h.max_bin_count(), Some(5));
+    assert_eq!(h.min_nonzero_bin_count(), Some(1));
+}
+
+#[test]
+fn mode() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert!(h.mode_center().is_nan());
+    let (a, b) = h.mode_range();
+    assert!(a.is_nan() && b.is_nan());
+    for &(x, n) in &[(1.5, 2), (3.5, 7), (6.5, 1), (8.5, 4)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    assert_eq!(h.mode_range(), (3., 4.));
+    assert_eq!(h.mode_center(), 3.5);
+    for _ in 0..3 {
+        h.add(8.5).unwrap();
+    }
+    // On ties, the leftmost bin is used.
+    assert_eq!(h.mode_range(), (3., 4.));
+    h.add(8.5).unwrap();
+    assert_eq!(h.mode_center(), 8.5);
+}
+
+#[test]
+fn percentile_of_score() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert!(h.percentile_of_score(50.).is_nan());
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.percentile_of_score(-1.), 0.);
+    assert_eq!(h.percentile_of_score(0.), 0.);
+    assert_eq!(h.percentile_of_score(30.), 0.3);
+    assert_almost_eq!(h.percentile_of_score(35.), 0.35, 1e-15);
+    assert_eq!(h.percentile_of_score(100.), 1.);
+    assert_eq!(h.percentile_of_score(1000.), 1.);
+    assert!(h.percentile_of_score(f64::NAN).is_nan());
+}
+
+#[test]
+fn total_count_fraction_below_find_percentile() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.total_count(), 0);
+    assert!(h.is_empty());
+    assert!(h.fraction_below(50.).is_nan());
+    assert!(h.find_percentile(0.5).is_nan());
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.total_count(), 100);
+    assert!(!h.is_empty());
+
+    assert_eq!(h.fraction_below(-1.), 0.);
+    assert_eq!(h.fraction_below(0.), 0.1);
+    assert_eq!(h.fraction_below(35.), 0.4);
+    assert_eq!(h.fraction_below(40.), 0.5);
+    assert_eq!(h.fraction_below(99.), 1.);
+    assert_eq!(h.fraction_below(1000.), 1.);
+    assert!(h.fraction_below(f64::NAN).is_nan());
+
+    assert_eq!(h.find_percentile(0.), 0.);
+    assert_eq!(h.find_percentile(0.3), 30.);
+    assert_almost_eq!(h.find_percentile(0.35), 35., 1e-12);
+    assert_almost_eq!(h.find_percentile(0.999), 99.9, 1e-12);
+    assert_eq!(h.find_percentile(1.), 100.);
+    assert_eq!(h.find_percentile(-0.1), 0.);
+    assert_eq!(h.find_percentile(1.1), 100.);
+    assert!(h.find_percentile(f64::NAN).is_nan());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn entropy_kl_divergence() {
+    let mut uniform = Histogram::<2>::with_const_width(0., 2.);
+    assert!(uniform.entropy().is_nan());
+    uniform.add(0.5).unwrap();
+    uniform.add(1.5).unwrap();
+    assert_almost_eq!(uniform.entropy(), core::f64::consts::LN_2, 1e-15);
+    assert_eq!(uniform.kl_divergence(&uniform), 0.);
+
+    let mut skewed = Histogram::<2>::with_const_width(0., 2.);
+    assert!(skewed.kl_divergence(&uniform).is_nan());
+    assert!(uniform.kl_divergence(&skewed).is_nan());
+    for _ in 0..3 {
+        skewed.add(0.5).unwrap();
+    }
+    assert_eq!(skewed.entropy(), 0.);
+    assert_eq!(skewed.kl_divergence(&uniform), core::f64::consts::LN_2);
+    assert_eq!(uniform.kl_divergence(&skewed), f64::INFINITY);
+    skewed.add(1.5).unwrap();
+    let expected_entropy = -(0.75 * 0.75f64.ln() + 0.25 * 0.25f64.ln());
+    assert_almost_eq!(skewed.entropy(), expected_entropy, 1e-15);
+    let expected_kl = 0.75 * 1.5f64.ln() + 0.25 * 0.5f64.ln();
+    assert_almost_eq!(skewed.kl_divergence(&uniform), expected_kl, 1e-15);
+}
+
+#[test]
+fn add_weighted() {
+    let mut a = Histogram::<10>::with_const_width(0., 10.);
+    let mut b = Histogram::<10>::with_const_width(0., 10.);
+    a.add_weighted(2.5, 3).unwrap();
+    a.add_weighted(7., 0).unwrap();
+    for _ in 0..3 {
+        b.add(2.5).unwrap();
+    }
+    assert_eq!(a.bins(), b.bins());
+    assert!(a.add_weighted(10., 1).is_err());
+
+    a.add_weighted_many(&[(0.5, 2), (9.5, 4)]).unwrap();
+    assert_eq!(a.bins(), &[2, 0, 3, 0, 0, 0, 0, 0, 0, 4]);
+    assert!(a.add_weighted_many(&[(1.5, 1), (-1., 1), (3.5, 1)]).is_err());
+    assert_eq!(a.bins(), &[2, 1, 3, 0, 0, 0, 0, 0, 0, 4]);
+}
+
+#[test]
+fn chi_squared_test() {
+    let uniform = [0.1; 10];
+    let mut h = Histogram::<10>::with_const_width(0., 10.);
+    assert!(h.chi_squared_test(&uniform).is_nan());
+    for i in 0..100 {
+        h.add(f64::from(i % 10)).unwrap();
+    }
+    assert_eq!(h.chi_squared_test(&uniform), 0.);
+    assert!(h.chi_squared_test(&uniform[..9]).is_nan());
+    let mut zero = uniform;
+    zero[0] = 0.;
+    zero[1] = 0.2;
+    assert!(h.chi_squared_test(&zero).is_nan());
+
+    // Moving 5 samples from the first to the second bin deviates from the
+    // expected counts of 10 by 5 twice, so the statistic is 2 * 5^2 / 10.
+    let mut g = Histogram::<10>::with_const_width(0., 10.);
+    for i in 0..100 {
+        let x = if i < 50 && i % 10 == 0 { 1. } else { f64::from(i % 10) };
+        g.add(x).unwrap();
+    }
+    assert_almost_eq!(g.chi_squared_test(&uniform), 5., 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn expected_counts() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for i in 0..100 {
+        h.add(f64::from(i) / 10.).unwrap();
+    }
+    let uniform = |x: f64| if (0. ..10.).contains(&x) { 0.1 } else { 0. };
+    for e in h.expected_counts(uniform, 100) {
+        assert_almost_eq!(e, 10., 1e-14);
+    }
+    assert_almost_eq!(h.chi_squared_vs_distribution(uniform), 0., 1e-13);
+
+    let concentrated = |x: f64| if (4. ..6.).contains(&x) { 0.5 } else { 0. };
+    assert_eq!(h.chi_squared_vs_distribution(concentrated), f64::INFINITY);
+
+    let inf = f64::INFINITY;
+    let k = Histogram10::from_ranges(
+        [-inf, 1., 2., 3., 4., 5., 6., 7., 8., 9., 10.].iter().cloned(),
+    )
+    .unwrap();
+    let expected = k.expected_counts(uniform, 100);
+    assert!(expected[0].is_nan());
+    assert_almost_eq!(expected[1], 10., 1e-14);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn smooth_gaussian() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert!(h.smooth_gaussian(0.).iter().all(|x| x.is_nan()));
+    assert!(h.smooth_gaussian(1.).iter().all(|&x| x == 0.));
+
+    for _ in 0..100 {
+        h.add(4.5).unwrap();
+    }
+    let smoothed = h.smooth_gaussian(1.);
+    assert_eq!(smoothed.len(), 10);
+    assert_almost_eq!(smoothed.iter().sum::<f64>(), 100., 1e-12);
+    assert_almost_eq!(smoothed[3], smoothed[5], 1e-12);
+    assert_eq!(smoothed[0], 0.);
+    assert_eq!(smoothed[4], smoothed.iter().cloned().fold(0., f64::max));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn apply_transform() {
+    use average::Histogram as _;
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for i in 0..100 {
+        h.add(f64::from(i) / 10.).unwrap();
+    }
+    let t = h.apply_transform(|x| 2. * x, 5);
+    assert_eq!(t.n_bins(), 5);
+    assert_eq!(t.range_min(), 0.);
+    assert_eq!(t.range_max(), 20.);
+    assert_eq!(t.bins(), &[20; 5]);
+
+    // Bins whose centers are mapped to NaN by `ln` are dropped.
+    let mut g = Histogram::<2>::with_const_width(-2., 2.);
+    g.add(-1.).unwrap();
+    g.add(1.).unwrap();
+    let t = g.apply_transform(f64::ln, 1);
+    assert_eq!(t.bins(), &[1]);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn js_divergence() {
+    let mut uniform = Histogram::<2>::with_const_width(0., 2.);
+    let mut skewed = Histogram::<2>::with_const_width(0., 2.);
+    assert!(uniform.js_divergence(&skewed).is_nan());
+    uniform.add(0.5).unwrap();
+    uniform.add(1.5).unwrap();
+    assert!(uniform.js_divergence(&skewed).is_nan());
+    skewed.add(0.5).unwrap();
+    let mut right = Histogram::<2>::with_const_width(0., 2.);
+    right.add(1.5).unwrap();
+    // Disjoint distributions have the maximal divergence.
+    assert_almost_eq!(skewed.js_divergence(&right), core::f64::consts::LN_2, 1e-15);
+    assert_eq!(uniform.js_divergence(&uniform), 0.);
+
+    for _ in 0..2 {
+        skewed.add(0.5).unwrap();
+    }
+    skewed.add(1.5).unwrap();
+    // P = (3/4, 1/4), Q = (1/2, 1/2), M = (5/8, 3/8)
+    let expected = 0.5 * (0.75 * (0.75f64 / 0.625).ln() + 0.25 * (0.25f64 / 0.375).ln())
+        + 0.5 * (0.5 * (0.5f64 / 0.625).ln() + 0.5 * (0.5f64 / 0.375).ln());
+    assert_almost_eq!(skewed.js_divergence(&uniform), expected, 1e-15);
+    assert_almost_eq!(uniform.js_divergence(&skewed), expected, 1e-15);
+}
+
+#[test]
+fn sub_assign() {
+    let mut signal = Histogram::<10>::with_const_width(0., 10.);
+    let mut background = Histogram::<10>::with_const_width(0., 10.);
+    signal.add_weighted_many(&[(0.5, 5), (1.5, 3), (2.5, 1)]).unwrap();
+    background.add_weighted_many(&[(0.5, 2), (1.5, 3), (2.5, 4)]).unwrap();
+
+    let mut equal = signal.clone();
+    equal -= &signal;
+    assert!(equal.is_empty());
+
+    signal -= &background;
+    assert_eq!(signal.bins(), &[3, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let other = Histogram::<10>::with_const_width(0., 20.);
+    assert!(signal.try_sub_assign(&other).is_err());
+    assert_eq!(signal.bins(), &[3, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn pmf_cdf() {
+    let mut h = Histogram::<10>::with_const_width(0., 10.);
+    for i in 0..49 {
+        h.add(f64::from(i % 7)).unwrap();
+    }
+    assert_eq!(h.pmf().len(), 10);
+    assert_eq!(h.cdf().len(), 10);
+    let sum: f64 = h.pmf().sum();
+    assert_almost_eq!(sum, 1., 1e-15);
+    for (p, count) in h.pmf().zip(h.bins()) {
+        assert_eq!(p, *count as f64 / 49.);
+    }
+    assert_eq!(h.cdf().last(), Some(1.));
+    let mut previous = 0.;
+    for (fraction, p) in h.cdf().zip(h.pmf()) {
+        assert_almost_eq!(fraction, previous + p, 1e-15);
+        previous = fraction;
+    }
+
+    let empty = Histogram::<10>::with_const_width(0., 10.);
+    assert!(empty.pmf().all(|p| p == 0.));
+    assert!(empty.cdf().all(f64::is_nan));
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn percentile_of_score_median() {
+    use average::{Estimate, Quantile};
+
+    let mut h = Histogram::<100>::with_const_width(-3., 3.);
+    let mut median = Quantile::new(0.5);
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    for _ in 0..100_000 {
+        let x = normal.sample(&mut rng);
+        if h.add(x).is_ok() {
+            median.add(x);
+        }
+    }
+    assert_almost_eq!(h.percentile_of_score(median.quantile()), 0.5, 0.01);
+}
+
 #[test]
 fn from_ranges_infinity() {
     let inf = std::f64::INFINITY;
@@ -175,6 +652,56 @@ fn out_of_range() {
     assert_eq!(h.add(100.1), Err(SampleOutOfRangeError));
 }
 
+#[test]
+fn add_assign_saturate() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h += -5.;
+    h += 0.;
+    h += 5.5;
+    h += 10.;
+    h += 1e9;
+    h += f64::NAN;
+    assert_eq!(h.bins(), &[2, 0, 0, 0, 0, 1, 0, 0, 0, 2]);
+    h += (f64::NEG_INFINITY, SaturationMode::Saturate);
+    assert_eq!(h.bins(), &[3, 0, 0, 0, 0, 1, 0, 0, 0, 2]);
+}
+
+#[test]
+fn add_assign_ignore() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h += (-5., SaturationMode::Ignore);
+    h += (0., SaturationMode::Ignore);
+    h += (10., SaturationMode::Ignore);
+    h += (f64::NAN, SaturationMode::Ignore);
+    assert_eq!(h.bins(), &[1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "Sample 10 is out of range of the histogram")]
+fn add_assign_panic() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h += (9.5, SaturationMode::Panic);
+    assert_eq!(h.bins(), &[0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    h += (10., SaturationMode::Panic);
+}
+
+#[test]
+fn add_slice() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.add_slice(&[1.0, -999.0, 2.0]), 1);
+    assert_eq!(h.bins(), &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(h.add_slice(&[]), 0);
+
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(
+        h.add_slice_strict(&[1.0, -999.0, 2.0]),
+        Err((1, SampleOutOfRangeError))
+    );
+    assert_eq!(h.bins(), &[1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(h.add_slice_strict(&[15.0, 25.0]), Ok(()));
+    assert_eq!(h.bins(), &[1, 1, 1, 0, 0, 0, 0, 0, 0, 0]);
+}
+
 #[test]
 fn reset() {
     let mut h = Histogram10::with_const_width(0., 100.);
@@ -272,3 +799,65 @@ fn merge() {
     println!("{:?}", h1.bins());
     assert_eq!(h.bins(), h1.bins());
 }
+
+#[test]
+fn cumulative_bins() {
+    let mut h = Histogram::<3>::with_const_width(0., 3.);
+    for &x in &[0.5, 1.5, 1.5, 2.5, 2.5, 2.5] {
+        h.add(x).unwrap();
+    }
+    let cumulative: Vec<u64> = h.cumulative_bins().collect();
+    assert_eq!(cumulative, [1, 3, 6]);
+    let fractions: Vec<f64> = h.cumulative_fractions().collect();
+    assert_eq!(fractions, [1. / 6., 0.5, 1.]);
+}
+
+#[test]
+fn normalize_to_total() {
+    let mut h = Histogram::<3>::with_const_width(0., 3.);
+    h[0] = 1;
+    h[1] = 2;
+    h[2] = 3;
+    assert_eq!(h.normalize_to_total(12).bins(), &[2, 4, 6]);
+    assert_eq!(h.normalize_to_total(10).bins(), &[2, 3, 5]);
+    assert_eq!(h.normalize_to_total(0).bins(), &[0, 0, 0]);
+}
+
+#[test]
+fn exact_size_and_double_ended_iterators() {
+    let mut h = Histogram::<3>::with_const_width(0., 3.);
+    h[0] = 1;
+    h[1] = 2;
+    h[2] = 3;
+
+    let mut iter = h.iter();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    assert_eq!(iter.len(), 2);
+
+    let reversed: Vec<_> = h.iter().rev().collect();
+    assert_eq!(reversed, [((2., 3.), 3), ((1., 2.), 2), ((0., 1.), 1)]);
+    let centers: Vec<f64> = h.centers().rev().collect();
+    assert_eq!(centers, [2.5, 1.5, 0.5]);
+    assert_eq!(h.normalized_bins().len(), 3);
+    assert_eq!(h.variances().len(), 3);
+}
+
+#[test]
+fn bins_as_probabilities() {
+    let mut h = Histogram::<3>::with_const_width(0., 1.5);
+    h[0] = 1;
+    h[1] = 2;
+    h[2] = 3;
+    let probabilities: Vec<f64> = h.bins_as_probabilities().collect();
+    assert_eq!(probabilities, [1. / 6., 2. / 6., 3. / 6.]);
+    let densities: Vec<f64> = h.bins_as_densities().collect();
+    assert_eq!(densities, [2. / 6., 4. / 6., 6. / 6.]);
+}
+
+#[test]
+fn display_table() {
+    let mut h = Histogram::<2>::with_const_width(0., 2.);
+    h[1] = 2;
+    assert_eq!(format!("{:.4}", h), "[0, 1) | 0 |\n[1, 2) | 2 | ####");
+}
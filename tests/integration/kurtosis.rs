@@ -1,6 +1,6 @@
 use core::iter::Iterator;
 
-use average::{assert_almost_eq, Estimate, Kurtosis, Merge};
+use average::{assert_almost_eq, assert_kurtosis_eq, Estimate, Kurtosis, Merge};
 
 #[test]
 fn trivial() {
@@ -80,15 +80,7 @@ fn merge() {
         let mut avg_left: Kurtosis = left.iter().collect();
         let avg_right: Kurtosis = right.iter().collect();
         avg_left.merge(&avg_right);
-        assert_eq!(avg_total.len(), avg_left.len());
-        assert_almost_eq!(avg_total.mean(), avg_left.mean(), 1e-14);
-        assert_almost_eq!(
-            avg_total.sample_variance(),
-            avg_left.sample_variance(),
-            1e-14
-        );
-        assert_almost_eq!(avg_total.skewness(), avg_left.skewness(), 1e-14);
-        assert_almost_eq!(avg_total.kurtosis(), avg_left.kurtosis(), 1e-14);
+        assert_kurtosis_eq!(avg_total, avg_left, 1e-14);
     }
 }
 
@@ -107,3 +99,144 @@ fn merge_empty() {
     assert_eq!(left.skewness(), 0.);
     assert_eq!(left.kurtosis(), 0.);
 }
+
+#[test]
+fn from_variance() {
+    use average::{MeanWithError, Skewness};
+
+    // The first part is symmetric, so its third moment vanishes.
+    let first: &[f64] = &[1., 2., 3., 4., 5.];
+    let second: &[f64] = &[3.1, -4., 7.3, 1.];
+    let total: Kurtosis = first.iter().chain(second).collect();
+
+    let v: MeanWithError = first.iter().collect();
+    let mut a = Kurtosis::from_variance(v.clone());
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.mean(), 3.);
+    assert_eq!(a.sample_variance(), 2.5);
+    assert_eq!(a.skewness(), 0.);
+    assert_eq!(a.kurtosis(), 0.);
+    a.extend(second);
+    assert_eq!(a.len(), total.len());
+    assert_almost_eq!(a.mean(), total.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), total.sample_variance(), 1e-14);
+    assert_almost_eq!(a.skewness(), total.skewness(), 1e-14);
+    // The fourth moment of the first part is lost.
+    assert!(a.kurtosis() < total.kurtosis());
+
+    let s: Skewness = first.iter().collect();
+    let mut b = Kurtosis::from_skewness(s);
+    b.extend(second);
+    assert_eq!(b.len(), total.len());
+    assert_almost_eq!(b.skewness(), total.skewness(), 1e-14);
+
+    let mut c = Skewness::from_variance(v);
+    c.extend(second);
+    assert_almost_eq!(c.skewness(), total.skewness(), 1e-14);
+}
+
+#[test]
+fn to_standard_scores() {
+    let a: Kurtosis = [1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.].iter().collect();
+    let s = a.to_standard_scores();
+    assert_eq!(s.n, a.len());
+    assert_eq!(s.mean, a.mean());
+    assert_eq!(s.std_dev, a.sample_variance().sqrt());
+    assert_eq!(s.skewness, a.skewness());
+    assert_eq!(s.excess_kurtosis, a.kurtosis());
+    assert_eq!(s, s.clone());
+
+    let b: Kurtosis = [1.].iter().collect();
+    let s = b.to_standard_scores();
+    assert_eq!((s.n, s.mean, s.skewness, s.excess_kurtosis), (1, 1., 0., 0.));
+    assert!(s.std_dev.is_nan());
+
+    let s = Kurtosis::new().to_standard_scores();
+    assert_eq!(s.n, 0);
+    assert!(s.mean.is_nan() && s.std_dev.is_nan());
+    assert!(s.skewness.is_nan() && s.excess_kurtosis.is_nan());
+}
+
+#[test]
+fn joanes_gill_estimators() {
+    let data = [1., 2., 3., 4., 8., 13.];
+    let a: Kurtosis = data.iter().collect();
+    assert_eq!(a.kurtosis_g2(), a.kurtosis());
+    assert_almost_eq!(a.kurtosis_g2(), -0.5854700293415367, 1e-14);
+    assert_almost_eq!(a.kurtosis_G2(), 0.7923790810871845, 1e-14);
+    assert_almost_eq!(a.kurtosis_b2(), -1.3232430759316225, 1e-14);
+    // For small samples, G2 > g2 > b2 (for g2 > -3 (n + 1)/(n + 4)).
+    assert!(a.kurtosis_G2() > a.kurtosis_g2() && a.kurtosis_g2() > a.kurtosis_b2());
+
+    let m: average::Moments4 = data.iter().collect();
+    assert_almost_eq!(m.kurtosis_g2(), a.kurtosis_g2(), 1e-14);
+    assert_almost_eq!(m.kurtosis_G2(), a.kurtosis_G2(), 1e-14);
+    assert_almost_eq!(m.kurtosis_b2(), a.kurtosis_b2(), 1e-14);
+    assert_eq!(m.sample_excess_kurtosis(), m.kurtosis_G2());
+
+    // The estimators converge for large samples.
+    let b: Kurtosis = (0..100_000).map(|i| f64::from((i * 7919) % 1000)).collect();
+    assert_almost_eq!(b.kurtosis_G2(), b.kurtosis_g2(), 1e-3);
+    assert_almost_eq!(b.kurtosis_b2(), b.kurtosis_g2(), 1e-3);
+
+    let c: Kurtosis = data[..3].iter().collect();
+    assert!(c.kurtosis_G2().is_nan());
+    assert!(!c.kurtosis_b2().is_nan());
+    assert!(Kurtosis::new().kurtosis_g2().is_nan());
+    assert!(data[..1].iter().collect::<Kurtosis>().kurtosis_b2().is_nan());
+}
+
+#[test]
+fn sample_excess_kurtosis() {
+    use average::Moments4;
+
+    let mut a = Kurtosis::new();
+    for &x in &[1., 2., 3.] {
+        a.add(x);
+        assert!(a.sample_excess_kurtosis().is_nan());
+    }
+
+    let sequence: &[f64] = &[1., 2., 3., -4., 5., 6., 7., 8., 9., 1.];
+    let a: Kurtosis = sequence.iter().collect();
+    let m: Moments4 = sequence.iter().collect();
+    assert_eq!(a.sample_excess_kurtosis(), a.kurtosis_G2());
+    assert_almost_eq!(a.sample_excess_kurtosis(), m.sample_excess_kurtosis(), 1e-14);
+    assert!(a.sample_excess_kurtosis() != a.kurtosis());
+}
+
+#[test]
+fn jarque_bera() {
+    let mut a = Kurtosis::new();
+    for &x in &[1., 2., 3.] {
+        a.add(x);
+        assert!(a.jarque_bera().is_nan());
+        assert!(a.jarque_bera_p_value().is_nan());
+    }
+
+    // Reference values calculated like `scipy.stats.jarque_bera`.
+    let a: Kurtosis = [1., 2., 3., -4., 5., 6., 7., 8., 9., 1.].iter().collect();
+    assert_almost_eq!(a.jarque_bera(), 0.5172425831141505, 1e-14);
+    assert_almost_eq!(a.jarque_bera_p_value(), 0.772115374293423, 1e-14);
+
+    let constant: Kurtosis = [2., 2., 2., 2.].iter().collect();
+    assert_eq!(constant.jarque_bera(), 0.);
+    assert_eq!(constant.jarque_bera_p_value(), 1.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn invalid_serde() {
+    let a: Kurtosis = [1., 2., 4., 8.].iter().collect();
+    let mut value = serde_json::to_value(&a).unwrap();
+    assert!(serde_json::from_value::<Kurtosis>(value.clone()).is_ok());
+
+    value["sum_4"] = serde_json::json!(-1.0);
+    let err = serde_json::from_value::<Kurtosis>(value).unwrap_err();
+    assert!(err.to_string().contains("negative sum of fourth powers"), "{}", err);
+
+    let err = serde_json::from_str::<Kurtosis>(
+        "{\"avg\":{\"avg\":{\"avg\":{\"avg\":1.0,\"n\":1},\"sum_2\":0.0},\"sum_3\":0.0},\"sum_4\":2.0}",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("sample of size less than 2"), "{}", err);
+}
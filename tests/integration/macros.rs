@@ -1,6 +1,6 @@
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp))]
 
-use average::{concatenate, Estimate, Max, Min};
+use average::{assert_estimators_eq, concatenate, Estimate, Max, Min, Variance};
 
 concatenate!(MinMax, [Min, min], [Max, max]);
 
@@ -34,6 +34,29 @@ fn concatenate_simple() {
     }
 }
 
+#[test]
+fn concatenate_merge() {
+    use average::Merge;
+
+    let sequence: &[f64] = &[4., 2., 9., 1., 7., 3., 8.];
+    for mid in 0..=sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut s: MinMax = left.iter().copied().collect();
+        let right: MinMax = right.iter().copied().collect();
+        s.merge(&right);
+        assert_eq!(s.min(), 1.0);
+        assert_eq!(s.max(), 9.0);
+    }
+
+    concatenate!(MeanMax, [Variance, variance, mean, sample_variance], [Max, max, max]);
+    let mut a: MeanMax = (1..4).map(f64::from).collect();
+    let b: MeanMax = (4..6).map(f64::from).collect();
+    a.merge(&b);
+    assert_eq!(a.mean(), 3.0);
+    assert_eq!(a.sample_variance(), 2.5);
+    assert_eq!(a.max(), 5.0);
+}
+
 #[test]
 fn concatenate_moments_max() {
     use average::{Max, Variance};
@@ -51,6 +74,24 @@ fn concatenate_moments_max() {
     assert_eq!(e.max(), 5.0);
 }
 
+#[test]
+fn concatenate_len() {
+    concatenate!(MinMaxLen, [Min, min, min], [Max, max, max], [Variance, variance, mean, len]);
+
+    let mut e = MinMaxLen::new();
+    assert_eq!(e.len(), 0);
+    assert!(e.is_empty());
+
+    for i in 1..6 {
+        e.add(f64::from(i));
+    }
+    assert_eq!(e.len(), 5);
+    assert!(!e.is_empty());
+    assert_eq!(e.min(), 1.0);
+    assert_eq!(e.max(), 5.0);
+    assert_eq!(e.mean(), 3.0);
+}
+
 #[cfg(any(feature = "std", feature = "libm"))]
 #[test]
 fn concatenate_moments_quantile() {
@@ -84,3 +125,35 @@ fn concatenate_pub() {
     assert_eq!(s.min(), 1.0);
     assert_eq!(s.max(), 5.0);
 }
+
+#[test]
+fn assert_estimators_eq_simple() {
+    let a: Variance = (1..6).map(f64::from).collect();
+    let b: Variance = (1..6).rev().map(f64::from).collect();
+    assert_estimators_eq!(a, b, 1e-14);
+    assert_estimators_eq!(Variance::new(), Variance::new(), 1e-14);
+}
+
+#[test]
+#[should_panic(expected = "estimators differ in `sample_variance()`")]
+fn assert_estimators_eq_diverging() {
+    let a: Variance = [1., 2., 3.].iter().collect();
+    let b: Variance = [0., 2., 4.].iter().collect();
+    assert_estimators_eq!(a, b, 1e-14);
+}
+
+#[test]
+#[should_panic(expected = "estimators differ in `mean()`")]
+fn assert_estimators_eq_nan() {
+    let a: Variance = [1., f64::NAN].iter().collect();
+    let b: Variance = [1., 2.].iter().collect();
+    assert_estimators_eq!(a, b, 1e-14);
+}
+
+#[test]
+#[should_panic(expected = "estimators differ in `len()`")]
+fn assert_estimators_eq_len() {
+    let a: Variance = [1., 2., 3.].iter().collect();
+    let b: Variance = [1., 2., 3., 2.].iter().collect();
+    assert_estimators_eq!(a, b, 1e-14);
+}
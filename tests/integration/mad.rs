@@ -0,0 +1,36 @@
+use rand::SeedableRng;
+use rand_distr::Distribution;
+
+use average::{assert_almost_eq, Estimate, MedianAbsoluteDeviation};
+
+#[test]
+fn trivial() {
+    let mut a = MedianAbsoluteDeviation::new();
+    assert!(a.is_empty());
+    assert!(a.median().is_nan());
+    assert!(a.mad().is_nan());
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.median(), 1.);
+    assert_eq!(a.mad(), 0.);
+    assert_eq!(a.estimate(), a.mad());
+}
+
+#[test]
+fn extend() {
+    let mut a = MedianAbsoluteDeviation::default();
+    a.extend([2., 2., 2., 2., 2., 2.].iter());
+    assert_eq!(a.len(), 6);
+    assert_eq!(a.median(), 2.);
+    assert_eq!(a.mad(), 0.);
+}
+
+#[test]
+fn normal_distribution() {
+    let normal = rand_distr::Normal::new(2., 3.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let a: MedianAbsoluteDeviation = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+    assert_eq!(a.len(), 100_000);
+    assert_almost_eq!(a.median(), 2., 0.05);
+    assert_almost_eq!(1.4826 * a.mad(), 3., 0.05);
+}
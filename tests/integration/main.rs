@@ -1,16 +1,30 @@
 #![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
 #![allow(clippy::float_cmp)]
 
+mod add_assign;
+mod add_slice;
+mod autocorrelation;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod circular;
+mod ewma;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod geometric_mean;
+mod harmonic_mean;
 mod histogram;
 #[cfg(feature = "nightly")]
 mod histogram_const;
 #[cfg(any(feature = "std", feature = "libm"))]
 mod kurtosis;
 mod macros;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod mad;
 mod max;
 mod mean;
 mod min;
 mod moments;
+mod population;
+mod reset;
+mod rolling;
 #[cfg(any(feature = "std", feature = "libm"))]
 mod quantile;
 #[cfg(any(feature = "std", feature = "libm"))]
@@ -19,5 +33,9 @@ mod random;
 mod skewness;
 #[cfg(feature = "std")]
 mod streaming_stats;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod summary;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod trimmed_mean;
 mod weighted_mean;
 mod covariance;
@@ -52,3 +52,21 @@ fn merge_empty() {
     left.add(1.);
     assert_eq!(left.max(), 1.);
 }
+
+#[test]
+fn from_f64() {
+    let mut a = Max::from(1.);
+    assert_eq!(a.max(), 1.);
+    a.add(2.);
+    assert_eq!(a.max(), 2.);
+}
+
+#[test]
+fn partial_eq() {
+    let data = [1., 5., 2., 4., 3.];
+    let a: Max = data.iter().collect();
+    let b: Max = data.iter().rev().collect();
+    assert_eq!(a, b);
+    assert_eq!(Max::new(), Max::default());
+    assert_ne!(a, Max::new());
+}
@@ -1,6 +1,5 @@
 use core::iter::Iterator;
 
-#[cfg(any(feature = "std", feature = "libm"))]
 use average::assert_almost_eq;
 use average::{Estimate, MeanWithError, Merge};
 
@@ -86,6 +85,30 @@ fn numerically_unstable() {
     assert_eq!(a.sample_variance(), 30.);
 }
 
+#[test]
+fn from_two_pass() {
+    let big = 1e9;
+    let sample: Vec<f64> = (1..11).map(|i| big + 0.1 * f64::from(i)).collect();
+    // Calculated with exact rational arithmetic for the rounded sample.
+    let exact = 0.09166666136847729;
+    let a = MeanWithError::from_two_pass(&sample);
+    let b: MeanWithError = sample.iter().collect();
+    assert_eq!(a.len(), 10);
+    assert_almost_eq!(a.mean(), b.mean(), 1e-6);
+    assert_almost_eq!(a.sample_variance(), exact, 1e-16);
+    let error_two_pass = (a.sample_variance() - exact).abs();
+    let error_online = (b.sample_variance() - exact).abs();
+    assert!(error_two_pass < error_online);
+
+    let mut c = MeanWithError::from_two_pass(&[1., 2., 3.]);
+    c.add(4.);
+    c.add(5.);
+    assert_eq!(c.len(), 5);
+    assert_eq!(c.mean(), 3.);
+    assert_eq!(c.sample_variance(), 2.5);
+    assert!(MeanWithError::from_two_pass(&[]).is_empty());
+}
+
 #[test]
 fn merge() {
     let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
@@ -112,3 +135,467 @@ fn merge_empty() {
     assert_eq!(left.mean(), 1.);
     assert_eq!(left.sample_variance(), 0.);
 }
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn known_variance() {
+    use average::Mean;
+
+    let a = Mean::new();
+    assert!(a.standard_error_with_known_variance(1.).is_nan());
+    let (lower, upper) = a.confidence_interval_with_known_variance(1., 1.96);
+    assert!(lower.is_nan());
+    assert!(upper.is_nan());
+
+    let a: Mean = (1..17).map(f64::from).collect();
+    assert_eq!(a.standard_error_with_known_variance(1.), 0.25);
+    assert_eq!(a.standard_error_with_known_variance(4.), 0.5);
+    let (lower, upper) = a.confidence_interval_with_known_variance(1., 1.96);
+    assert_almost_eq!(upper - lower, 2. * 1.96 / num_traits::Float::sqrt(16.), 1e-15);
+    assert_almost_eq!(0.5 * (lower + upper), a.mean(), 1e-15);
+}
+
+#[test]
+fn edge_cases() {
+    let mut a = MeanWithError::new();
+    assert!(a.sample_variance().is_nan());
+    assert!(a.population_variance().is_nan());
+    assert!(a.variance_of_mean().is_nan());
+    assert!(a.variance_of_mean_unbiased().is_nan());
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert!(a.error().is_nan());
+
+    a.add(2.0);
+    assert!(a.sample_variance().is_nan());
+    assert_eq!(a.population_variance(), 0.0);
+    assert_eq!(a.variance_of_mean(), 0.0);
+    assert!(a.variance_of_mean_unbiased().is_nan());
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert_eq!(a.error(), 0.0);
+
+    a.add(4.0);
+    assert_eq!(a.sample_variance(), 2.0);
+    assert_eq!(a.population_variance(), 1.0);
+    assert_eq!(a.variance_of_mean(), 1.0);
+    assert_eq!(a.variance_of_mean_unbiased(), 1.0);
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert_eq!(a.error(), 1.0);
+}
+
+#[test]
+fn update_with_removed() {
+    let window = 3;
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let mut a: MeanWithError = sequence[..window].iter().collect();
+    for i in window..sequence.len() {
+        a.update_with_removed(sequence[i - window], sequence[i]);
+        let expected: MeanWithError = sequence[i + 1 - window..=i].iter().collect();
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-14);
+        assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-13);
+    }
+}
+
+#[test]
+fn update_with_removed_numerically_unstable() {
+    let window = 3;
+    let sequence: &[f64] = &[1e15, 1e15, 1e15, 1., 1., 1.];
+    let mut a: MeanWithError = sequence[..window].iter().collect();
+    for i in window..sequence.len() {
+        a.update_with_removed(sequence[i - window], sequence[i]);
+    }
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.sample_variance(), 0.);
+}
+
+#[test]
+fn try_merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(3);
+    let avg_total: MeanWithError = sequence.iter().collect();
+    let mut avg_left: MeanWithError = left.iter().collect();
+    let avg_right: MeanWithError = right.iter().collect();
+    avg_left.try_merge(&avg_right).unwrap();
+    assert_eq!(avg_total.len(), avg_left.len());
+    assert_eq!(avg_total.mean(), avg_left.mean());
+    assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
+    assert_eq!(avg_left.n_as_f64(), 9.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn try_merge_overflow() {
+    use average::OverflowError;
+
+    let mut a: MeanWithError =
+        serde_json::from_str("{\"avg\":{\"avg\":3.0,\"n\":18446744073709551615},\"sum_2\":10.0}")
+            .unwrap();
+    assert_eq!(a.len(), u64::MAX);
+    assert_eq!(a.n_as_f64(), f64::INFINITY);
+    let b: MeanWithError = (1..6).map(f64::from).collect();
+    assert_eq!(
+        a.try_merge(&b),
+        Err(OverflowError {
+            field: "n",
+            left: u64::MAX,
+            right: 5
+        })
+    );
+    #[cfg(feature = "std")]
+    {
+        let err: Box<dyn std::error::Error> = a.try_merge(&b).unwrap_err().into();
+        assert_eq!(
+            err.to_string(),
+            "overflow of `n` when adding 5 to 18446744073709551615"
+        );
+    }
+    assert_eq!(a.len(), u64::MAX);
+    assert_eq!(a.mean(), 3.);
+
+    let c: MeanWithError =
+        serde_json::from_str("{\"avg\":{\"avg\":3.0,\"n\":9007199254740992},\"sum_2\":10.0}")
+            .unwrap();
+    assert_eq!(c.n_as_f64(), 9007199254740992.);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn t_critical() {
+    use average::t_critical;
+
+    assert_almost_eq!(t_critical(9, 0.95), 2.262157162798205, 1e-6);
+    assert_almost_eq!(t_critical(1, 0.95), 12.706204736174693, 1e-6);
+    assert_almost_eq!(t_critical(2, 0.99), 9.92484320091829, 1e-6);
+    assert_almost_eq!(t_critical(4, 0.9), 2.1318467863266495, 1e-6);
+    assert_almost_eq!(t_critical(29, 0.95), 2.045229642132704, 1e-6);
+    assert_almost_eq!(t_critical(120, 0.999), 3.3734537685625336, 1e-6);
+    assert_almost_eq!(t_critical(1000, 0.95), 1.962339080826408, 1e-6);
+    assert_eq!(t_critical(5, 0.), 0.);
+    assert!(t_critical(0, 0.95).is_nan());
+    assert!(t_critical(5, 1.).is_nan());
+    assert!(t_critical(5, -0.5).is_nan());
+
+    let a: MeanWithError = (1..11).map(f64::from).collect();
+    let (lower, upper) = a.confidence_interval_t(0.95);
+    assert_almost_eq!(lower, 5.5 - 2.262157162798205 * a.error(), 1e-6);
+    assert_almost_eq!(upper, 5.5 + 2.262157162798205 * a.error(), 1e-6);
+    let b: MeanWithError = [1.].iter().collect();
+    assert!(b.confidence_interval_t(0.95).0.is_nan());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn standard_deviation_and_coefficient_of_variation() {
+    use average::Variance;
+
+    let a: Variance = (1..=5).map(f64::from).collect();
+    assert_eq!(a.sample_variance(), 2.5);
+    assert_eq!(a.population_variance(), 2.);
+    assert_eq!(a.sample_standard_deviation(), 2.5f64.sqrt());
+    assert_eq!(a.population_standard_deviation(), 2f64.sqrt());
+    assert_eq!(a.coefficient_of_variation(), 2.5f64.sqrt() / 3.);
+
+    let single: Variance = [1.].iter().collect();
+    assert!(single.sample_standard_deviation().is_nan());
+    assert_eq!(single.population_standard_deviation(), 0.);
+    assert!(single.coefficient_of_variation().is_nan());
+
+    let zero_mean: Variance = [-1., 1.].iter().collect();
+    assert!(zero_mean.coefficient_of_variation().is_nan());
+
+    let empty = Variance::new();
+    assert!(empty.population_standard_deviation().is_nan());
+}
+
+#[test]
+fn difference() {
+    use average::Mean;
+
+    let a: Mean = [1., 2., 3., 4.].iter().collect();
+    let b: Mean = [1., 2.].iter().collect();
+    let d = Mean::difference(&a, &b);
+    assert_eq!(d.mean(), 1.);
+    assert_eq!(d.len(), 2);
+    assert_eq!(b.sub_mean(&a).unwrap().mean(), -1.);
+    assert!(a.sub_mean(&Mean::new()).is_none());
+    assert!(Mean::difference(&a, &Mean::new()).mean().is_nan());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn combined_variance() {
+    let a: MeanWithError = [1., 2., 3., 4., 5.].iter().collect();
+    let b: MeanWithError = [2., 4., 4., 5., 7., 8., 11.].iter().collect();
+    let c = MeanWithError::combined_variance(&a, &b);
+    assert_eq!(c.len(), 5);
+    assert_eq!(c.mean(), a.mean() - b.mean());
+    let welch = (a.sample_variance() / 5. + b.sample_variance() / 7.).sqrt();
+    assert_almost_eq!(c.error(), welch, 1e-14);
+    assert_almost_eq!(c.error(), f64::sqrt(a.error().powi(2) + b.error().powi(2)), 1e-14);
+
+    let d: MeanWithError = [1.].iter().collect();
+    assert!(MeanWithError::combined_variance(&a, &d).is_empty());
+}
+
+#[test]
+fn neg() {
+    use average::Mean;
+
+    let m: Mean = [1., 2., 3.].iter().collect();
+    let mut n = -m.clone();
+    assert_eq!(n.mean(), -m.mean());
+    assert_eq!(n.len(), 3);
+    n.add(-6.);
+    assert_eq!(n.mean(), -3.);
+
+    let v: MeanWithError = [1., 2., 3., 4., 5.].iter().collect();
+    let mut w = -v.clone();
+    assert_eq!(w.mean(), -v.mean());
+    assert_eq!(w.sample_variance(), v.sample_variance());
+    w.add(-6.);
+    let expected: MeanWithError = [-1., -2., -3., -4., -5., -6.].iter().collect();
+    assert_almost_eq!(w.mean(), expected.mean(), 1e-15);
+    assert_almost_eq!(w.sample_variance(), expected.sample_variance(), 1e-15);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn jackknife_and_bootstrap() {
+    let data = [1., 3., 4., 4., 7., 9., 12.];
+    let a: MeanWithError = data.iter().collect();
+    assert_eq!(a.jackknife_std_error(), a.error());
+
+    // The explicit delete-one jackknife agrees with the analytical formula
+    // because the mean is a linear statistic.
+    let n = data.len() as f64;
+    let sum: f64 = data.iter().sum();
+    let sum_sq_dev: f64 = data
+        .iter()
+        .map(|x| (sum - x) / (n - 1.) - a.mean())
+        .map(|d| d * d)
+        .sum();
+    assert_almost_eq!(((n - 1.) / n * sum_sq_dev).sqrt(), a.error(), 1e-14);
+
+    // The bootstrap uses the population variance and is biased downwards.
+    assert_almost_eq!(a.bootstrap_std_error_approx(), ((n - 1.) / n).sqrt() * a.error(), 1e-15);
+
+    let b: MeanWithError = [1.].iter().collect();
+    assert!(b.jackknife_std_error().is_nan());
+    assert_eq!(b.bootstrap_std_error_approx(), 0.);
+    assert!(MeanWithError::new().bootstrap_std_error_approx().is_nan());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn t_statistic() {
+    let a: MeanWithError = [1., 2., 3., 4., 5.].iter().collect();
+    assert_eq!(a.t_statistic(3.), 0.);
+    let expected = (3. - 4.) / (2.5f64 / 5.).sqrt();
+    assert_almost_eq!(a.t_statistic(4.), expected, 1e-15);
+    assert!(MeanWithError::new().t_statistic(0.).is_nan());
+    assert!([1.].iter().collect::<MeanWithError>().t_statistic(0.).is_nan());
+
+    let b: MeanWithError = [2., 4., 4., 5., 7., 8., 11.].iter().collect();
+    let welch = (a.mean() - b.mean())
+        / (a.sample_variance() / 5. + b.sample_variance() / 7.).sqrt();
+    assert_almost_eq!(a.t_statistic_two_sample(&b), welch, 1e-15);
+    assert_almost_eq!(b.t_statistic_two_sample(&a), -welch, 1e-15);
+    assert!(a.t_statistic_two_sample(&MeanWithError::new()).is_nan());
+}
+
+#[test]
+fn from_f64() {
+    use average::Mean;
+
+    let a = Mean::from(3.0);
+    let b = Mean::new_with_sample(3.0);
+    assert_eq!((a.mean(), a.len()), (b.mean(), b.len()));
+    assert_eq!((a.mean(), a.len()), (3.0, 1));
+
+    let m: Mean = [1., 2., 4.].iter().collect();
+    let expected = m.mean();
+    let x: f64 = m.into();
+    assert_eq!(x, expected);
+    assert!(f64::from(Mean::new()).is_nan());
+}
+
+#[test]
+fn center_and_compute() {
+    let mut data = [1e9 + 0.1, 1e9 + 0.4, 1e9 + 0.2, 1e9 + 0.7, 1e9 + 0.3];
+    let original: MeanWithError = data.iter().collect();
+    let a = MeanWithError::center_and_compute(&mut data);
+    assert_almost_eq!(data.iter().sum::<f64>(), 0., 1e-6);
+    assert_almost_eq!(a.mean(), 0., 1e-7);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sample_variance(), data.iter().collect::<MeanWithError>().sample_variance());
+    assert_almost_eq!(a.sample_variance(), original.sample_variance(), 1e-6);
+
+    let mut empty: [f64; 0] = [];
+    assert!(MeanWithError::center_and_compute(&mut empty).is_empty());
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn invalid_serde() {
+    let valid = "{\"avg\":{\"avg\":3.0,\"n\":5},\"sum_2\":10.0}";
+    let a: MeanWithError = serde_json::from_str(valid).unwrap();
+    assert_eq!(a.sample_variance(), 2.5);
+
+    for (json, reason) in &[
+        ("{\"avg\":{\"avg\":3.0,\"n\":5},\"sum_2\":-10.0}", "negative sum of squares"),
+        (
+            "{\"avg\":{\"avg\":3.0,\"n\":1},\"sum_2\":10.0}",
+            "nonzero sum of squares for a sample of size less than 2",
+        ),
+        (
+            "{\"avg\":{\"avg\":3.0,\"n\":0},\"sum_2\":1.0}",
+            "nonzero sum of squares for a sample of size less than 2",
+        ),
+    ] {
+        let err = serde_json::from_str::<MeanWithError>(json).unwrap_err();
+        assert!(err.to_string().starts_with("invalid estimator state"), "{}", err);
+        assert!(err.to_string().contains(reason), "{}", err);
+    }
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn update_with_removed_serde() {
+    // A window of size 1 must not accumulate rounding errors in the sum of
+    // squares, which would make the state invalid. The large value is
+    // absorbed by the running mean, which then drifts from the observation.
+    let mut a: MeanWithError = [1e-3].iter().collect();
+    let mut x_old = 1e-3;
+    for &x_new in &[1e16, 1., 3.] {
+        a.update_with_removed(x_old, x_new);
+        x_old = x_new;
+        assert_eq!(a.population_variance(), 0.);
+        let json = serde_json::to_string(&a).unwrap();
+        let b: MeanWithError = serde_json::from_str(&json).unwrap();
+        assert_eq!(b.len(), 1);
+        assert_eq!(b.population_variance(), 0.);
+    }
+}
+
+#[test]
+fn partial_eq() {
+    use average::{Mean, Variance};
+
+    let data = [1., 5., 2., 4., 3.];
+    let a: Mean = data.iter().collect();
+    let b: Mean = data.iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(Mean::new(), Mean::default());
+    let c: Mean = data[1..].iter().collect();
+    assert_ne!(a, c);
+
+    let a: Variance = data.iter().collect();
+    let b: Variance = data.iter().collect();
+    assert_eq!(a, b);
+    assert_eq!(Variance::new(), Variance::default());
+    // Same mean and sample size, different variance.
+    let c: Variance = [2., 4., 3., 3., 3.].iter().collect();
+    assert_eq!(a.mean(), c.mean());
+    assert_ne!(a, c);
+
+    let mut nan = Mean::new();
+    nan.add(f64::NAN);
+    assert_ne!(nan, nan.clone());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn cohen_d() {
+    use average::{cohen_d, cohen_d_confidence_interval, Variance};
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    // Shifting a sample by its standard deviation does not change the pooled
+    // standard deviation.
+    let a: Variance = [1., 4., 2., 8., 5.].iter().collect();
+    let s = a.sample_standard_deviation();
+    let b: Variance = [1., 4., 2., 8., 5.].iter().map(|x| x - s).collect();
+    assert_almost_eq!(cohen_d(&a, &b), 1., 1e-14);
+    assert_almost_eq!(cohen_d(&b, &a), -1., 1e-14);
+
+    let a: Variance = [4., 5., 6.].iter().collect();
+    let b: Variance = [3., 4., 5.].iter().collect();
+    assert_eq!(cohen_d(&a, &b), 1.);
+    let (lower, upper) = cohen_d_confidence_interval(&a, &b, 0.95);
+    let error = 1.959963984540054 * 0.75f64.sqrt();
+    assert_almost_eq!(lower, 1. - error, 1e-8);
+    assert_almost_eq!(upper, 1. + error, 1e-8);
+    assert!(cohen_d_confidence_interval(&a, &b, 1.).0.is_nan());
+
+    let normal = rand_distr::Normal::new(0., 2.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let a: Variance = (0..10_000).map(|_| normal.sample(&mut rng) + 2.).collect();
+    let b: Variance = (0..10_000).map(|_| normal.sample(&mut rng)).collect();
+    assert_almost_eq!(cohen_d(&a, &b), 1., 0.05);
+    let (lower, upper) = cohen_d_confidence_interval(&a, &b, 0.95);
+    assert!(lower < 1. && 1. < upper);
+
+    let single: Variance = [1.].iter().collect();
+    assert!(cohen_d(&a, &single).is_nan());
+    assert!(cohen_d_confidence_interval(&single, &b, 0.95).1.is_nan());
+}
+
+#[test]
+fn variance_aggregated() {
+    use average::{Merge, Variance};
+
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(4);
+    let right_mean = right.iter().sum::<f64>() / right.len() as f64;
+    let right_sum: f64 = right.iter().sum();
+    let right_sum_sq_dev: f64 = right.iter().map(|x| (x - right_mean) * (x - right_mean)).sum();
+
+    let mut merged: Variance = left.iter().collect();
+    merged.merge(&right.iter().collect());
+    let mut aggregated: Variance = left.iter().collect();
+    aggregated.add_aggregated(right_sum, right_sum_sq_dev, right.len() as u64);
+    assert_eq!(aggregated, merged);
+    assert_eq!(aggregated.len(), 9);
+    assert_eq!(aggregated.sample_variance(), 7.5);
+
+    let from: Variance = Variance::from_aggregated(right_sum, right_sum_sq_dev, 5);
+    assert_eq!(from, right.iter().collect());
+    assert!(Variance::from_aggregated(0., 0., 0).is_empty());
+    aggregated.add_aggregated(0., 0., 0);
+    assert_eq!(aggregated, merged);
+
+    // Invalid sums of squares are replaced with zero.
+    let single = Variance::from_aggregated(3., 0.5, 1);
+    assert_eq!(single, [3.].iter().collect());
+    assert_eq!(single.population_variance(), 0.);
+    let negative = Variance::from_aggregated(6., -1e-16, 2);
+    assert_eq!(negative, [3., 3.].iter().collect());
+    assert_eq!(negative.sample_variance(), 0.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn from_aggregated_serde() {
+    use average::Variance;
+
+    let a = Variance::from_aggregated(3., 0.5, 1);
+    let json = serde_json::to_string(&a).unwrap();
+    let b: Variance = serde_json::from_str(&json).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(b.population_variance(), 0.);
+}
+
+#[test]
+fn display() {
+    use average::{Mean, Variance};
+
+    let a: Mean = (1..6).map(f64::from).collect();
+    assert_eq!(format!("{}", a), "Mean(n=5, μ=3)");
+    assert_eq!(format!("{:.1}", a), "Mean(n=5, μ=3.0)");
+    assert_eq!(format!("{}", Mean::new()), "Mean(n=0, μ=NaN)");
+
+    let v: Variance = (1..6).map(f64::from).collect();
+    assert_eq!(format!("{}", v), "Variance(n=5, μ=3, s²=2.5)");
+    assert_eq!(format!("{:.2}", v), "Variance(n=5, μ=3.00, s²=2.50)");
+}
@@ -52,3 +52,21 @@ fn merge_empty() {
     left.add(1.);
     assert_eq!(left.min(), 1.);
 }
+
+#[test]
+fn from_f64() {
+    let mut a = Min::from(2.);
+    assert_eq!(a.min(), 2.);
+    a.add(1.);
+    assert_eq!(a.min(), 1.);
+}
+
+#[test]
+fn partial_eq() {
+    let data = [1., 5., 2., 4., 3.];
+    let a: Min = data.iter().collect();
+    let b: Min = data.iter().rev().collect();
+    assert_eq!(a, b);
+    assert_eq!(Min::new(), Min::default());
+    assert_ne!(a, Min::new());
+}
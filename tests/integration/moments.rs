@@ -80,6 +80,30 @@ fn simple_extend() {
     }
 }
 
+#[test]
+fn sum() {
+    let a: Moments4 = (1..6).map(f64::from).collect();
+    let b: Moments4 = (1..6).map(f64::from).sum();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.mean(), b.mean());
+    assert_eq!(a.central_moment(2), b.central_moment(2));
+    let c: Moments4 = [1., 2., 3., 4., 5.].iter().sum();
+    assert_eq!(a.central_moment(2), c.central_moment(2));
+
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let total: Moments4 = sequence.iter().collect();
+    let parts: Vec<Moments4> = sequence.chunks(3).map(|c| c.iter().collect()).collect();
+    let d: Moments4 = parts.iter().sum();
+    let e: Moments4 = parts.into_iter().sum();
+    for m in [d, e] {
+        assert_eq!(m.len(), total.len());
+        assert_almost_eq!(m.mean(), total.mean(), 1e-14);
+        assert_almost_eq!(m.central_moment(2), total.central_moment(2), 1e-13);
+        assert_almost_eq!(m.central_moment(4), total.central_moment(4), 1e-11);
+    }
+    assert!(core::iter::empty::<Moments4>().sum::<Moments4>().is_empty());
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn simple_serde() {
@@ -158,3 +182,43 @@ fn merge_empty() {
     assert_eq!(left.central_moment(3), 0.);
     assert_eq!(left.central_moment(4), 0.);
 }
+
+#[test]
+fn raw_moments_and_cumulants() {
+    let data = [1., 2., 3., 4., 5., 1.];
+    let a: Moments4 = data.iter().collect();
+    let n = data.len() as f64;
+    for p in 0..=4 {
+        let expected = data.iter().map(|x| x.powi(p as i32)).sum::<f64>() / n;
+        assert_almost_eq!(a.raw_moment(p), expected, 1e-12);
+    }
+
+    let k = a.cumulants();
+    assert_eq!(k[0], a.mean());
+    assert_almost_eq!(k[1], a.central_moment(2), 1e-14);
+    assert_almost_eq!(k[2], a.central_moment(3), 1e-14);
+    let m2 = a.central_moment(2);
+    assert_almost_eq!(k[3], a.central_moment(4) - 3. * m2 * m2, 1e-14);
+
+    let empty = Moments4::new();
+    assert_eq!(empty.raw_moment(0), 1.);
+    assert!(empty.raw_moment(2).is_nan());
+    assert!(empty.cumulants().iter().all(|k| k.is_nan()));
+}
+
+#[test]
+fn cumulants_normal() {
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    // All cumulants of the normal distribution beyond the second vanish.
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let a: Moments4 = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+    let k = a.cumulants();
+    assert_almost_eq!(k[0], 0., 0.01);
+    assert_almost_eq!(k[1], 1., 0.02);
+    assert_almost_eq!(k[2], 0., 0.05);
+    assert_almost_eq!(k[3], 0., 0.05);
+    assert_almost_eq!(a.raw_moment(2), 1., 0.02);
+}
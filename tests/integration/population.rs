@@ -0,0 +1,79 @@
+use core::iter::Iterator;
+
+use average::{assert_almost_eq, Estimate, Max, MeanWithError, Merge, Min, Population};
+
+#[test]
+fn trivial() {
+    let a = Population::new();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.sum(), 0.);
+    assert!(a.mean().is_nan());
+    assert!(a.sample_variance().is_nan());
+    assert_eq!(a.min(), f64::INFINITY);
+    assert_eq!(a.max(), f64::NEG_INFINITY);
+}
+
+#[test]
+fn consistent() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let a: Population = sequence.iter().collect();
+    let var: MeanWithError = sequence.iter().collect();
+    let min: Min = sequence.iter().collect();
+    let max: Max = sequence.iter().collect();
+    assert_eq!(a.len(), var.len());
+    assert_eq!(a.sum(), sequence.iter().sum::<f64>());
+    assert_almost_eq!(a.sum(), a.mean() * a.len() as f64, 1e-14);
+    assert_eq!(a.mean(), var.mean());
+    assert_eq!(a.sample_variance(), var.sample_variance());
+    assert_eq!(a.population_variance(), var.population_variance());
+    #[cfg(any(feature = "std", feature = "libm"))]
+    {
+        assert_eq!(a.standard_deviation(), var.sample_variance().sqrt());
+        assert_eq!(a.standard_error(), var.error());
+    }
+    assert_eq!(a.min(), min.min());
+    assert_eq!(a.max(), max.max());
+    assert_eq!(a.estimate(), a.mean());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Population = sequence.iter().collect();
+        let mut merged: Population = left.iter().collect();
+        merged.merge(&right.iter().collect());
+        assert_eq!(total.len(), merged.len());
+        assert_almost_eq!(total.sum(), merged.sum(), 1e-14);
+        assert_almost_eq!(total.mean(), merged.mean(), 1e-14);
+        assert_almost_eq!(total.sample_variance(), merged.sample_variance(), 1e-14);
+        assert_eq!(total.min(), merged.min());
+        assert_eq!(total.max(), merged.max());
+    }
+}
+
+#[test]
+fn extend() {
+    let mut a: Population = (1..4).map(f64::from).collect();
+    a.extend([4., 5.].iter());
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sum(), 15.);
+    assert_eq!(a.max(), 5.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: Population = (1..6).map(f64::from).collect();
+    let b = serde_json::to_string(&a).unwrap();
+    assert_eq!(
+        &b,
+        "{\"var\":{\"avg\":{\"avg\":3.0,\"n\":5},\"sum_2\":10.0},\"sum\":15.0,\"min\":{\"x\":1.0},\"max\":{\"x\":5.0}}"
+    );
+    let c: Population = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.sum(), 15.);
+    assert_eq!(c.sample_variance(), 2.5);
+    assert_eq!(c.min(), 1.);
+}
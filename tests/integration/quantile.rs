@@ -395,3 +395,155 @@ fn percentile_99() {
     }
     assert!((q.quantile() - 9.).abs() < TOL);
 }
+
+#[test]
+fn merge_by_averaging() {
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    let normal = rand_distr::Normal::new(2., 1.).unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let mut estimators = Vec::new();
+    for _ in 0..10 {
+        let mut q = Quantile::new(0.9);
+        for _ in 0..1_000 {
+            q.add(normal.sample(&mut rng));
+        }
+        estimators.push(q);
+    }
+    // An estimator with too few samples for averaging.
+    let mut small = Quantile::new(0.9);
+    small.add(2.);
+    small.add(3.);
+    estimators.push(small);
+
+    let q = Quantile::merge_by_averaging(&estimators);
+    assert_eq!(q.len(), 10_002);
+    assert_eq!(q.p(), 0.9);
+    // The 0.9-quantile of the normal distribution is 1.2816 standard
+    // deviations above the mean.
+    assert!((q.quantile() - 3.2816).abs() < 0.05);
+
+    let mut r = Quantile::merge_by_averaging(&estimators[..1]);
+    assert_eq!(r.len(), 1_000);
+    assert!((r.quantile() - estimators[0].quantile()).abs() < 0.1);
+    r.add(1.);
+    assert_eq!(r.len(), 1_001);
+
+    let s = Quantile::merge_by_averaging(&estimators[10..]);
+    assert_eq!(s.len(), 2);
+    assert_eq!(s.quantile(), 3.);
+}
+
+#[test]
+#[should_panic(expected = "Only estimators of the same quantile can be merged")]
+fn merge_by_averaging_different_p() {
+    Quantile::merge_by_averaging(&[Quantile::new(0.5), Quantile::new(0.9)]);
+}
+
+#[test]
+fn exceedance_probability() {
+    let mut q = Quantile::new(0.5);
+    assert!(q.exceedance_probability(0.).is_nan());
+    q.add(1.);
+    q.add(3.);
+    q.add(2.);
+    assert_eq!(q.exceedance_probability(0.), 1.);
+    assert_eq!(q.exceedance_probability(1.5), 2. / 3.);
+    assert_eq!(q.exceedance_probability(3.), 0.);
+
+    for i in 0..10_000 {
+        q.add(f64::from((i * 7919) % 1000));
+    }
+    assert!((q.exceedance_probability(q.quantile()) - 0.5).abs() < 1e-3);
+    assert!((q.exceedance_probability(250.) - 0.75).abs() < 0.05);
+    assert_eq!(q.exceedance_probability(-1.), 1.);
+    assert_eq!(q.exceedance_probability(1000.), 0.);
+    assert!(q.exceedance_probability(f64::NAN).is_nan());
+}
+
+#[test]
+fn new_checked() {
+    use core::convert::TryFrom;
+
+    for &p in &[-0.1, 1.1, f64::NAN, f64::INFINITY] {
+        let err = Quantile::new_checked(p).unwrap_err();
+        assert!(err.p.is_nan() || err.p == p);
+        assert_eq!(Quantile::try_from(p).unwrap_err().to_string(), err.to_string());
+    }
+    assert_eq!(
+        Quantile::new_checked(2.).unwrap_err().to_string(),
+        "invalid quantile level: 2 is not between 0 and 1"
+    );
+
+    let data = [3., 1., 4., 1., 5., 9., 2., 6.];
+    for &p in &[0., 0.25, 0.5, 1.] {
+        let mut checked = Quantile::new_checked(p).unwrap();
+        let mut converted = Quantile::try_from(p).unwrap();
+        let mut q = Quantile::new(p);
+        for &x in &data {
+            checked.add(x);
+            converted.add(x);
+            q.add(x);
+        }
+        assert_eq!(checked.p(), p);
+        assert_eq!(checked.quantile(), q.quantile());
+        assert_eq!(converted.quantile(), q.quantile());
+    }
+}
+
+#[test]
+#[should_panic]
+fn new_invalid() {
+    Quantile::new(1.5);
+}
+
+#[test]
+fn multi_quantile() {
+    use average::MultiQuantile;
+    use rand::{Rng, SeedableRng};
+
+    let ps = [0.1, 0.25, 0.5, 0.75, 0.9];
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let data: Vec<f64> = (0..100_000).map(|_| rng.gen_range(0.0..1.0)).collect();
+
+    let mut multi = MultiQuantile::new(&ps);
+    assert!(multi.is_empty());
+    assert!(multi.quantiles().iter().all(|q| q.is_nan()));
+    multi.extend(&data);
+    assert_eq!(multi.len(), 100_000);
+    for (i, &p) in ps.iter().enumerate() {
+        let mut single = Quantile::new(p);
+        for &x in &data {
+            single.add(x);
+        }
+        assert_eq!(multi.p(i), p);
+        assert_eq!(multi.quantile(i), single.quantile());
+        assert_eq!(multi.quantiles()[i], single.quantile());
+        assert!((multi.quantile(i) - p).abs() < 0.01);
+    }
+    assert_eq!(multi.estimate(), multi.quantile(2));
+}
+
+#[test]
+fn multi_quantile_default() {
+    use average::MultiQuantile;
+
+    let a: MultiQuantile = (1..=1000).map(f64::from).collect();
+    assert_eq!(a.len(), 1000);
+    for (q, expected) in a.quantiles().iter().zip(&[1., 250., 500., 750., 1000.]) {
+        assert!((q - expected).abs() <= 5.);
+    }
+    assert_eq!(a.estimate(), a.quantile(2));
+}
+
+#[test]
+fn display() {
+    let mut q = Quantile::new(0.5);
+    assert_eq!(format!("{}", q), "Quantile(p=0.5, n=0, estimate=NaN)");
+    for x in &[1., 5., 3., 2., 4.] {
+        q.add(*x);
+    }
+    assert_eq!(format!("{}", q), "Quantile(p=0.5, n=5, estimate=3)");
+    assert_eq!(format!("{:.1}", q), "Quantile(p=0.5, n=5, estimate=3.0)");
+}
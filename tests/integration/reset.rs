@@ -0,0 +1,95 @@
+use average::{
+    Covariance, Estimate, Ewma, EwmaVariance, Histogram10, Max, Mean, Min, Reset, Variance,
+    WeightedMean, WeightedMeanWithError,
+};
+
+const FIRST: &[f64] = &[10., -3., 7., 100.];
+const SECOND: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+
+/// Feed `FIRST` into a new estimator, reset it, feed `SECOND` and compare with
+/// an estimator that only saw `SECOND`.
+macro_rules! check_reset {
+    ($new:expr, $($statistic:ident),+) => {{
+        let mut a = $new;
+        for &x in FIRST {
+            a.add(x);
+        }
+        a.reset();
+        let mut b = $new;
+        for &x in SECOND {
+            a.add(x);
+            b.add(x);
+        }
+        $(
+            assert_eq!(a.$statistic(), b.$statistic());
+        )+
+    }};
+}
+
+#[test]
+fn simple() {
+    check_reset!(Mean::new(), len, mean);
+    check_reset!(Variance::new(), len, mean, sample_variance);
+    check_reset!(Min::new(), min);
+    check_reset!(Max::new(), max);
+    check_reset!(Ewma::new(0.3), len, alpha, value);
+    check_reset!(EwmaVariance::new(0.3), len, alpha, mean, variance);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn float() {
+    use average::{Kurtosis, Quantile, Skewness};
+
+    check_reset!(Skewness::new(), len, mean, skewness);
+    check_reset!(Kurtosis::new(), len, mean, kurtosis);
+    check_reset!(Quantile::new(0.75), len, quantile, p);
+}
+
+#[test]
+fn weighted() {
+    let mut a = WeightedMean::new();
+    let mut b = WeightedMeanWithError::new();
+    let mut c = Covariance::new();
+    for &x in FIRST {
+        a.add(x, 2.);
+        b.add(x, 2.);
+        c.add(x, -x);
+    }
+    a.reset();
+    b.reset();
+    c.reset();
+    assert!(a.is_empty());
+    assert!(b.is_empty());
+    assert!(c.is_empty());
+    for &x in SECOND {
+        a.add(x, x);
+        b.add(x, x);
+        c.add(x, 2. * x);
+    }
+    let a_new: WeightedMean = SECOND.iter().map(|&x| (x, x)).collect();
+    let b_new: WeightedMeanWithError = SECOND.iter().map(|&x| (x, x)).collect();
+    let c_new: Covariance = SECOND.iter().map(|&x| (x, 2. * x)).collect();
+    assert_eq!(a.mean(), a_new.mean());
+    assert_eq!(a.min_weight(), a_new.min_weight());
+    assert_eq!(b.weighted_mean(), b_new.weighted_mean());
+    assert_eq!(b.variance_of_weighted_mean(), b_new.variance_of_weighted_mean());
+    assert_eq!(c.len(), c_new.len());
+    assert_eq!(c.sample_covariance(), c_new.sample_covariance());
+}
+
+#[test]
+fn histogram() {
+    use average::Histogram as _;
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    for &x in SECOND {
+        h.add(x).unwrap();
+    }
+    h.reset();
+    assert!(h.bins().iter().all(|&c| c == 0));
+    assert_eq!(h.range_min(), 0.);
+    assert_eq!(h.range_max(), 10.);
+    h.add(3.5).unwrap();
+    assert_eq!(h.bins()[3], 1);
+}
@@ -0,0 +1,93 @@
+use average::{assert_almost_eq, Estimate, Mean, RollingMean, RollingVariance, Variance};
+
+const SEQUENCE: &[f64] = &[1., 2., -3., 4., 5.1, 7.3, -8., 9., 1e3, 1., 2., 3., 4.];
+
+#[test]
+fn rolling_mean() {
+    const N: usize = 4;
+    let mut a = RollingMean::<N>::new();
+    assert!(a.is_empty());
+    assert!(a.mean().is_nan());
+    for (i, &x) in SEQUENCE.iter().enumerate() {
+        a.add(x);
+        assert_eq!(a.is_full(), i + 1 >= N);
+        let window = &SEQUENCE[(i + 1).saturating_sub(N)..=i];
+        let expected: Mean = window.iter().collect();
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+    }
+    assert_eq!(a.mean(), 2.5);
+    assert_eq!(a.estimate(), 2.5);
+
+    let b: RollingMean<N> = SEQUENCE.iter().collect();
+    assert_eq!(b.mean(), a.mean());
+}
+
+#[test]
+fn rolling_mean_exactly_full() {
+    let a: RollingMean<5> = (1..=5).map(f64::from).collect();
+    assert!(a.is_full());
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.mean(), 3.);
+}
+
+#[test]
+fn rolling_mean_absorption() {
+    // The small numbers are absorbed when added to the running sum together
+    // with the large one, which must not affect the mean after it left.
+    let mut a = RollingMean::<3>::new();
+    a.extend(&[1e17, 1., 2., 3., 4., 5.]);
+    assert_eq!(a.mean(), 4.);
+    a.add(6.);
+    assert_eq!(a.mean(), 5.);
+}
+
+#[test]
+fn rolling_variance() {
+    const N: usize = 4;
+    let mut a = RollingVariance::<N>::new();
+    assert!(a.is_empty());
+    assert!(a.mean().is_nan());
+    assert!(a.sample_variance().is_nan());
+    for (i, &x) in SEQUENCE.iter().enumerate() {
+        a.add(x);
+        assert_eq!(a.is_full(), i + 1 >= N);
+        let window = &SEQUENCE[(i + 1).saturating_sub(N)..=i];
+        let expected: Variance = window.iter().collect();
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-9);
+        assert_almost_eq!(a.population_variance(), expected.population_variance(), 1e-9);
+        if window.len() > 1 {
+            assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-9);
+        } else {
+            // The sample variance of a single observation is NaN, which
+            // `assert_almost_eq` does not check.
+            assert!(a.sample_variance().is_nan());
+        }
+    }
+    assert_almost_eq!(a.estimate(), 5. / 3., 1e-9);
+    assert_eq!(a.estimator().len(), N as u64);
+
+    let mut b = RollingVariance::<N>::default();
+    b.extend(SEQUENCE);
+    assert_eq!(b.sample_variance(), a.sample_variance());
+}
+
+#[test]
+fn rolling_variance_absorption() {
+    // The rounding errors of removing the large number from the running
+    // moments must not affect the variance long after it left.
+    let mut a = RollingVariance::<3>::new();
+    a.add(1e9);
+    for i in 0..10_000 {
+        a.add(f64::from(i % 3));
+    }
+    assert_almost_eq!(a.mean(), 1., 1e-12);
+    assert_almost_eq!(a.sample_variance(), 1., 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "The window must not be empty")]
+fn empty_window() {
+    RollingMean::<0>::new();
+}
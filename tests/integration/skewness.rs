@@ -1,6 +1,6 @@
 use core::iter::Iterator;
 
-use average::{assert_almost_eq, Estimate, Merge, Skewness};
+use average::{assert_almost_eq, assert_estimators_eq, Estimate, Merge, Skewness};
 
 #[test]
 fn trivial() {
@@ -80,14 +80,10 @@ fn merge() {
         let mut avg_left: Skewness = left.iter().collect();
         let avg_right: Skewness = right.iter().collect();
         avg_left.merge(&avg_right);
-        assert_eq!(avg_total.len(), avg_left.len());
-        assert_almost_eq!(avg_total.mean(), avg_left.mean(), 1e-14);
-        assert_almost_eq!(
-            avg_total.sample_variance(),
-            avg_left.sample_variance(),
-            1e-14
+        assert_estimators_eq!(
+            avg_total, avg_left, 1e-14,
+            [mean, sample_variance, skewness]
         );
-        assert_almost_eq!(avg_total.skewness(), avg_left.skewness(), 1e-14);
     }
 }
 
@@ -104,3 +100,52 @@ fn merge_empty() {
     assert_eq!(left.sample_variance(), 0.);
     assert_eq!(left.skewness(), 0.);
 }
+
+#[test]
+fn sample_skewness() {
+    use average::Moments4;
+
+    let mut a = Skewness::new();
+    assert!(a.sample_skewness().is_nan());
+    a.add(1.);
+    a.add(2.);
+    assert!(a.sample_skewness().is_nan());
+
+    let a: Skewness = [1., 2., 3., 4., 5., 1.].iter().collect();
+    let n: f64 = 6.;
+    let correction = (n * (n - 1.)).sqrt() / (n - 2.);
+    assert_almost_eq!(a.skewness(), 0.27950849718747406, 1e-15);
+    assert_almost_eq!(a.sample_skewness(), 0.38273277230987207, 1e-15);
+    assert_almost_eq!(a.sample_skewness(), correction * a.skewness(), 1e-15);
+
+    let sequence: &[f64] = &[1., 2., 3., -4., 5., 6., 7., 8., 9., 1.];
+    let a: Skewness = sequence.iter().collect();
+    let m: Moments4 = sequence.iter().collect();
+    assert_almost_eq!(a.sample_skewness(), -0.5896804135963241, 1e-14);
+    assert_almost_eq!(a.sample_skewness(), m.sample_skewness(), 1e-14);
+
+    // For large samples, the correction becomes negligible.
+    let a: Skewness = (0..100_000).map(|i| f64::from(i % 100).powi(2)).collect();
+    assert_almost_eq!(a.sample_skewness(), a.skewness(), 1e-4);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn invalid_serde() {
+    let a: Skewness = [1., 2., 4.].iter().collect();
+    let json = serde_json::to_string(&a).unwrap();
+    assert!(serde_json::from_str::<Skewness>(&json).is_ok());
+
+    let err = serde_json::from_str::<Skewness>(
+        "{\"avg\":{\"avg\":{\"avg\":1.5,\"n\":2},\"sum_2\":0.5},\"sum_3\":1.0}",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("nonzero sum of cubes"), "{}", err);
+
+    // The inner variance estimator is validated as well.
+    let err = serde_json::from_str::<Skewness>(
+        "{\"avg\":{\"avg\":{\"avg\":1.5,\"n\":3},\"sum_2\":-0.5},\"sum_3\":1.0}",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("negative sum of squares"), "{}", err);
+}
@@ -0,0 +1,50 @@
+use average::{assert_almost_eq, five_number_summary, full_summary, FiveNumberSummary};
+
+#[test]
+fn five_numbers() {
+    let s = five_number_summary((1..=5).map(f64::from));
+    // Small samples are summarized exactly.
+    assert_eq!(
+        s,
+        FiveNumberSummary { min: 1., q1: 2., median: 3., q3: 4., max: 5. }
+    );
+    let s = five_number_summary([4., 1., 3., 2.].iter().cloned());
+    assert_eq!(
+        s,
+        FiveNumberSummary { min: 1., q1: 1.75, median: 2.5, q3: 3.25, max: 4. }
+    );
+    let s = five_number_summary(core::iter::once(7.));
+    assert_eq!(
+        s,
+        FiveNumberSummary { min: 7., q1: 7., median: 7., q3: 7., max: 7. }
+    );
+
+    let s = five_number_summary((0..=1000).map(f64::from));
+    assert_eq!(s.min, 0.);
+    assert_almost_eq!(s.q1, 250., 1.);
+    assert_almost_eq!(s.median, 500., 1.);
+    assert_almost_eq!(s.q3, 750., 1.);
+    assert_eq!(s.max, 1000.);
+
+    let s = five_number_summary(core::iter::empty());
+    assert!(s.min.is_nan() && s.q1.is_nan() && s.median.is_nan());
+    assert!(s.q3.is_nan() && s.max.is_nan());
+}
+
+#[test]
+fn full() {
+    let s = full_summary((1..=5).map(f64::from));
+    assert_eq!(s.five_number, five_number_summary((1..=5).map(f64::from)));
+    assert_eq!(s.n, 5);
+    assert_eq!(s.mean, 3.);
+    assert_eq!(s.sample_variance, 2.5);
+    assert_eq!(s.skewness, 0.);
+    assert_almost_eq!(s.kurtosis, -1.3, 1e-15);
+}
+
+#[test]
+fn display() {
+    let s = FiveNumberSummary { min: 1., q1: 2., median: 3., q3: 4., max: 5. };
+    assert_eq!(format!("{}", s), "1 |-[2 | 3 | 4]-| 5");
+    assert_eq!(format!("{:.1}", s), "1.0 |-[2.0 | 3.0 | 4.0]-| 5.0");
+}
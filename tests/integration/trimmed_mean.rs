@@ -0,0 +1,62 @@
+use average::{assert_almost_eq, Estimate, Mean, TrimmedMean};
+
+#[test]
+fn trivial() {
+    let mut a = TrimmedMean::new(0.1);
+    assert!(a.is_empty());
+    assert!(a.trimmed_mean().is_nan());
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.trimmed_mean(), 1.);
+    assert_eq!(a.estimate(), 1.);
+}
+
+#[test]
+fn simple() {
+    // The sequence 1..=10 in a fixed shuffled order. The bounds are estimated
+    // online, so a sorted sequence would discard every new maximum.
+    let data = [4., 9., 1., 7., 3., 10., 6., 2., 8., 5.];
+    let mut a = TrimmedMean::new(0.1);
+    a.extend(&data);
+    assert_eq!(a.alpha(), 0.1);
+    assert_eq!(a.len(), 10);
+    // The quantile estimates are too crude for such a small sample to
+    // compare against the exact trimmed mean, see `large_sample` instead.
+    assert!(0 < a.interior_len() && a.interior_len() < a.len());
+    assert!((1. ..=10.).contains(&a.trimmed_mean()));
+
+    let b: TrimmedMean = data.iter().collect();
+    assert_eq!(b.alpha(), 0.1);
+    assert_eq!(b.trimmed_mean(), a.trimmed_mean());
+}
+
+#[test]
+fn large_sample() {
+    // The sequence 1..=10000 in a fixed shuffled order. The exact trimmed
+    // mean is 5000.5. Once the bounds have converged, the estimate only
+    // deviates due to the samples added before, which is below 0.5 % here.
+    let mut a = TrimmedMean::new(0.1);
+    for i in 0..10000 {
+        a.add(f64::from((i * 3371) % 10000 + 1));
+    }
+    assert_almost_eq!(a.trimmed_mean(), 5000.5, 0.005 * 5000.5);
+}
+
+#[test]
+fn outliers() {
+    let mut a = TrimmedMean::new(0.1);
+    let mut mean = Mean::new();
+    for i in 1..=1000 {
+        let x = if i % 50 == 0 { 1e6 } else { f64::from(i % 10) };
+        a.add(x);
+        mean.add(x);
+    }
+    assert!(mean.mean() > 1e4);
+    assert_almost_eq!(a.trimmed_mean(), 5., 0.5);
+}
+
+#[test]
+#[should_panic]
+fn invalid_alpha() {
+    TrimmedMean::new(0.5);
+}
@@ -1,6 +1,6 @@
 use core::iter::Iterator;
 
-use average::{assert_almost_eq, Merge, WeightedMeanWithError};
+use average::{assert_almost_eq, Merge, Variance, WeightedMeanWithError, WeightedVariance};
 
 #[test]
 fn trivial() {
@@ -52,7 +52,13 @@ fn simple() {
 fn simple_serde() {
     let a: WeightedMeanWithError = (1..6).map(|x| (f64::from(x), 1.0)).collect();
     let b = serde_json::to_string(&a).unwrap();
-    assert_eq!(&b, "{\"weight_sum_sq\":5.0,\"weighted_avg\":{\"weight_sum\":5.0,\"weighted_avg\":3.0},\"unweighted_avg\":{\"avg\":{\"avg\":3.0,\"n\":5},\"sum_2\":10.0}}");
+    let (head, log_avg) = b.split_at(b.find(",\"log_avg\"").unwrap_or(b.len() - 1));
+    assert_eq!(head, "{\"weight_sum_sq\":5.0,\"weight_sum_log\":0.0,\"weighted_avg\":{\"weight_sum\":5.0,\"weighted_avg\":3.0,\"max_weight\":{\"x\":1.0},\"min_weight\":{\"x\":1.0}},\"unweighted_avg\":{\"avg\":{\"avg\":3.0,\"n\":5},\"sum_2\":10.0}");
+    // The logarithms are only accumulated if `ln` is available.
+    #[cfg(not(any(feature = "std", feature = "libm")))]
+    assert_eq!(log_avg, "}");
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert_eq!(log_avg, ",\"log_avg\":{\"weight_sum_sq\":5.0,\"sum_2\":1.6154889869425695,\"weighted_avg\":{\"weight_sum\":5.0,\"weighted_avg\":0.9574983485564093,\"max_weight\":{\"x\":1.0},\"min_weight\":{\"x\":1.0}}}}");
     let c: WeightedMeanWithError = serde_json::from_str(&b).unwrap();
     assert_eq!(c.len(), 5);
     assert_eq!(c.weighted_mean(), 3.0);
@@ -62,6 +68,10 @@ fn simple_serde() {
     assert_eq!(a.variance_of_weighted_mean(), 0.5);
     #[cfg(any(feature = "std", feature = "libm"))]
     assert_almost_eq!(c.error(), f64::sqrt(0.5), 1e-16);
+
+    // A state without the logarithms of the samples cannot be restored.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    assert!(serde_json::from_str::<WeightedMeanWithError>(&format!("{}}}", head)).is_err());
 }
 
 #[test]
@@ -167,3 +177,200 @@ fn merge_empty() {
     assert_eq!(left.unweighted_mean(), 1.);
     assert_eq!(left.sample_variance(), 0.);
 }
+
+#[test]
+fn into_mean() {
+    use average::{Estimate, Mean, WeightedMean};
+
+    let a: WeightedMean = (1..6).map(|x| (f64::from(x), 1.)).collect();
+    let expected = a.mean();
+    let mut m = a.into_mean();
+    assert_eq!(m.mean(), expected);
+    assert_eq!(m.len(), 5);
+    m.add(6.);
+    assert_eq!(m.mean(), 3.5);
+    assert_eq!(m.len(), 6);
+
+    assert!(WeightedMean::new().into_mean().is_empty());
+
+    let w = WeightedMean::from_mean(m);
+    assert_eq!(w.mean(), 3.5);
+    assert_eq!(w.sum_weights(), 6.);
+    assert_eq!(w.weight_range(), 0.);
+
+    // An empty estimator has no weights.
+    let empty = WeightedMean::from_mean(Mean::new());
+    assert!(empty.is_empty());
+    assert_eq!(empty.max_weight(), WeightedMean::new().max_weight());
+    assert_eq!(empty.min_weight(), WeightedMean::new().min_weight());
+    assert!(empty.weight_range().is_nan());
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn weight_entropy() {
+    assert!(WeightedMeanWithError::new().weight_entropy().is_nan());
+
+    let a: WeightedMeanWithError = (1..9).map(|x| (f64::from(x), 0.5)).collect();
+    assert_almost_eq!(a.weight_entropy(), 8f64.ln(), 1e-15);
+    assert_almost_eq!(a.normalized_weight_entropy(), 1., 1e-15);
+
+    let b: WeightedMeanWithError = [(1., 0.), (2., 3.), (3., 0.)].iter().collect();
+    assert_eq!(b.weight_entropy(), 0.);
+    assert_eq!(b.normalized_weight_entropy(), 0.);
+
+    let weights = [0.1, 0.2, 0.3, 0.4];
+    let c: WeightedMeanWithError = weights.iter().map(|&w| (1., w)).collect();
+    let expected: f64 = weights.iter().map(|&w: &f64| -w * w.ln()).sum();
+    assert_almost_eq!(c.weight_entropy(), expected, 1e-15);
+
+    let (left, right) = weights.split_at(1);
+    let mut d: WeightedMeanWithError = left.iter().map(|&w| (1., w)).collect();
+    d.merge(&right.iter().map(|&w| (1., w)).collect());
+    assert_almost_eq!(d.weight_entropy(), expected, 1e-15);
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn geometric_mean() {
+    use rand::SeedableRng;
+    use rand_distr::Distribution;
+
+    assert!(WeightedMeanWithError::new().geometric_mean().is_nan());
+
+    // exp((1 ln(1) + 2 ln(8)) / 3) = 4
+    let a: WeightedMeanWithError = [(1., 1.), (8., 2.)].iter().collect();
+    assert_almost_eq!(a.geometric_mean(), 4., 1e-14);
+    let b: WeightedMeanWithError = [(1., 1.), (1., 2.), (8., 4.)].iter().collect();
+    let log_mean = 4. * 8f64.ln() / 7.;
+    let log_sum_2 = 3. * log_mean * log_mean + 4. * (8f64.ln() - log_mean).powi(2);
+    assert_almost_eq!(b.log_variance(), log_sum_2 / (7. - 21. / 7.), 1e-14);
+
+    // Samples that are zero yield NaN, wherever they are in the sequence.
+    for data in &[
+        &[(0., 1.), (2., 1.)][..],
+        &[(2., 1.), (0., 1.), (3., 1.)][..],
+        &[(2., 1.), (0., 1.)][..],
+    ] {
+        let z: WeightedMeanWithError = data.iter().collect();
+        assert!(z.geometric_mean().is_nan());
+        assert!(z.log_variance().is_nan());
+    }
+
+    let mu = 1.5;
+    let sigma = 0.5;
+    let distribution = rand_distr::LogNormal::new(mu, sigma).unwrap();
+    let weights = rand_distr::Uniform::new(0.5, 2.);
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let samples: Vec<(f64, f64)> = (0..100_000)
+        .map(|_| (distribution.sample(&mut rng), weights.sample(&mut rng)))
+        .collect();
+    let c: WeightedMeanWithError = samples.iter().collect();
+    assert_almost_eq!(c.geometric_mean(), mu.exp(), 0.01 * mu.exp());
+    assert_almost_eq!(c.log_variance(), sigma * sigma, 0.01);
+
+    let (left, right) = samples.split_at(30_000);
+    let mut d: WeightedMeanWithError = left.iter().collect();
+    d.merge(&right.iter().collect());
+    assert_almost_eq!(d.geometric_mean(), c.geometric_mean(), 1e-12);
+    assert_almost_eq!(d.log_variance(), c.log_variance(), 1e-12);
+}
+
+#[test]
+fn extreme_weights() {
+    use average::WeightedMean;
+
+    let empty = WeightedMean::new();
+    assert_eq!(empty.max_weight(), f64::NEG_INFINITY);
+    assert_eq!(empty.min_weight(), f64::INFINITY);
+    assert!(empty.weight_range().is_nan());
+
+    let a: WeightedMean = [(1., 0.1), (2., 0.5), (3., 2.0), (4., 0.3)].iter().collect();
+    assert_eq!(a.max_weight(), 2.0);
+    assert_eq!(a.min_weight(), 0.1);
+    assert_eq!(a.weight_range(), 1.9);
+
+    let mut left: WeightedMean = [(1., 0.1), (2., 0.5)].iter().collect();
+    let right: WeightedMean = [(3., 2.0), (4., 0.3)].iter().collect();
+    left.merge(&right);
+    assert_eq!(left.max_weight(), 2.0);
+    assert_eq!(left.min_weight(), 0.1);
+
+    let mut e = WeightedMean::new();
+    e.merge(&a);
+    assert_eq!(e.max_weight(), 2.0);
+    assert_eq!(e.min_weight(), 0.1);
+    assert_eq!(e.mean(), a.mean());
+}
+
+#[test]
+fn weighted_variance_equal_weights() {
+    let data = [1., 5., 2., 9., 4., 6.];
+    let var: Variance = data.iter().collect();
+    for &w in &[1., 0.5, 3.] {
+        let a: WeightedVariance = data.iter().map(|&x| (x, w)).collect();
+        assert_almost_eq!(a.weighted_mean(), var.mean(), 1e-14);
+        assert_almost_eq!(a.weighted_population_variance(), var.population_variance(), 1e-14);
+        assert_almost_eq!(a.weighted_sample_variance(), var.sample_variance(), 1e-14);
+    }
+}
+
+#[test]
+fn weighted_variance_reference() {
+    let data = [(1., 0.5), (2., 1.), (4., 2.), (8., 0.5)];
+    let a: WeightedVariance = data.iter().collect();
+    let w: f64 = data.iter().map(|&(_, w)| w).sum();
+    let w2: f64 = data.iter().map(|&(_, w)| w * w).sum();
+    let mean = data.iter().map(|&(x, w)| w * x).sum::<f64>() / w;
+    let sum_2: f64 = data.iter().map(|&(x, w)| w * (x - mean) * (x - mean)).sum();
+    assert_eq!(a.sum_weights(), w);
+    assert_almost_eq!(a.weighted_mean(), mean, 1e-14);
+    assert_almost_eq!(a.weighted_population_variance(), sum_2 / w, 1e-14);
+    assert_almost_eq!(a.weighted_sample_variance(), sum_2 / (w - w2 / w), 1e-14);
+}
+
+#[test]
+fn weighted_variance_corner_cases() {
+    let mut a = WeightedVariance::new();
+    assert!(a.is_empty());
+    assert!(a.weighted_mean().is_nan());
+    assert!(a.weighted_population_variance().is_nan());
+    assert!(a.weighted_sample_variance().is_nan());
+    a.add(2., 3.);
+    assert_eq!(a.weighted_mean(), 2.);
+    assert_eq!(a.weighted_population_variance(), 0.);
+    assert!(a.weighted_sample_variance().is_nan());
+    a.add(5., 0.);
+    assert!(a.weighted_sample_variance().is_nan());
+
+    // `w - w * w / w` is not zero for this weight due to rounding errors.
+    let mut a = WeightedVariance::new();
+    a.add(2., 6.435138486891349);
+    assert!(a.weighted_sample_variance().is_nan());
+}
+
+#[test]
+fn weighted_variance_merge() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+        (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9),
+    ];
+    let total: WeightedVariance = sequence.iter().collect();
+    for mid in 0..=sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut merged: WeightedVariance = left.iter().collect();
+        merged.merge(&right.iter().collect());
+        assert_almost_eq!(merged.sum_weights(), total.sum_weights(), 1e-14);
+        assert_almost_eq!(merged.weighted_mean(), total.weighted_mean(), 1e-14);
+        assert_almost_eq!(
+            merged.weighted_population_variance(),
+            total.weighted_population_variance(),
+            1e-14
+        );
+        assert_almost_eq!(
+            merged.weighted_sample_variance(),
+            total.weighted_sample_variance(),
+            1e-14
+        );
+    }
+}